@@ -40,6 +40,28 @@ pub struct Union {
   pub variants: std::collections::BTreeMap<String, std::rc::Rc<UnionVariant>>,
 }
 
+impl Union {
+  /// Find the declared payload type of the variant with the given id.
+  ///
+  /// Only variants carrying a value have a declared payload type; this will
+  /// return `None` for string and singleton variants, or if the variant does
+  /// not belong to this union.
+  pub(crate) fn variant_payload_type(
+    &self,
+    variant_id: &symbol_table::RegistryId,
+  ) -> Option<&types::Type> {
+    let variant = self
+      .variants
+      .values()
+      .find(|variant| variant.registry_id == *variant_id)?;
+
+    match &variant.kind {
+      UnionVariantKind::Type(ty) => Some(ty),
+      _ => None,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub enum UnionInstanceValue {
   Singleton(String),
@@ -65,7 +87,9 @@ pub struct Match {
   pub subject_type_id: symbol_table::TypeId,
   pub subject: Expr,
   pub arms: Vec<MatchArm>,
-  pub default_case: Expr,
+  /// The body of the wildcard (`_`) arm, if any. Without it, the arms must
+  /// cover every variant of the subject's union type.
+  pub default_case: Option<Expr>,
 }
 
 #[derive(Debug)]
@@ -84,6 +108,22 @@ pub struct Generics {
 #[derive(Debug)]
 pub struct Group(pub Expr);
 
+/// A name bound to a part of a destructured value.
+#[derive(Debug, Clone)]
+pub struct PatternBinding {
+  pub name: String,
+  pub type_id: symbol_table::TypeId,
+}
+
+/// A destructuring pattern, which binds parts of a value to names.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+  /// Binds each element of a tuple, by position.
+  Tuple(Vec<PatternBinding>),
+  /// Binds fields of an object, by field name.
+  Object(Vec<(String, PatternBinding)>),
+}
+
 #[derive(Debug, Clone)]
 pub struct Parameter {
   pub registry_id: symbol_table::RegistryId,
@@ -91,6 +131,11 @@ pub struct Parameter {
   pub name: String,
   pub position: parser::LlvmSize,
   pub type_hint: Option<types::Type>,
+  /// The pattern used to destructure the parameter's value, if any.
+  ///
+  /// The parameter's name still refers to the whole value.
+  // TODO: Parse destructuring patterns, and declare & lower their sub-bindings.
+  pub pattern: Option<Pattern>,
 }
 
 #[derive(Debug, Clone)]
@@ -325,9 +370,20 @@ pub struct Import {
   pub module_name: String,
 }
 
+/// Determines how the target of a closure capture is stored within the
+/// closure's environment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureMode {
+  /// The target's value is copied into the closure's environment.
+  ByValue,
+  /// Only a reference to the target is stored in the closure's environment.
+  ByReference,
+}
+
 #[derive(Debug, Clone)]
 pub struct ClosureCapture {
   pub name: String,
+  pub mode: CaptureMode,
   pub registry_id: symbol_table::RegistryId,
   pub closure_registry_id: symbol_table::RegistryId,
   pub index: u32,
@@ -353,6 +409,7 @@ pub struct ForeignCluster {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct Path {
   pub link_id: symbol_table::LinkId,
   /// The basic resolution details for this path.
@@ -371,6 +428,25 @@ pub struct Path {
   pub symbol_kind: symbol_table::SymbolKind,
 }
 
+impl std::fmt::Display for Path {
+  fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if let Some(qualifier) = &self.qualifier {
+      formatter.write_fmt(format_args!(
+        "{}::{}::",
+        qualifier.package_name, qualifier.module_name
+      ))?;
+    }
+
+    formatter.write_str(&self.base_name)?;
+
+    if let Some(sub_name) = &self.sub_name {
+      formatter.write_fmt(format_args!("::{}", sub_name))?;
+    }
+
+    Ok(())
+  }
+}
+
 #[derive(Debug)]
 pub struct Object {
   pub type_id: symbol_table::TypeId,
@@ -410,6 +486,14 @@ impl Reference {
   }
 }
 
+/// A `typeof` type expression, which evaluates to the type of its
+/// inner expression.
+#[derive(Debug, Clone)]
+pub struct Typeof {
+  pub type_id: symbol_table::TypeId,
+  pub expr: Expr,
+}
+
 #[derive(Debug)]
 pub struct Sizeof {
   pub ty: types::Type,
@@ -553,6 +637,11 @@ pub struct Binding {
   /// Only for use during type inference; all later stages should rely
   /// on a type environment to resolve the type of this AST node instead.
   pub type_hint: Option<types::Type>,
+  /// The pattern used to destructure the binding's value, if any.
+  ///
+  /// The binding's name still refers to the whole value.
+  // TODO: Parse destructuring patterns, and declare & lower their sub-bindings.
+  pub pattern: Option<Pattern>,
 }
 
 #[derive(Debug)]
@@ -594,11 +683,25 @@ impl Callable {
     }
   }
 
-  pub(crate) fn get_registry_id(&self) -> symbol_table::RegistryId {
+  /// Retrieve the signature of the callable's declaration.
+  pub(crate) fn get_signature(&self) -> &Signature {
+    match self {
+      Callable::ForeignFunction(foreign_function) => &foreign_function.signature,
+      Callable::Function(function) => &function.signature,
+      Callable::Closure(closure) => &closure.signature,
+    }
+  }
+
+  /// Retrieve the registry id of the callable's declaration, if it is a
+  /// named callable.
+  ///
+  /// Closures are unnamed, thus their registry id identifies the closure
+  /// expression rather than a declaration, and `None` is returned instead.
+  pub(crate) fn get_registry_id(&self) -> Option<symbol_table::RegistryId> {
     match self {
-      Callable::ForeignFunction(foreign_function) => foreign_function.registry_id,
-      Callable::Function(function) => function.registry_id,
-      Callable::Closure(closure) => closure.registry_id,
+      Callable::ForeignFunction(foreign_function) => Some(foreign_function.registry_id),
+      Callable::Function(function) => Some(function.registry_id),
+      Callable::Closure(_) => None,
     }
   }
 }
@@ -22,9 +22,6 @@ pub(crate) const BUG_BUFFER_CONTRACT: &str =
 pub(crate) const BUG_MISSING_TYPE: &str =
   "item or type id should have a corresponding associated type in the type environment";
 
-pub(crate) const BUG_FOREIGN_FN_TYPE_HINTS: &str =
-  "foreign functions should always provide full signature type hints";
-
 pub(crate) const MISSING_SYMBOL_TABLE_ENTRY: &str =
   "a required entry is missing on the provided symbol table";
 
@@ -44,6 +41,25 @@ impl IdGenerator {
     self.counter
   }
 
+  /// Capture the current state of the counter, so that it may be
+  /// later restored by another generator.
+  pub fn checkpoint(&self) -> usize {
+    self.counter
+  }
+
+  /// Advance the counter to a previously captured checkpoint.
+  ///
+  /// The counter should never move backwards, otherwise ids that were
+  /// already issued would be issued again.
+  pub fn restore(&mut self, checkpoint: usize) {
+    debug_assert!(
+      checkpoint >= self.counter,
+      "restoring the id generator should never cause already issued ids to be issued again"
+    );
+
+    self.counter = checkpoint;
+  }
+
   /// Create a unique, non-repeating identifier based from a counter.
   ///
   /// This is used to create unique ids for AST node fields and identifiers.
@@ -284,3 +300,30 @@ pub(crate) fn join_two_opts<'a, A, B>(
     _ => None,
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn id_generator_restore() {
+    let mut id_generator = IdGenerator::default();
+    let mut other_id_generator = IdGenerator::default();
+
+    id_generator.next();
+    id_generator.next();
+    other_id_generator.restore(id_generator.checkpoint());
+
+    assert_eq!(2, other_id_generator.next());
+  }
+
+  #[test]
+  #[should_panic]
+  fn id_generator_restore_backwards() {
+    let mut id_generator = IdGenerator::default();
+    let checkpoint = id_generator.checkpoint();
+
+    id_generator.next();
+    id_generator.restore(checkpoint);
+  }
+}
@@ -222,7 +222,7 @@ impl<'a> visit::Visitor for DeclarationContext {
       if !stub_type.generic_hints.is_empty() {
         self.symbol_table.artifacts.insert(
           stub_type.universe_id.to_owned(),
-          instantiation::Artifact::StubType(stub_type.to_owned()),
+          instantiation::Artifact::StubType(stub_type.as_ref().to_owned()),
         );
       }
     }
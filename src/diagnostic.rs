@@ -2,7 +2,7 @@
 //! problem that might arise (except for logic bugs, or internal errors). In other words,
 //! diagnostics can be seen as the compiler's approach to error handling and reporting.
 
-use crate::{pass, symbol_table, types};
+use crate::{inference, pass, symbol_table, types};
 
 /// A function that may produce multiple diagnostics which are visible to the
 /// end user, in the case of its failure.
@@ -14,7 +14,8 @@ use crate::{pass, symbol_table, types};
 pub type Maybe<T = ()> = Result<T, Vec<Diagnostic>>;
 
 // REVISE: Expand certain variants into objects with field names if they have two or more fields. This is for code readability and clarity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum Diagnostic {
   FunctionMissingGenericHints(String),
   ReturnTypeHintRequired,
@@ -33,6 +34,29 @@ pub enum Diagnostic {
   SignaturesDifferInParameterCount(usize, usize),
   ObjectTypeMismatch,
   TypeMismatch(types::Type, types::Type),
+  ExpectedUnionType(types::Type),
+  /// An open object type could not be finalized into a closed object type,
+  /// because the types of its fields could not be fully determined.
+  AmbiguousObjectType(types::Type),
+  /// A union whose values are used as a discriminant (ie. as the subject of
+  /// a match expression) has a variant that is not represented by an integer.
+  NonIntegerUnionDiscriminant {
+    union_name: String,
+    variant_name: String,
+  },
+  /// A reference was used where a pointer was expected, or vice versa.
+  ///
+  /// References (produced by the reference-of operator) and pointers (expected
+  /// by the dereference operator) are distinct types.
+  ReferencePointerMismatch {
+    found_reference: bool,
+  },
+  /// The constraints to be solved contain more type variables than the
+  /// configured limit allows.
+  TooManyTypeVariables {
+    count: usize,
+    limit: usize,
+  },
   TargetFieldDoesNotExist(String),
   /// A type variable could not be solved, and it suggests that type annotations
   /// might be needed.
@@ -44,6 +68,9 @@ pub enum Diagnostic {
   ExpectedButGotToken(String, String),
   UnexpectedlyReachedEndOfFile,
   NumberLiteralTooBig,
+  /// A real number literal was given an integer type hint, which would
+  /// otherwise silently truncate its value.
+  RealLiteralWithIntegerTypeHint(types::Type),
   InvalidEscapeSequence(char),
   CannotUseOutsideUnsafe,
   Redefinition(String),
@@ -60,7 +87,10 @@ pub enum Diagnostic {
   InvalidIndexingTarget,
   CannotYieldTemporaryReference,
   BindingUsedAfterMove(String),
-  TuplesDifferInLength,
+  TupleArityMismatch {
+    expected: usize,
+    actual: usize,
+  },
   UnionTypesDiffer,
   NestedUnsafeScopes,
   ConditionOrValueIsConstant,
@@ -82,21 +112,474 @@ pub enum Diagnostic {
   ConstantValueNotConstant,
   CountOrSizeTooLarge,
   RepeatedObjectField(String),
+  NonExhaustiveMatch {
+    union_name: String,
+    missing_variants: Vec<String>,
+  },
+  ArrayIndexOutOfBounds {
+    index: u64,
+    array_length: u64,
+  },
+  FunctionPointerCastOutsideUnsafe,
+  NumberKindMismatch(types::Type, types::Type),
+  ModuloOperandMustBeSignedInteger(types::Type),
+  OpenObjectTypeNotClosed(types::Type),
+  UnexpectedUnit {
+    expected: types::Type,
+  },
+  /// A foreign function's signature lacks a type hint, either on the named
+  /// parameter, or on its return type if no parameter name is given.
+  ForeignFunctionMissingTypeHint {
+    function_name: String,
+    parameter_name: Option<String>,
+  },
+  SizeofOperandUnsized(types::Type),
+  SolveBudgetExceeded {
+    limit: usize,
+  },
+  VariadicArgumentPromoted {
+    from: types::Type,
+    to: types::Type,
+  },
+  VariadicPointerDecayed(types::Type),
+  /// A match expression without a default case has a subject whose cases
+  /// cannot be enumerated (ie. it is not a union).
+  MatchMissingDefaultCase(types::Type),
+  DivisionOperandMustBeNumeric(types::Type),
+  /// Same as `SignaturesDifferInParameterCount`, but at least one of the
+  /// signatures takes or returns a function, which is a common source of
+  /// arity mistakes (ie. passing a callback of the wrong arity).
+  HigherOrderSignaturesDifferInParameterCount(usize, usize),
+  ObjectFieldTypeMismatch {
+    field_name: String,
+    expected: types::Type,
+    actual: types::Type,
+  },
+  /// The length of an array type exceeds the maximum length that can be
+  /// lowered, since LLVM represents array lengths as 32-bit integers.
+  ArrayTooLarge(u64),
+  /// A diagnostic produced while solving a constraint, along with the
+  /// construct that introduced such constraint.
+  ConstraintOriginated {
+    origin: inference::ConstraintOrigin,
+    /// The type id of the node whose type was constrained, if any, which
+    /// locates the diagnostic in the source code.
+    type_id: Option<symbol_table::TypeId>,
+    diagnostic: Box<Diagnostic>,
+  },
 }
 
 impl Diagnostic {
   pub fn is_warning(&self) -> bool {
-    matches!(
-      self,
-      Diagnostic::RedundantCast
-        | Diagnostic::NestedUnsafeScopes
-        | Diagnostic::ConditionOrValueIsConstant
-    )
+    match self {
+      Diagnostic::ConstraintOriginated { diagnostic, .. } => diagnostic.is_warning(),
+      _ => matches!(
+        self,
+        Diagnostic::RedundantCast
+          | Diagnostic::NestedUnsafeScopes
+          | Diagnostic::ConditionOrValueIsConstant
+          | Diagnostic::VariadicArgumentPromoted { .. }
+          | Diagnostic::VariadicPointerDecayed(..)
+      ),
+    }
   }
 
   pub fn is_error(&self) -> bool {
     !self.is_warning()
   }
+
+  /// Determine the stable code which uniquely identifies this kind of
+  /// diagnostic.
+  ///
+  /// Diagnostics which wrap other diagnostics take on the code of the
+  /// wrapped diagnostic.
+  // NOTE: Codes must never be changed or reused, since external tooling may
+  // rely on them. New variants should be assigned the next available code.
+  pub fn code(&self) -> &'static str {
+    match self {
+      Diagnostic::FunctionMissingGenericHints(..) => "E0001",
+      Diagnostic::ReturnTypeHintRequired => "E0002",
+      Diagnostic::ClosureCaptureAfterParameters => "E0003",
+      Diagnostic::ParameterTypeHintRequired(..) => "E0004",
+      Diagnostic::NonAsciiCharactersNotSupported(..) => "E0005",
+      Diagnostic::CalleeCannotAcceptGenericHints(..) => "E0006",
+      Diagnostic::RecursiveType(..) => "E0007",
+      Diagnostic::IntersectionOfClosedObjectsIsIncomplete(..) => "E0008",
+      Diagnostic::GenericParameterCountMismatch { .. } => "E0009",
+      Diagnostic::OpaquePointerMustBeCasted => "E0010",
+      Diagnostic::ConstructionOfInfiniteType => "E0011",
+      Diagnostic::SignaturesDifferInParameterCount(..) => "E0012",
+      Diagnostic::ObjectTypeMismatch => "E0013",
+      Diagnostic::TypeMismatch(..) => "E0014",
+      Diagnostic::ExpectedUnionType(..) => "E0015",
+      Diagnostic::AmbiguousObjectType(..) => "E0016",
+      Diagnostic::NonIntegerUnionDiscriminant { .. } => "E0017",
+      Diagnostic::ReferencePointerMismatch { .. } => "E0018",
+      Diagnostic::TooManyTypeVariables { .. } => "E0019",
+      Diagnostic::TargetFieldDoesNotExist(..) => "E0020",
+      Diagnostic::UnsolvedTypeVariable(..) => "E0021",
+      Diagnostic::FunctionsCannotBeVariadic(..) => "E0022",
+      Diagnostic::ExpectedButGotCharacter(..) => "E0023",
+      Diagnostic::MainFunctionSignatureMismatch => "E0024",
+      Diagnostic::RangeStartMustBeLessOrEqualToEnd(..) => "E0025",
+      Diagnostic::ExpectedButGotToken(..) => "E0026",
+      Diagnostic::UnexpectedlyReachedEndOfFile => "E0027",
+      Diagnostic::NumberLiteralTooBig => "E0028",
+      Diagnostic::RealLiteralWithIntegerTypeHint(..) => "E0029",
+      Diagnostic::InvalidEscapeSequence(..) => "E0030",
+      Diagnostic::CannotUseOutsideUnsafe => "E0031",
+      Diagnostic::Redefinition(..) => "E0032",
+      Diagnostic::Redeclaration(..) => "E0033",
+      Diagnostic::QualifiedSymbolNotFound(..) => "E0034",
+      Diagnostic::MultipleEntryPoints => "E0035",
+      Diagnostic::UndefinedReference(..) => "E0036",
+      Diagnostic::InvalidCastType => "E0037",
+      Diagnostic::RedundantCast => "E0038",
+      Diagnostic::UnexpectedEndOfInputExpectedChar => "E0039",
+      Diagnostic::ObjectsDifferInFieldCount => "E0040",
+      Diagnostic::ObjectsDifferInFieldName => "E0041",
+      Diagnostic::FunctionBodyMustYield(..) => "E0042",
+      Diagnostic::InvalidIndexingTarget => "E0043",
+      Diagnostic::CannotYieldTemporaryReference => "E0044",
+      Diagnostic::BindingUsedAfterMove(..) => "E0045",
+      Diagnostic::TupleArityMismatch { .. } => "E0046",
+      Diagnostic::UnionTypesDiffer => "E0047",
+      Diagnostic::NestedUnsafeScopes => "E0048",
+      Diagnostic::ConditionOrValueIsConstant => "E0049",
+      Diagnostic::BlocksMustHaveAtLeastOneStatement => "E0050",
+      Diagnostic::MissingEntryPoint => "E0051",
+      Diagnostic::LifetimeViolation { .. } => "E0052",
+      Diagnostic::FunctionTakesNoGenericParameters(..) => "E0053",
+      Diagnostic::UnusedValueMustBeUsedOrDiscarded => "E0054",
+      Diagnostic::TupleAccessOutOfBounds { .. } => "E0055",
+      Diagnostic::ObjectFieldCountMismatch(..) => "E0056",
+      Diagnostic::ObjectFieldDoesNotExist(..) => "E0057",
+      Diagnostic::ConstantValueNotConstant => "E0058",
+      Diagnostic::CountOrSizeTooLarge => "E0059",
+      Diagnostic::RepeatedObjectField(..) => "E0060",
+      Diagnostic::NonExhaustiveMatch { .. } => "E0061",
+      Diagnostic::ArrayIndexOutOfBounds { .. } => "E0062",
+      Diagnostic::FunctionPointerCastOutsideUnsafe => "E0063",
+      Diagnostic::NumberKindMismatch(..) => "E0064",
+      Diagnostic::ModuloOperandMustBeSignedInteger(..) => "E0065",
+      Diagnostic::OpenObjectTypeNotClosed(..) => "E0066",
+      Diagnostic::UnexpectedUnit { .. } => "E0067",
+      Diagnostic::ForeignFunctionMissingTypeHint { .. } => "E0068",
+      Diagnostic::SizeofOperandUnsized(..) => "E0069",
+      Diagnostic::SolveBudgetExceeded { .. } => "E0070",
+      Diagnostic::VariadicArgumentPromoted { .. } => "E0071",
+      Diagnostic::VariadicPointerDecayed(..) => "E0072",
+      Diagnostic::MatchMissingDefaultCase(..) => "E0073",
+      Diagnostic::DivisionOperandMustBeNumeric(..) => "E0074",
+      Diagnostic::HigherOrderSignaturesDifferInParameterCount(..) => "E0075",
+      Diagnostic::ObjectFieldTypeMismatch { .. } => "E0076",
+      Diagnostic::ArrayTooLarge(..) => "E0077",
+      Diagnostic::ConstraintOriginated { diagnostic, .. } => diagnostic.code(),
+    }
+  }
+
+  fn message(&self) -> String {
+    match self {
+      Diagnostic::FunctionMissingGenericHints(name) => {
+        format!("function `{}` is missing generic hints", name)
+      }
+      Diagnostic::ReturnTypeHintRequired => "a return type hint is required".to_string(),
+      Diagnostic::ClosureCaptureAfterParameters => {
+        "closure captures must come before the parameters".to_string()
+      }
+      Diagnostic::ParameterTypeHintRequired(name) => {
+        format!("parameter `{}` requires a type hint", name)
+      }
+      Diagnostic::NonAsciiCharactersNotSupported(character) => {
+        format!("non-ASCII character `{}` is not supported", character)
+      }
+      Diagnostic::CalleeCannotAcceptGenericHints(name) => {
+        format!("callee `{}` cannot accept generic hints", name)
+      }
+      // Stub types are shown as the path that was written, rather than
+      // their internal representation.
+      Diagnostic::RecursiveType(types::Type::Stub(stub_type)) => {
+        format!("type `{}` is recursive", stub_type.path)
+      }
+      Diagnostic::RecursiveType(ty) => format!("type `{:?}` is recursive", ty),
+      Diagnostic::IntersectionOfClosedObjectsIsIncomplete(a, b) => format!(
+        "intersection of closed objects with {} and {} fields is incomplete",
+        a, b
+      ),
+      Diagnostic::GenericParameterCountMismatch { expected, actual } => format!(
+        "expected {} generic parameters, but got {}",
+        expected, actual
+      ),
+      Diagnostic::OpaquePointerMustBeCasted => {
+        "opaque pointers must be casted before use".to_string()
+      }
+      Diagnostic::ConstructionOfInfiniteType => "construction of an infinite type".to_string(),
+      Diagnostic::SignaturesDifferInParameterCount(a, b) => {
+        format!("signatures differ in parameter count: {} vs. {}", a, b)
+      }
+      Diagnostic::ObjectTypeMismatch => "object types do not match".to_string(),
+      Diagnostic::TypeMismatch(expected, actual) => format!(
+        "type mismatch: expected `{:?}`, but got `{:?}`",
+        expected, actual
+      ),
+      Diagnostic::ExpectedUnionType(ty) => format!("expected a union type, but got `{:?}`", ty),
+      Diagnostic::AmbiguousObjectType(ty) => format!("object type `{:?}` is ambiguous", ty),
+      Diagnostic::NonIntegerUnionDiscriminant {
+        union_name,
+        variant_name,
+      } => format!(
+        "variant `{}` of union `{}` is not represented by an integer",
+        variant_name, union_name
+      ),
+      Diagnostic::ReferencePointerMismatch { found_reference } => {
+        if *found_reference {
+          "expected a pointer, but got a reference".to_string()
+        } else {
+          "expected a reference, but got a pointer".to_string()
+        }
+      }
+      Diagnostic::TooManyTypeVariables { count, limit } => format!(
+        "too many type variables: {} exceeds the limit of {}",
+        count, limit
+      ),
+      Diagnostic::TargetFieldDoesNotExist(name) => {
+        format!("target field `{}` does not exist", name)
+      }
+      Diagnostic::UnsolvedTypeVariable(_, debug_name) => format!(
+        "could not solve type variable `{}`; type annotations might be needed",
+        debug_name
+      ),
+      Diagnostic::FunctionsCannotBeVariadic(name) => {
+        format!("function `{}` cannot be variadic", name)
+      }
+      Diagnostic::ExpectedButGotCharacter(expected, actual) => {
+        format!("expected character `{}`, but got `{}`", expected, actual)
+      }
+      Diagnostic::MainFunctionSignatureMismatch => {
+        "the main function's signature does not match the expected signature".to_string()
+      }
+      Diagnostic::RangeStartMustBeLessOrEqualToEnd(start, end) => format!(
+        "range start {} must be less than or equal to its end {}",
+        start, end
+      ),
+      Diagnostic::ExpectedButGotToken(expected, actual) => {
+        format!("expected {}, but got {}", expected, actual)
+      }
+      Diagnostic::UnexpectedlyReachedEndOfFile => {
+        "unexpectedly reached the end of the file".to_string()
+      }
+      Diagnostic::NumberLiteralTooBig => "number literal is too big".to_string(),
+      Diagnostic::RealLiteralWithIntegerTypeHint(ty) => format!(
+        "real number literal cannot have integer type hint `{:?}`",
+        ty
+      ),
+      Diagnostic::InvalidEscapeSequence(character) => {
+        format!("invalid escape sequence `\\{}`", character)
+      }
+      Diagnostic::CannotUseOutsideUnsafe => "cannot be used outside of an unsafe scope".to_string(),
+      Diagnostic::Redefinition(name) => format!("redefinition of `{}`", name),
+      Diagnostic::Redeclaration(path) => format!("redeclaration of `{}`", path),
+      Diagnostic::QualifiedSymbolNotFound(path) => {
+        format!("qualified symbol `{}` was not found", path)
+      }
+      Diagnostic::MultipleEntryPoints => "multiple entry points were defined".to_string(),
+      Diagnostic::UndefinedReference(name) => format!("undefined reference to `{}`", name),
+      Diagnostic::InvalidCastType => "invalid cast type".to_string(),
+      Diagnostic::RedundantCast => "redundant cast".to_string(),
+      Diagnostic::UnexpectedEndOfInputExpectedChar => {
+        "unexpected end of input; expected a character".to_string()
+      }
+      Diagnostic::ObjectsDifferInFieldCount => "objects differ in field count".to_string(),
+      Diagnostic::ObjectsDifferInFieldName => "objects differ in field names".to_string(),
+      Diagnostic::FunctionBodyMustYield(name) => {
+        format!("the body of function `{}` must yield a value", name)
+      }
+      Diagnostic::InvalidIndexingTarget => "invalid indexing target".to_string(),
+      Diagnostic::CannotYieldTemporaryReference => {
+        "cannot yield a reference to a temporary value".to_string()
+      }
+      Diagnostic::BindingUsedAfterMove(name) => {
+        format!("binding `{}` was used after being moved", name)
+      }
+      Diagnostic::TupleArityMismatch { expected, actual } => format!(
+        "expected a tuple of {} elements, but got {}",
+        expected, actual
+      ),
+      Diagnostic::UnionTypesDiffer => "union types differ".to_string(),
+      Diagnostic::NestedUnsafeScopes => "unsafe scopes are nested".to_string(),
+      Diagnostic::ConditionOrValueIsConstant => "condition or value is constant".to_string(),
+      Diagnostic::BlocksMustHaveAtLeastOneStatement => {
+        "blocks must have at least one statement".to_string()
+      }
+      Diagnostic::MissingEntryPoint => "missing entry point".to_string(),
+      Diagnostic::LifetimeViolation { .. } => {
+        "binding was used outside of its lifetime".to_string()
+      }
+      Diagnostic::FunctionTakesNoGenericParameters(name) => {
+        format!("function `{}` takes no generic parameters", name)
+      }
+      Diagnostic::UnusedValueMustBeUsedOrDiscarded => {
+        "unused value must be used or discarded".to_string()
+      }
+      Diagnostic::TupleAccessOutOfBounds {
+        index,
+        tuple_length,
+      } => format!(
+        "tuple index {} is out of bounds for a tuple of {} elements",
+        index, tuple_length
+      ),
+      Diagnostic::ObjectFieldCountMismatch(expected, actual) => {
+        format!("expected {} object fields, but got {}", expected, actual)
+      }
+      Diagnostic::ObjectFieldDoesNotExist(name) => {
+        format!("object field `{}` does not exist", name)
+      }
+      Diagnostic::ConstantValueNotConstant => "constant value is not constant".to_string(),
+      Diagnostic::CountOrSizeTooLarge => "count or size is too large".to_string(),
+      Diagnostic::RepeatedObjectField(name) => format!("object field `{}` is repeated", name),
+      Diagnostic::NonExhaustiveMatch {
+        union_name,
+        missing_variants,
+      } => format!(
+        "match on union `{}` is not exhaustive; missing variants: {}",
+        union_name,
+        missing_variants.join(", ")
+      ),
+      Diagnostic::ArrayIndexOutOfBounds {
+        index,
+        array_length,
+      } => format!(
+        "array index {} is out of bounds for an array of {} elements",
+        index, array_length
+      ),
+      Diagnostic::FunctionPointerCastOutsideUnsafe => {
+        "casting a data pointer into a function pointer must occur within an unsafe scope"
+          .to_string()
+      }
+      Diagnostic::NumberKindMismatch(expected, actual) => format!(
+        "integer and real types cannot be used interchangeably: expected `{:?}`, but got `{:?}`",
+        expected, actual
+      ),
+      Diagnostic::ModuloOperandMustBeSignedInteger(ty) => format!(
+        "modulo operands must be signed integers, but got `{:?}`",
+        ty
+      ),
+      Diagnostic::OpenObjectTypeNotClosed(ty) => format!(
+        "object type `{:?}` must be unified with a closed object type",
+        ty
+      ),
+      Diagnostic::UnexpectedUnit { expected } => format!(
+        "expected a value of type `{:?}`, but got unit; a statement may have been used where a value was expected",
+        expected
+      ),
+      Diagnostic::ForeignFunctionMissingTypeHint {
+        function_name,
+        parameter_name: Some(parameter_name),
+      } => format!(
+        "foreign function `{}` is missing a type hint on parameter `{}`; foreign functions must fully annotate their signature",
+        function_name, parameter_name
+      ),
+      Diagnostic::ForeignFunctionMissingTypeHint {
+        function_name,
+        parameter_name: None,
+      } => format!(
+        "foreign function `{}` is missing a return type hint; foreign functions must fully annotate their signature",
+        function_name
+      ),
+      Diagnostic::SizeofOperandUnsized(ty) => format!(
+        "cannot determine the size of type `{:?}`, as it has no known layout",
+        ty
+      ),
+      Diagnostic::SolveBudgetExceeded { limit } => format!(
+        "solving type constraints exceeded the limit of {} unification steps",
+        limit
+      ),
+      Diagnostic::VariadicArgumentPromoted { from, to } => format!(
+        "variadic argument of type `{:?}` will be promoted to type `{:?}`",
+        from, to
+      ),
+      Diagnostic::VariadicPointerDecayed(ty) => format!(
+        "variadic argument of pointer type `{:?}` will decay into an opaque pointer",
+        ty
+      ),
+      Diagnostic::MatchMissingDefaultCase(ty) => format!(
+        "match on type `{:?}` requires a default case (`_`), since its cases cannot be enumerated",
+        ty
+      ),
+      Diagnostic::DivisionOperandMustBeNumeric(ty) => format!(
+        "division operands must be numeric, but got `{:?}`",
+        ty
+      ),
+      Diagnostic::HigherOrderSignaturesDifferInParameterCount(a, b) => format!(
+        "signatures differ in parameter count: {} vs. {}; check the arity of any function arguments or return values",
+        a, b
+      ),
+      Diagnostic::ObjectFieldTypeMismatch {
+        field_name,
+        expected,
+        actual,
+      } => format!(
+        "object field `{}` has conflicting types: expected `{:?}`, but got `{:?}`",
+        field_name, expected, actual
+      ),
+      Diagnostic::ArrayTooLarge(length) => format!(
+        "array length {} exceeds the maximum array length of {}",
+        length,
+        u32::MAX
+      ),
+      Diagnostic::ConstraintOriginated { diagnostic, .. } => diagnostic.message(),
+    }
+  }
+
+  /// Find the type id of the node that this diagnostic pertains to, if any.
+  pub fn find_type_id(&self) -> Option<&symbol_table::TypeId> {
+    match self {
+      Diagnostic::ConstraintOriginated { type_id, .. } => type_id.as_ref(),
+      _ => None,
+    }
+  }
+
+  /// Produce a structured, machine-readable representation of this
+  /// diagnostic.
+  ///
+  /// The given span table is used to locate the diagnostic in the source
+  /// code, if it pertains to a node.
+  pub fn to_structured(&self, span_table: &symbol_table::SpanTable) -> StructuredDiagnostic {
+    let notes = match self {
+      Diagnostic::ConstraintOriginated { origin, .. } => vec![origin.to_string()],
+      _ => Vec::new(),
+    };
+
+    StructuredDiagnostic {
+      code: self.code(),
+      message: self.message(),
+      span: self
+        .find_type_id()
+        .and_then(|type_id| span_table.get(type_id))
+        .copied(),
+      notes,
+    }
+  }
+
+  /// Serialize this diagnostic into a compact JSON object, for consumption
+  /// by external tooling.
+  #[cfg(feature = "json-errors")]
+  pub fn to_json(&self) -> String {
+    serde_json::to_string(self).expect("diagnostics should always be serializable")
+  }
+}
+
+/// A machine-readable representation of a diagnostic, intended for
+/// consumption by external tooling (ie. editors and language servers).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
+pub struct StructuredDiagnostic {
+  /// A stable code which uniquely identifies the kind of diagnostic.
+  pub code: &'static str,
+  pub message: String,
+  /// The source span of the node that the diagnostic pertains to, if any.
+  pub span: Option<symbol_table::Span>,
+  pub notes: Vec<String>,
 }
 
 #[derive(Default, Clone)]
@@ -157,6 +640,20 @@ impl DiagnosticsHelper {
     }
   }
 
+  /// Remove repeated diagnostics, keeping only the first occurrence of each.
+  ///
+  /// This is useful when multiple paths lead to the same diagnostic, such as
+  /// a function being constrained once per call site.
+  pub fn dedup(mut self) -> Self {
+    let mut seen = std::collections::HashSet::new();
+
+    self
+      .diagnostics
+      .retain(|diagnostic| seen.insert(diagnostic.clone()));
+
+    self
+  }
+
   pub fn into_pass_result(self) -> pass::PassResult {
     if self.contains_errors() {
       pass::PassResult::Err(self.diagnostics)
@@ -171,3 +668,103 @@ impl std::convert::From<Vec<Diagnostic>> for DiagnosticsHelper {
     Self { diagnostics }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn to_structured() {
+    let span = symbol_table::Span { start: 4, end: 8 };
+    let span_table = symbol_table::SpanTable::from([(symbol_table::TypeId(0), span)]);
+
+    let unification_failure = Diagnostic::ConstraintOriginated {
+      origin: inference::ConstraintOrigin::Return,
+      type_id: Some(symbol_table::TypeId(0)),
+      diagnostic: Box::new(Diagnostic::TypeMismatch(
+        types::Type::Unit,
+        types::Type::Opaque,
+      )),
+    }
+    .to_structured(&span_table);
+
+    assert_eq!("E0014", unification_failure.code);
+    assert!(unification_failure.message.starts_with("type mismatch"));
+    assert_eq!(
+      vec![String::from("in the return type of a function")],
+      unification_failure.notes
+    );
+
+    assert_eq!(Some(span), unification_failure.span);
+
+    let arity_mismatch = Diagnostic::TupleArityMismatch {
+      expected: 2,
+      actual: 3,
+    }
+    .to_structured(&span_table);
+
+    assert_eq!("E0046", arity_mismatch.code);
+    assert_eq!(
+      "expected a tuple of 2 elements, but got 3",
+      arity_mismatch.message
+    );
+    assert!(arity_mismatch.notes.is_empty());
+    assert_eq!(None, arity_mismatch.span);
+  }
+
+  #[test]
+  fn dedup() {
+    let helper = DiagnosticsHelper::from(vec![
+      Diagnostic::TypeMismatch(types::Type::Unit, types::Type::Opaque),
+      Diagnostic::OpaquePointerMustBeCasted,
+      Diagnostic::TypeMismatch(types::Type::Unit, types::Type::Opaque),
+      Diagnostic::TypeMismatch(types::Type::Opaque, types::Type::Unit),
+    ])
+    .dedup();
+
+    assert!(matches!(
+      helper.diagnostics.as_slice(),
+      [
+        Diagnostic::TypeMismatch(types::Type::Unit, types::Type::Opaque),
+        Diagnostic::OpaquePointerMustBeCasted,
+        Diagnostic::TypeMismatch(types::Type::Opaque, types::Type::Unit)
+      ]
+    ));
+  }
+
+  #[test]
+  fn recursive_stub_type_message() {
+    let stub_type = types::Type::Stub(Box::new(types::StubType {
+      universe_id: symbol_table::UniverseId(0, String::from("test")),
+      path: crate::ast::Path {
+        link_id: symbol_table::LinkId(0),
+        qualifier: Some(symbol_table::Qualifier {
+          package_name: String::from("std"),
+          module_name: String::from("io"),
+        }),
+        base_name: String::from("File"),
+        sub_name: Some(String::from("Handle")),
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints: Vec::new(),
+    }));
+
+    assert_eq!(
+      "type `std::io::File::Handle` is recursive",
+      Diagnostic::RecursiveType(stub_type).message()
+    );
+  }
+
+  #[test]
+  fn object_field_type_mismatch_message() {
+    assert_eq!(
+      "object field `a` has conflicting types: expected `Unit`, but got `Opaque`",
+      Diagnostic::ObjectFieldTypeMismatch {
+        field_name: String::from("a"),
+        expected: types::Type::Unit,
+        actual: types::Type::Opaque,
+      }
+      .message()
+    );
+  }
+}
@@ -1,14 +1,149 @@
-use crate::{assert_extract, ast, auxiliary, resolution, symbol_table, types};
-
+use crate::{
+  assert_extract, ast, auxiliary, diagnostic, instantiation, resolution, substitution,
+  symbol_table, types,
+};
+
+/// The constraints gathered during inference, in insertion order.
+///
+/// Structurally identical constraints (under the same universe stack) may
+/// appear more than once, unless deduplication is enabled (see
+/// `InferenceContext::set_constraint_deduplication_enabled`).
 pub type ConstraintSet = Vec<(resolution::UniverseStack, Constraint)>;
 
-pub(crate) struct InferenceResult {
+/// Associates type variables with the numeric literals they originate from.
+pub(crate) type LiteralOrigins =
+  std::collections::BTreeMap<symbol_table::SubstitutionId, LiteralOrigin>;
+
+/// Describes the numeric literal that a type variable was created for.
+///
+/// Such type variables may adopt the type of the context in which the literal
+/// is used, as long as the literal's value fits within it. Otherwise, they
+/// default to the literal's default type.
+#[derive(Clone, Debug)]
+pub(crate) struct LiteralOrigin {
+  /// The values of the literal, along with those of any other literals whose
+  /// type variables were bound to this one during unification.
+  pub values: Vec<f64>,
+  pub default_type: types::Type,
+}
+
+impl LiteralOrigin {
+  /// Attempt to re-type the literal into the expected type, which only
+  /// succeeds if all of its values fit within it.
+  pub(crate) fn coerce(&self, expected: &types::Type) -> Option<types::Type> {
+    let mut coercion = None;
+
+    for value in &self.values {
+      coercion = Some(types::Type::coerce_literal(
+        &self.default_type,
+        *value,
+        expected,
+      )?);
+    }
+
+    coercion
+  }
+}
+
+/// Determines how the result type of division operations is inferred.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DivisionSemantics {
+  /// The result's type follows that of the operands. For example, dividing
+  /// two integers yields an integer.
+  #[default]
+  FollowOperands,
+  /// The result is always a real number, regardless of the operands' types.
+  AlwaysReal,
+}
+
+/// Associates items with the set of items whose types they depend on.
+pub type DependencyGraph = std::collections::HashMap<
+  symbol_table::RegistryId,
+  std::collections::HashSet<symbol_table::RegistryId>,
+>;
+
+/// Associates unions with their pre-registered types.
+pub(crate) type UnionTypes = std::collections::HashMap<symbol_table::RegistryId, types::Type>;
+
+/// Associates closure captures with the type shared by all of their uses
+/// within the closure's body.
+pub(crate) type CaptureTypes = std::collections::HashMap<symbol_table::RegistryId, types::Type>;
+
+/// Associates monomorphic callees with their inferred type.
+pub(crate) type CalleeCache = std::collections::HashMap<symbol_table::RegistryId, types::Type>;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum MergeError {
+  /// The id count of the result being merged is lower than the current
+  /// id count, which means that it was produced by an older context.
+  RegressingIdCount { current: usize, other: usize },
+  /// The same type variable was created by both results.
+  DuplicateSubstitutionId(symbol_table::SubstitutionId),
+}
+
+/// The portion of an inference result which can be merged with that of
+/// other inference results.
+#[derive(Default)]
+pub(crate) struct InferenceResultData {
   pub constraints: ConstraintSet,
-  pub universe_id: Option<symbol_table::UniverseId>,
   pub type_var_substitutions: symbol_table::SubstitutionEnv,
   pub type_env: symbol_table::TypeEnvironment,
-  pub ty: types::Type,
   pub id_count: usize,
+  pub diagnostics: Vec<diagnostic::Diagnostic>,
+  pub literal_origins: LiteralOrigins,
+  pub dependency_graph: DependencyGraph,
+}
+
+impl InferenceResultData {
+  /// Merge another, newer inference result's data into this one.
+  pub(crate) fn merge(mut self, other: InferenceResultData) -> Result<Self, MergeError> {
+    if other.id_count < self.id_count {
+      return Err(MergeError::RegressingIdCount {
+        current: self.id_count,
+        other: other.id_count,
+      });
+    }
+
+    self.id_count = other.id_count;
+
+    for (substitution_id, ty) in other.type_var_substitutions {
+      if self.type_var_substitutions.contains_key(&substitution_id) {
+        return Err(MergeError::DuplicateSubstitutionId(substitution_id));
+      }
+
+      self.type_var_substitutions.insert(substitution_id, ty);
+    }
+
+    // NOTE: Duplicate type environment entries are expected, since the same
+    // AST node may be inferred more than once (ie. polymorphic functions
+    // invoked from multiple artifacts). In such cases, the newest type wins.
+    for (type_id, ty) in other.type_env {
+      // CONSIDER: Changing it so that instead of the type environment containing one type, it contains a set/vector of types, all of which should be compatible with one another (must be verified through unification). This is safer, because it ensures that any version of the same AST node with any input parameters, produces a compatible type.
+
+      // TODO: If inference caching is added, add a check to ensure that no duplicates should ever be inserted into the type environment (assert that the current type environment doesn't contain the type id to be inserted). Also note that inference caching will need to consider polymorphic functions invoked from artifacts (in such cases, caching should not be used). But then, those polymorphic functions would be inserted multiple times onto the type environment...
+      self.type_env.insert(type_id, ty);
+    }
+
+    self.constraints.extend(other.constraints);
+    self.diagnostics.extend(other.diagnostics);
+    self.literal_origins.extend(other.literal_origins);
+
+    for (dependent_id, dependency_ids) in other.dependency_graph {
+      self
+        .dependency_graph
+        .entry(dependent_id)
+        .or_default()
+        .extend(dependency_ids);
+    }
+
+    Ok(self)
+  }
+}
+
+pub(crate) struct InferenceResult {
+  pub data: InferenceResultData,
+  pub universe_id: Option<symbol_table::UniverseId>,
+  pub ty: types::Type,
 }
 
 pub(crate) struct InferenceOverallResult {
@@ -16,6 +151,9 @@ pub(crate) struct InferenceOverallResult {
   pub type_var_substitutions: symbol_table::SubstitutionEnv,
   pub type_env: symbol_table::TypeEnvironment,
   pub next_id_count: usize,
+  pub diagnostics: Vec<diagnostic::Diagnostic>,
+  pub literal_origins: LiteralOrigins,
+  pub dependency_graph: DependencyGraph,
 }
 
 pub(crate) struct InferenceContext<'a> {
@@ -45,6 +183,48 @@ pub(crate) struct InferenceContext<'a> {
   /// monomorphic. It contains no type variable substitutions or meta types.
   type_env: symbol_table::TypeEnvironment,
   symbol_table: &'a symbol_table::SymbolTable,
+  /// Diagnostics that can be determined during inference, without needing
+  /// to wait for unification to occur.
+  diagnostics: Vec<diagnostic::Diagnostic>,
+  /// Type variables that were created for numeric literals which lacked
+  /// a type hint.
+  literal_origins: LiteralOrigins,
+  /// The item whose definition is currently being inferred, if any.
+  ///
+  /// Used as the dependent side when recording dependencies.
+  current_item_id: Option<symbol_table::RegistryId>,
+  dependency_graph: DependencyGraph,
+  division_semantics: DivisionSemantics,
+  /// Whether function bodies may yield a subtype of their declared return
+  /// type, instead of being required to yield the exact same type.
+  is_subtyping_enabled: bool,
+  /// Whether constraints that are structurally identical to an already
+  /// gathered constraint should be discarded.
+  ///
+  /// Deduplication requires hashing every constraint, thus it is opt-in.
+  is_constraint_deduplication_enabled: bool,
+  /// The type that the expression currently being inferred is assumed to
+  /// have, if known beforehand.
+  ///
+  /// This propagates known types top-down, and is only visible to the
+  /// expression itself, not to its sub-expressions.
+  assumed_type: Option<types::Type>,
+  /// The types of all unions, registered before inference begins.
+  ///
+  /// Unions have no type id, thus they cannot be registered in the type
+  /// environment, which is keyed by type ids. This is shared among all
+  /// inherited contexts, since it is only written to by the root context.
+  union_types: std::rc::Rc<UnionTypes>,
+  /// The types of the captures of the closures currently being inferred.
+  ///
+  /// This is shared among inherited contexts, so that all uses of a capture
+  /// within a closure's body refer to the same type.
+  capture_types: std::rc::Rc<CaptureTypes>,
+  /// The inferred types of monomorphic callees, keyed by their registry id.
+  ///
+  /// This is shared among all inherited contexts, so that a monomorphic
+  /// callee invoked from multiple call sites is only inferred once.
+  callee_cache: std::rc::Rc<std::cell::RefCell<CalleeCache>>,
 }
 
 impl<'a> InferenceContext<'a> {
@@ -59,8 +239,25 @@ impl<'a> InferenceContext<'a> {
       constraints: ConstraintSet::new(),
       universe_stack: resolution::UniverseStack::new(),
       id_generator: auxiliary::IdGenerator::new(initial_id_count),
+      // NOTE: The substitution environment is a `BTreeMap`, which cannot be
+      // pre-sized; only the type environment benefits from the id count hint.
       type_var_substitutions: symbol_table::SubstitutionEnv::new(),
-      type_env: symbol_table::TypeEnvironment::new(),
+      // OPTIMIZE: The root context absorbs the results of every inherited
+      // context, so its type environment ends up holding roughly one entry
+      // per AST node. Pre-sizing it using the id count (an upper bound on the
+      // amount of nodes) avoids repeated rehashing as results are merged in.
+      type_env: symbol_table::TypeEnvironment::with_capacity(initial_id_count),
+      diagnostics: Vec::new(),
+      literal_origins: LiteralOrigins::new(),
+      current_item_id: None,
+      dependency_graph: DependencyGraph::new(),
+      division_semantics: DivisionSemantics::default(),
+      is_subtyping_enabled: false,
+      is_constraint_deduplication_enabled: false,
+      assumed_type: None,
+      union_types: std::rc::Rc::new(UnionTypes::new()),
+      capture_types: std::rc::Rc::new(CaptureTypes::new()),
+      callee_cache: std::rc::Rc::new(std::cell::RefCell::new(CalleeCache::new())),
     }
   }
 
@@ -82,18 +279,126 @@ impl<'a> InferenceContext<'a> {
       own_universe_id: child_universe_id,
       universe_stack,
       constraints: ConstraintSet::new(),
-      id_generator: auxiliary::IdGenerator::new(self.id_generator.get_counter()),
+      id_generator: {
+        let mut id_generator = auxiliary::IdGenerator::default();
+
+        id_generator.restore(self.id_generator.checkpoint());
+
+        id_generator
+      },
       type_var_substitutions: symbol_table::SubstitutionEnv::new(),
       type_env: symbol_table::TypeEnvironment::new(),
+      diagnostics: Vec::new(),
+      literal_origins: LiteralOrigins::new(),
+      current_item_id: self.current_item_id,
+      dependency_graph: DependencyGraph::new(),
+      division_semantics: self.division_semantics,
+      is_subtyping_enabled: self.is_subtyping_enabled,
+      is_constraint_deduplication_enabled: self.is_constraint_deduplication_enabled,
+      assumed_type: self.assumed_type.clone(),
+      union_types: std::rc::Rc::clone(&self.union_types),
+      capture_types: std::rc::Rc::clone(&self.capture_types),
+      callee_cache: std::rc::Rc::clone(&self.callee_cache),
+    }
+  }
+
+  /// Register the type of the given union, so that references to it
+  /// resolve immediately, regardless of the order in which items are
+  /// inferred.
+  ///
+  /// This should be called for all unions before inference begins.
+  pub(crate) fn register_union_type(&mut self, union: &std::rc::Rc<ast::Union>) {
+    std::rc::Rc::make_mut(&mut self.union_types).insert(
+      union.registry_id,
+      types::Type::Union(std::rc::Rc::clone(union)),
+    );
+  }
+
+  /// Verify that the arms of a match expression cover all the possible cases
+  /// of its subject's type.
+  ///
+  /// A default (wildcard) case covers any remaining cases. Otherwise, only
+  /// union subjects can be covered, since they are the only types with a
+  /// finite, known set of cases. The default case is optional, since match
+  /// expressions are not required to have a wildcard arm.
+  ///
+  /// The subject's type must be resolved, thus this is invoked once
+  /// unification has taken place (see the semantic check pass).
+  pub(crate) fn check_pattern_exhaustiveness(
+    subject_type: &types::Type,
+    arm_cases: &[&ast::Expr],
+    default_case: Option<&ast::Expr>,
+  ) -> diagnostic::Maybe {
+    if default_case.is_some() {
+      return Ok(());
+    }
+
+    let Ok(union) = subject_type.try_as_union() else {
+      return Err(vec![diagnostic::Diagnostic::MatchMissingDefaultCase(
+        subject_type.to_owned(),
+      )]);
+    };
+
+    let covered_variants = arm_cases
+      .iter()
+      .filter_map(|arm_case| match arm_case.flatten() {
+        ast::Expr::UnionInstance(union_instance) => union_instance.path.sub_name.as_ref(),
+        ast::Expr::Reference(reference) => reference.path.sub_name.as_ref(),
+        _ => None,
+      })
+      .collect::<std::collections::HashSet<_>>();
+
+    let missing_variants = union
+      .variants
+      .keys()
+      .filter(|variant_name| !covered_variants.contains(variant_name))
+      .cloned()
+      .collect::<Vec<_>>();
+
+    if missing_variants.is_empty() {
+      return Ok(());
     }
+
+    Err(vec![diagnostic::Diagnostic::NonExhaustiveMatch {
+      union_name: union.name.to_owned(),
+      missing_variants,
+    }])
+  }
+
+  /// Find the type of the union with the given registry id.
+  ///
+  /// The union must have been registered through `register_union_type`.
+  fn find_union_type(&self, union_id: &symbol_table::RegistryId) -> types::Type {
+    self
+      .union_types
+      .get(union_id)
+      .expect("all unions should have been registered before inference")
+      .to_owned()
   }
 
   pub(crate) fn into_overall_result(self) -> InferenceOverallResult {
+    // NOTE: Deduplication takes place once all of the constraints have been
+    // gathered, so that those merged from inherited contexts are considered
+    // as well.
+    let constraints = if self.is_constraint_deduplication_enabled {
+      self
+        .constraints
+        .into_iter()
+        .collect::<indexmap::IndexSet<_>>()
+        .into_iter()
+        .collect()
+    } else {
+      self.constraints
+    };
+
     InferenceOverallResult {
-      constraints: self.constraints,
+      constraints,
       type_var_substitutions: self.type_var_substitutions,
       type_env: self.type_env,
-      next_id_count: self.id_generator.get_counter(),
+      next_id_count: self.id_generator.checkpoint(),
+      diagnostics: self.diagnostics,
+      literal_origins: self.literal_origins,
+      dependency_graph: self.dependency_graph,
     }
   }
 
@@ -108,7 +413,7 @@ impl<'a> InferenceContext<'a> {
     signature: &ast::Signature,
   ) -> types::SignatureType {
     let return_type = if let Some(return_type_hint) = &signature.return_type_hint {
-      return_type_hint.to_owned()
+      self.instantiate_typeof_types(return_type_hint)
     } else {
       self.create_type_variable("signature.return_type")
     };
@@ -144,12 +449,57 @@ impl<'a> InferenceContext<'a> {
       .ok_or(auxiliary::MISSING_SYMBOL_TABLE_ENTRY)?;
 
     let target_item = target.into_item().ok_or("target is not an item")?;
+    let target_item_id = target_item.find_registry_id().copied();
+
+    if let (Some(current_item_id), Some(target_item_id)) = (self.current_item_id, target_item_id) {
+      self.record_dependency(current_item_id, target_item_id);
+    }
+
+    // The target's definition is inferred on behalf of the target itself,
+    // so any links it follows are dependencies of the target, not of the
+    // item that referenced it.
+    let previous_item_id = std::mem::replace(&mut self.current_item_id, target_item_id);
+
+    // NOTE: The target's type should not be cached here since the expected
+    // type might be different, regardless of whether multiple references
+    // point to the same target node. For example, this is crucial when
+    // dealing with polymorphic functions. Call sites cache the types of
+    // monomorphic callees instead (see `CalleeCache`).
+    let ty = self.visit(&target_item);
+
+    self.current_item_id = previous_item_id;
+
+    Ok(ty)
+  }
+
+  /// Record that the type of the item `from` depends on the type of the
+  /// item `to`.
+  pub(crate) fn record_dependency(
+    &mut self,
+    from: symbol_table::RegistryId,
+    to: symbol_table::RegistryId,
+  ) {
+    self.dependency_graph.entry(from).or_default().insert(to);
+  }
+
+  pub(crate) fn set_division_semantics(&mut self, division_semantics: DivisionSemantics) {
+    self.division_semantics = division_semantics;
+  }
+
+  pub(crate) fn set_subtyping_enabled(&mut self, is_subtyping_enabled: bool) {
+    self.is_subtyping_enabled = is_subtyping_enabled;
+  }
+
+  pub(crate) fn set_constraint_deduplication_enabled(
+    &mut self,
+    is_constraint_deduplication_enabled: bool,
+  ) {
+    self.is_constraint_deduplication_enabled = is_constraint_deduplication_enabled;
+  }
 
-    // NOTE: The target's type should not be cached since the expected type
-    // might be different, regardless of whether multiple references point to
-    // the same target node. For example, this is crucial when dealing with
-    // polymorphic functions.
-    Ok(self.visit(&target_item))
+  /// Set the item whose definition is about to be inferred.
+  pub(crate) fn enter_item(&mut self, item_id: Option<symbol_table::RegistryId>) {
+    self.current_item_id = item_id;
   }
 
   pub(crate) fn determine_arity_mode_for_callable(
@@ -204,8 +554,42 @@ impl<'a> InferenceContext<'a> {
     context.finalize(ty)
   }
 
-  pub(crate) fn visit(&mut self, inferable: &impl Infer<'a>) -> types::Type {
+  /// Perform inference within a child context, which is then finalized with
+  /// the type produced by the given closure.
+  ///
+  /// This takes care of inheriting from, and finalizing the child context,
+  /// so that inference implementations cannot forget to do so.
+  pub(crate) fn scoped(
+    &self,
+    infer: impl FnOnce(&mut InferenceContext<'a>) -> types::Type,
+  ) -> InferenceResult {
+    let mut context = self.inherit(None);
+    let ty = infer(&mut context);
+
+    context.finalize(ty)
+  }
+
+  pub(crate) fn get_assumed_type(&self) -> Option<&types::Type> {
+    self.assumed_type.as_ref()
+  }
+
+  /// Infer the given inferable under an assumed type, which is only visible
+  /// to the inferable itself.
+  fn infer_assuming(
+    &mut self,
+    inferable: &impl Infer<'a>,
+    assumed_type: Option<types::Type>,
+  ) -> InferenceResult {
+    let previous_assumed_type = std::mem::replace(&mut self.assumed_type, assumed_type);
     let result = inferable.infer(self);
+
+    self.assumed_type = previous_assumed_type;
+
+    result
+  }
+
+  pub(crate) fn visit(&mut self, inferable: &impl Infer<'a>) -> types::Type {
+    let result = self.infer_assuming(inferable, None);
     let ty = result.ty.clone();
 
     self.extend(result);
@@ -213,8 +597,216 @@ impl<'a> InferenceContext<'a> {
     ty
   }
 
+  /// Determine the type of a closure capture from the type of its target in
+  /// the enclosing scope, according to the capture's mode.
+  fn infer_capture_target(&mut self, capture: &ast::ClosureCapture) -> types::Type {
+    let target_type = self.visit_target_via_link(&capture.target_link_id).unwrap();
+
+    match capture.mode {
+      ast::CaptureMode::ByValue => target_type,
+      ast::CaptureMode::ByReference => types::Type::Reference(Box::new(target_type)),
+    }
+  }
+
+  /// Constrain the type of each closure capture's target in the enclosing
+  /// scope (as adjusted by the capture's mode) to be equal to its
+  /// corresponding expected type.
+  ///
+  /// The amount of expected types must match the amount of captures.
+  pub(crate) fn constrain_closure_captures(
+    &mut self,
+    captures: &[ast::ClosureCapture],
+    expected_types: &[types::Type],
+  ) {
+    assert_eq!(
+      captures.len(),
+      expected_types.len(),
+      "each closure capture should have a corresponding expected type"
+    );
+
+    let constraints = captures
+      .iter()
+      .zip(expected_types)
+      .map(|(capture, expected_type)| {
+        (self.infer_capture_target(capture), expected_type.to_owned())
+      })
+      .collect::<Vec<_>>();
+
+    self.add_constraint_batch(constraints);
+  }
+
+  /// Constrain a closure's body to its declared return type, independently
+  /// of the types of its captures.
+  ///
+  /// The captures are constrained first, so that their types are tied to
+  /// those of their targets before the body is inferred. Any use of a
+  /// capture within the body that disagrees with its target's type is then
+  /// reported by unification.
+  pub(crate) fn constrain_return(
+    &mut self,
+    body: &impl Infer<'a>,
+    declared_return: &types::Type,
+    captures: &[ast::ClosureCapture],
+  ) -> types::Type {
+    // The captured variables may be re-assigned in the enclosing scope,
+    // thus their types must be verified against what the closure expects
+    // instead of merely visited.
+    let capture_types = captures
+      .iter()
+      .map(|_| self.create_type_variable("closure.capture"))
+      .collect::<Vec<_>>();
+
+    // All uses of a capture within the body share its type, which ties them
+    // to the type of the capture's target.
+    for (capture, capture_type) in captures.iter().zip(&capture_types) {
+      std::rc::Rc::make_mut(&mut self.capture_types)
+        .insert(capture.registry_id, capture_type.clone());
+
+      self.type_env.insert(capture.type_id, capture_type.clone());
+    }
+
+    self.constrain_closure_captures(captures, &capture_types);
+
+    self.constrain_from(
+      body,
+      declared_return.to_owned(),
+      ConstraintOrigin::ClosureReturn,
+    )
+  }
+
+  /// Infer the types of a list of call arguments.
+  ///
+  /// Each argument is constrained against a fresh parameter type, and its
+  /// own type is registered under its type id in the type environment.
+  ///
+  /// Returns the parameter types, along with the type environment entries
+  /// that were registered for the arguments.
+  pub(crate) fn infer_argument_list(
+    &mut self,
+    arguments: &[ast::CallSiteArg],
+  ) -> (Vec<types::Type>, Vec<(symbol_table::TypeId, types::Type)>) {
+    let mut parameter_types = Vec::with_capacity(arguments.len());
+    let mut type_env_entries = Vec::with_capacity(arguments.len());
+
+    for (index, argument) in arguments.iter().enumerate() {
+      // NOTE: Parameter types do not need to be type variables in order to
+      // take the 'form' of generics; the unification order does not matter,
+      // since either way it is the argument's type that is unified against
+      // the generic. Instead, they are type variables so that each argument
+      // is constrained separately, recording its position as the origin of
+      // the constraint for diagnostics.
+      let parameter_type = self.create_type_variable("call_site.parameter");
+
+      let argument_type = self.constrain_from(
+        &argument.value,
+        parameter_type.clone(),
+        ConstraintOrigin::CallArgument { index },
+      );
+
+      self
+        .type_env
+        .insert(argument.type_id, argument_type.clone());
+      parameter_types.push(parameter_type);
+      type_env_entries.push((argument.type_id, argument_type));
+    }
+
+    (parameter_types, type_env_entries)
+  }
+
+  /// Constrain the accessed object's type to contain the accessed field,
+  /// with the same type as that of the value being written to it.
+  ///
+  /// The value's type is returned.
+  // TODO: Verify that the object is mutable once the language supports mutability annotations.
+  pub(crate) fn constrain_field_write(
+    &mut self,
+    object_access: &ast::ObjectAccess,
+    value: &impl Infer<'a>,
+  ) -> types::Type {
+    let value_type = self.visit(value);
+
+    // NOTE: An open object type is used, since the object may contain
+    // other fields besides the one being written to.
+    let object_type = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(
+        object_access.field_name.to_owned(),
+        value_type.clone(),
+      )]),
+      kind: types::ObjectKind::Open(self.id_generator.next_substitution_id()),
+    });
+
+    self.constrain(&object_access.object, object_type.clone());
+
+    // The object access is not visited, thus its types must be registered
+    // here, since it is still lowered as the address being written to.
+    self
+      .type_env
+      .insert(object_access.type_id, value_type.clone());
+
+    self
+      .type_env
+      .insert(object_access.base_expr_type_id, object_type);
+
+    value_type
+  }
+
   pub(crate) fn constrain(&mut self, inferable: &impl Infer<'a>, ty: types::Type) -> types::Type {
-    let result = inferable.infer(self);
+    self.constrain_with_variance(inferable, ty, Variance::Invariant, None)
+  }
+
+  /// Same as `constrain`, but also records the construct that introduced
+  /// the constraint, so that unification failures may be attributed to it.
+  pub(crate) fn constrain_from(
+    &mut self,
+    inferable: &impl Infer<'a>,
+    ty: types::Type,
+    origin: ConstraintOrigin,
+  ) -> types::Type {
+    self.constrain_with_variance(inferable, ty, Variance::Invariant, Some(origin))
+  }
+
+  /// Infer the inferable's type while assuming it to be the given type,
+  /// which propagates the type top-down into the inferable. The inferred
+  /// type is then constrained to be equal to the assumed type.
+  pub(crate) fn assume_type(
+    &mut self,
+    inferable: &impl Infer<'a>,
+    assumed_type: types::Type,
+  ) -> types::Type {
+    let result = self.infer_assuming(inferable, Some(assumed_type.clone()));
+
+    self.constrain_result(result, assumed_type, Variance::Invariant, None, None)
+  }
+
+  /// Same as `constrain_from`, but the given type is only required to be
+  /// compatible with the inferable's type under the given variance,
+  /// instead of being strictly equal to it. Recording the constraint's
+  /// origin is optional.
+  pub(crate) fn constrain_with_variance(
+    &mut self,
+    inferable: &impl Infer<'a>,
+    ty: types::Type,
+    variance: Variance,
+    origin: Option<ConstraintOrigin>,
+  ) -> types::Type {
+    let result = self.infer_assuming(inferable, None);
+
+    // NOTE: Only the diagnostics of originated constraints are located. The
+    // type id is otherwise omitted, so that identical constraints on the
+    // types of different nodes remain structurally identical.
+    let type_id = origin.as_ref().and_then(|_| inferable.find_node_type_id());
+
+    self.constrain_result(result, ty, variance, origin, type_id)
+  }
+
+  fn constrain_result(
+    &mut self,
+    result: InferenceResult,
+    ty: types::Type,
+    variance: Variance,
+    origin: Option<ConstraintOrigin>,
+    type_id: Option<symbol_table::TypeId>,
+  ) -> types::Type {
     let mut constraint_universe_stack = self.universe_stack.clone();
 
     // If the inference result contained a universe id, add it to the
@@ -239,7 +831,13 @@ impl<'a> InferenceContext<'a> {
 
     self.constraints.push((
       constraint_universe_stack,
-      Constraint::Equality(ty, result.ty.clone()),
+      Constraint::Compatibility {
+        a: ty,
+        b: result.ty.clone(),
+        variance,
+        origin,
+        type_id,
+      },
     ));
 
     let ty = result.ty.clone();
@@ -251,7 +849,7 @@ impl<'a> InferenceContext<'a> {
 
   pub(crate) fn infer_parameter(&mut self, parameter: &ast::Parameter) -> types::Type {
     let ty = if let Some(type_hint) = &parameter.type_hint {
-      type_hint.to_owned()
+      self.instantiate_typeof_types(type_hint)
     } else {
       // BUG: The inference system needs to be revised with regards to the constraints against generics; If a constraint set involving a generic and a type variable occurs, and the inference function was invoked by an artifact, the type variables might not end up becoming generics: they may ta ...
       // If the parameter has no type hint, its type will remain as a
@@ -263,9 +861,141 @@ impl<'a> InferenceContext<'a> {
 
     self.type_env.insert(parameter.type_id, ty.clone());
 
+    if let Some(pattern) = &parameter.pattern {
+      self.infer_pattern(pattern, &ty);
+    }
+
     ty
   }
 
+  /// Insert type environment entries for each of the sub-bindings of a
+  /// destructuring pattern, by structurally decomposing the type of the
+  /// value being destructured.
+  ///
+  /// If the type cannot be decomposed directly (ie. it is a type variable),
+  /// the sub-bindings are given fresh type variables instead, and the type
+  /// is constrained against the pattern's shape.
+  pub(crate) fn infer_pattern(&mut self, pattern: &ast::Pattern, ty: &types::Type) {
+    match pattern {
+      ast::Pattern::Tuple(bindings) => {
+        let element_types = match ty {
+          types::Type::Tuple(types::TupleType(element_types))
+            if element_types.len() == bindings.len() =>
+          {
+            element_types.to_owned()
+          }
+          _ => {
+            let element_types = bindings
+              .iter()
+              .map(|_| self.create_type_variable("pattern.tuple.element"))
+              .collect::<Vec<_>>();
+
+            self.add_constraint(
+              ty.to_owned(),
+              types::Type::Tuple(types::TupleType(element_types.clone())),
+            );
+
+            element_types
+          }
+        };
+
+        for (binding, element_type) in bindings.iter().zip(element_types) {
+          self.type_env.insert(binding.type_id, element_type);
+        }
+      }
+      ast::Pattern::Object(bindings) => {
+        let fields = bindings
+          .iter()
+          .map(|(field_name, binding)| {
+            let field_type = match ty {
+              types::Type::Object(object_type) => object_type.field_type(field_name).cloned(),
+              _ => None,
+            }
+            .unwrap_or_else(|| self.create_type_variable("pattern.object.field"));
+
+            self.type_env.insert(binding.type_id, field_type.clone());
+
+            (field_name.to_owned(), field_type)
+          })
+          .collect::<types::ObjectFieldMap>();
+
+        // The pattern need not mention every field of the object, thus its
+        // shape is that of an open object.
+        let pattern_type = types::Type::Object(types::ObjectType {
+          fields,
+          kind: types::ObjectKind::Open(self.id_generator.next_substitution_id()),
+        });
+
+        self.add_constraint(ty.to_owned(), pattern_type);
+      }
+    }
+  }
+
+  /// Verify that the operand of a `sizeof` expression is a sized, concrete
+  /// type.
+  ///
+  /// Opaque pointers are rejected as well, since the size of their pointee
+  /// is unknown, and the size of the pointer itself is seldom what is meant.
+  /// Polymorphic types are only known once instantiated, thus they are not
+  /// verified here.
+  pub(crate) fn constrain_sizeof_operand(&self, ty: &types::Type) -> diagnostic::Maybe {
+    // NOTE: Types that cannot be stripped (ie. recursive types) are
+    // reported elsewhere.
+    let Ok(stripped_type) = ty
+      .to_owned()
+      .try_strip_all_monomorphic_stub_layers(self.symbol_table)
+    else {
+      return Ok(());
+    };
+
+    match stripped_type.as_concrete() {
+      Some(concrete_type)
+        if !concrete_type.is_sized() || matches!(concrete_type, types::Type::Opaque) =>
+      {
+        Err(vec![diagnostic::Diagnostic::SizeofOperandUnsized(
+          ty.to_owned(),
+        )])
+      }
+      _ => Ok(()),
+    }
+  }
+
+  /// Verify that the signature of a foreign function provides type hints
+  /// for all of its parameters, as well as for its return type.
+  ///
+  /// Foreign functions have no body to infer their types from, thus their
+  /// signature must be fully annotated.
+  pub(crate) fn validate_foreign_function_hints(
+    foreign_function: &ast::ForeignFunction,
+  ) -> diagnostic::Maybe {
+    let missing_type_hint =
+      |parameter_name: Option<&str>| diagnostic::Diagnostic::ForeignFunctionMissingTypeHint {
+        function_name: foreign_function.name.to_owned(),
+        parameter_name: parameter_name.map(|name| name.to_owned()),
+      };
+
+    let parameter_diagnostics = foreign_function
+      .signature
+      .parameters
+      .iter()
+      .filter(|parameter| parameter.type_hint.is_none())
+      .map(|parameter| missing_type_hint(Some(&parameter.name)))
+      .collect::<Vec<_>>();
+
+    let parameters_result = if parameter_diagnostics.is_empty() {
+      Ok(())
+    } else {
+      Err(parameter_diagnostics)
+    };
+
+    let return_type_result = match foreign_function.signature.return_type_hint {
+      Some(_) => Ok(()),
+      None => Err(vec![missing_type_hint(None)]),
+    };
+
+    combine_results(parameters_result, return_type_result).map(|_| ())
+  }
+
   pub(crate) fn add_other_constraint(&mut self, constraint: Constraint) {
     let mut universe_stack = self.universe_stack.clone();
 
@@ -289,47 +1019,220 @@ impl<'a> InferenceContext<'a> {
   /// Create an equality constraint and add it to the constraint list,
   /// taking into account the current universe stack.
   pub(crate) fn add_constraint(&mut self, type_a: types::Type, type_b: types::Type) {
-    self.add_other_constraint(Constraint::Equality(type_a, type_b))
+    self.add_other_constraint(Constraint::Compatibility {
+      a: type_a,
+      b: type_b,
+      variance: Variance::Invariant,
+      origin: None,
+      type_id: None,
+    })
+  }
+
+  /// Same as `add_constraint`, but also records the construct that
+  /// introduced the constraint.
+  pub(crate) fn add_constraint_from(
+    &mut self,
+    type_a: types::Type,
+    type_b: types::Type,
+    origin: ConstraintOrigin,
+  ) {
+    self.add_other_constraint(Constraint::Compatibility {
+      a: type_a,
+      b: type_b,
+      variance: Variance::Invariant,
+      origin: Some(origin),
+      type_id: None,
+    })
+  }
+
+  /// Infer the type of the given expression, and constrain it to be equal
+  /// to the type of the `typeof` type expression evaluating to it.
+  pub(crate) fn add_typeof_constraint(&mut self, typeof_type: types::Type, expr: &impl Infer<'a>) {
+    let expr_type = self.visit(expr);
+
+    self.add_constraint(typeof_type, expr_type);
+  }
+
+  /// Replace all `typeof` types within the given type with fresh type
+  /// variables, each constrained against the type of its expression.
+  ///
+  /// This allows `typeof` types to be solved, and thus substituted, like any
+  /// other type variable.
+  pub(crate) fn instantiate_typeof_types(&mut self, ty: &types::Type) -> types::Type {
+    match ty {
+      types::Type::Typeof(typeof_) => {
+        let typeof_type = self.create_type_variable("typeof");
+
+        self.add_typeof_constraint(typeof_type.clone(), typeof_.as_ref());
+
+        typeof_type
+      }
+      types::Type::Pointer(pointee) => self.instantiate_typeof_types(pointee).into_pointer_type(),
+      types::Type::Reference(pointee) => {
+        types::Type::Reference(Box::new(self.instantiate_typeof_types(pointee)))
+      }
+      types::Type::Array { element, length } => types::Type::Array {
+        element: Box::new(self.instantiate_typeof_types(element)),
+        length: *length,
+      },
+      types::Type::Tuple(tuple_type) => types::Type::Tuple(types::TupleType(
+        tuple_type
+          .iter()
+          .map(|element_type| self.instantiate_typeof_types(element_type))
+          .collect(),
+      )),
+      types::Type::Object(object_type) => types::Type::Object(types::ObjectType {
+        fields: object_type
+          .fields
+          .iter()
+          .map(|(name, field_type)| (name.to_owned(), self.instantiate_typeof_types(field_type)))
+          .collect(),
+        kind: object_type.kind,
+      }),
+      types::Type::Signature(signature_type) => {
+        types::Type::Signature(Box::new(types::SignatureType {
+          parameter_types: signature_type
+            .parameter_types
+            .iter()
+            .map(|parameter_type| self.instantiate_typeof_types(parameter_type))
+            .collect(),
+          return_type: Box::new(self.instantiate_typeof_types(&signature_type.return_type)),
+          arity_mode: signature_type.arity_mode,
+        }))
+      }
+      _ => ty.to_owned(),
+    }
+  }
+
+  /// Create an equality constraint for each pair of types, and add them to
+  /// the constraint list.
+  pub(crate) fn add_constraint_batch(
+    &mut self,
+    constraints: impl IntoIterator<Item = (types::Type, types::Type)>,
+  ) {
+    for (type_a, type_b) in constraints {
+      self.add_constraint(type_a, type_b);
+    }
   }
 
   pub(crate) fn finalize(self, ty: types::Type) -> InferenceResult {
     InferenceResult {
-      constraints: self.constraints,
+      data: InferenceResultData {
+        constraints: self.constraints,
+        type_var_substitutions: self.type_var_substitutions,
+        type_env: self.type_env,
+        id_count: self.id_generator.checkpoint(),
+        diagnostics: self.diagnostics,
+        literal_origins: self.literal_origins,
+        dependency_graph: self.dependency_graph,
+      },
       universe_id: self.own_universe_id,
-      type_var_substitutions: self.type_var_substitutions,
-      type_env: self.type_env,
-      id_count: self.id_generator.get_counter(),
       ty,
     }
   }
 
   fn extend(&mut self, other: InferenceResult) {
-    // SAFETY: If it is valid/possible for the API to accept an 'older' context, then this assertion should be replaced with a `Result` type. Or if we're assuming that this would always be a logic bug, add a note. Also it is missing the reasoning message.
-    assert!(other.id_count >= self.id_generator.get_counter());
-
-    self.id_generator = auxiliary::IdGenerator::new(other.id_count);
+    let own_data = InferenceResultData {
+      constraints: std::mem::take(&mut self.constraints),
+      type_var_substitutions: std::mem::take(&mut self.type_var_substitutions),
+      type_env: std::mem::take(&mut self.type_env),
+      id_count: self.id_generator.checkpoint(),
+      diagnostics: std::mem::take(&mut self.diagnostics),
+      literal_origins: std::mem::take(&mut self.literal_origins),
+      dependency_graph: std::mem::take(&mut self.dependency_graph),
+    };
 
-    for (substitution_id, ty) in other.type_var_substitutions {
-      assert!(!self.type_var_substitutions.contains_key(&substitution_id));
-      self.type_var_substitutions.insert(substitution_id, ty);
-    }
+    // NOTE: Results are always produced by contexts inherited from this
+    // one, thus a failed merge constitutes a logic bug.
+    let merged_data = own_data
+      .merge(other.data)
+      .expect("inference results should always be mergeable with their parent context");
+
+    self.id_generator.restore(merged_data.id_count);
+    self.constraints = merged_data.constraints;
+    self.type_var_substitutions = merged_data.type_var_substitutions;
+    self.type_env = merged_data.type_env;
+    self.diagnostics = merged_data.diagnostics;
+    self.literal_origins = merged_data.literal_origins;
+    self.dependency_graph = merged_data.dependency_graph;
+  }
+}
 
-    for (type_id, ty) in other.type_env {
-      // CONSIDER: Changing it so that instead of the type environment containing one type, it contains a set/vector of types, all of which should be compatible with one another (must be verified through unification). This is safer, because it ensures that any version of the same AST node with any input parameters, produces a compatible type.
+/// The construct that introduced a constraint.
+///
+/// Used to provide more context on diagnostics produced during unification.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
+pub enum ConstraintOrigin {
+  BinaryOperand,
+  IfBranch,
+  CallArgument {
+    index: usize,
+  },
+  Return,
+  ClosureReturn,
+  /// The case of a match arm, which must be of the same type as the subject.
+  ///
+  /// The subject and case types are deliberately not included: when the
+  /// constraint is introduced they are usually still type variables, and the
+  /// originated diagnostic (ie. a type mismatch) already carries them once
+  /// they are resolved during unification.
+  MatchArmCase {
+    arm_index: usize,
+  },
+}
 
-      // TODO: If inference caching is added, add a check to ensure that no duplicates should ever be inserted into the type environment (assert that the current type environment doesn't contain the type id to be inserted). Also note that inference caching will need to consider polymorphic functions invoked from artifacts (in such cases, caching should not be used). But then, those polymorphic functions would be inserted multiple times onto the type environment...
-      self.type_env.insert(type_id, ty.clone());
+impl std::fmt::Display for ConstraintOrigin {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ConstraintOrigin::BinaryOperand => write!(f, "in an operand of a binary operation"),
+      ConstraintOrigin::IfBranch => write!(f, "in a branch of an if expression"),
+      ConstraintOrigin::CallArgument { index } => {
+        write!(f, "in argument #{} of a call site", index + 1)
+      }
+      ConstraintOrigin::Return => write!(f, "in the return type of a function"),
+      ConstraintOrigin::ClosureReturn => write!(f, "in the return type of a closure"),
+      ConstraintOrigin::MatchArmCase { arm_index } => {
+        write!(
+          f,
+          "in the case of match arm #{}, which must match the subject's type",
+          arm_index + 1
+        )
+      }
     }
-
-    self.constraints.extend(other.constraints);
   }
 }
 
+/// Determines how two types must relate to one another for them to be
+/// considered compatible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Variance {
+  /// The first type must be a subtype of the second type.
+  Covariant,
+  /// The second type must be a subtype of the first type.
+  Contravariant,
+  /// Both types must be equal.
+  #[default]
+  Invariant,
+}
+
 // FIXME: 'Contamination' is a possible problem that needs to be addressed; contamination can occur when 'special' or 'unique' types are created that are supposed to be attached to specific AST nodes (ie. specific metadata in the type, or flags, or classification, etc.), but those types can be cloned and inserted as substitutions for type variables, thus associating the type unique with a different construct. This happens during unification. Some approaches that may be taken could possibly be extending the constraint enum to add an 'is_unique' flag, which should be respected during unification to prevent carbon cloning the type. One example of contamination would be the pointer type created for nullptr, as it has the special flag of 'is_nullptr', which allows an exception for the unification of pointer types against the opaque type. For such reason, it was decided not to special case for the nullptr, and instead force the user to use the opaque type hint for the null value instead.
 #[derive(Clone, Debug)]
 pub enum Constraint {
-  /// Represents equality between two types.
-  Equality(types::Type, types::Type),
+  /// Represents compatibility between two types under a certain variance,
+  /// optionally along with the construct that introduced it.
+  ///
+  /// Invariant compatibility is equivalent to equality.
+  Compatibility {
+    a: types::Type,
+    b: types::Type,
+    variance: Variance,
+    origin: Option<ConstraintOrigin>,
+    /// The type id of the node whose type is constrained, if any.
+    ///
+    /// Used to locate diagnostics produced while solving the constraint.
+    type_id: Option<symbol_table::TypeId>,
+  },
   // CONSIDER: Another, perhaps more complex method would be to have tuples be similar to objects, but as a hash map. This way, it would have index -> element type mapping. It would need an open/closed system, similar to objects. Then, the 'element type of' can be modeled as an open tuple type, with key=index, and value=element type. This method of constraints might be more intuitive and simpler to manage, however.
   // CONSIDER: If this method works properly, replacing current object unification system with 'object element of' constraint.
   // REVIEW: If this occurs POST unification, then won't it unify against other things? In other words, it could only be a 'verification' constraint, since it won't aid unification?
@@ -340,11 +1243,209 @@ pub enum Constraint {
   },
 }
 
-pub(crate) trait Infer<'a> {
-  fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
-    // Default implementations to unit type.
+impl PartialEq for Constraint {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (
+        Constraint::Compatibility {
+          a: a_a,
+          b: b_a,
+          variance: variance_a,
+          origin: origin_a,
+          type_id: type_id_a,
+        },
+        Constraint::Compatibility {
+          a: a_b,
+          b: b_b,
+          variance: variance_b,
+          origin: origin_b,
+          type_id: type_id_b,
+        },
+      ) => {
+        variance_a == variance_b
+          && origin_a == origin_b
+          && type_id_a == type_id_b
+          && a_a.is_structurally_identical_to(a_b)
+          && b_a.is_structurally_identical_to(b_b)
+      }
+      (
+        Constraint::TupleElementOf {
+          tuple_type: tuple_type_a,
+          element_type: element_type_a,
+          index: index_a,
+        },
+        Constraint::TupleElementOf {
+          tuple_type: tuple_type_b,
+          element_type: element_type_b,
+          index: index_b,
+        },
+      ) => {
+        index_a == index_b
+          && tuple_type_a.is_structurally_identical_to(tuple_type_b)
+          && element_type_a.is_structurally_identical_to(element_type_b)
+      }
+      _ => false,
+    }
+  }
+}
+
+impl Eq for Constraint {}
+
+impl std::hash::Hash for Constraint {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    std::mem::discriminant(self).hash(state);
+
+    match self {
+      Constraint::Compatibility {
+        a,
+        b,
+        variance,
+        origin,
+        type_id,
+      } => {
+        variance.hash(state);
+        origin.hash(state);
+        type_id.hash(state);
+        a.hash(state);
+        b.hash(state);
+      }
+      Constraint::TupleElementOf {
+        tuple_type,
+        element_type,
+        index,
+      } => {
+        index.hash(state);
+        tuple_type.hash(state);
+        element_type.hash(state);
+      }
+    }
+  }
+}
+
+/// Evaluate a constraint once both of its sides are substituted using the
+/// given substitution environment.
+///
+/// Returns `None` if the constraint cannot be evaluated without unification
+/// (ie. either side remains a meta type after substitution), in which case
+/// it is neither proven nor disproven.
+fn evaluate_constraint(
+  symbol_table: &symbol_table::SymbolTable,
+  constraint: &Constraint,
+  substitution_env: &symbol_table::SubstitutionEnv,
+) -> Option<bool> {
+  let Constraint::Compatibility { a, b, variance, .. } = constraint else {
+    return None;
+  };
+
+  let substitution_helper = substitution::UnificationSubstitutionHelper {
+    symbol_table,
+    substitution_env,
+  };
+
+  let a = substitution_helper.substitute(a).ok()?;
+  let b = substitution_helper.substitute(b).ok()?;
+
+  let is_subtype = match variance {
+    Variance::Invariant => false,
+    Variance::Covariant => types::Type::is_coercible_to(&a, &b),
+    Variance::Contravariant => types::Type::is_coercible_to(&b, &a),
+  };
+
+  if is_subtype {
+    return Some(true);
+  }
+
+  a.concrete_eq(&b)
+}
+
+/// Determine whether a constraint demonstrably holds under the given
+/// substitution environment.
+///
+/// Intended for verifying the constraints produced by inference in tests.
+/// The substitution environment must contain an entry for every type
+/// variable involved; unsolved type variables should point to themselves.
+pub fn prove_constraint(
+  symbol_table: &symbol_table::SymbolTable,
+  constraint: &Constraint,
+  substitution_env: &symbol_table::SubstitutionEnv,
+) -> bool {
+  evaluate_constraint(symbol_table, constraint, substitution_env) == Some(true)
+}
+
+/// Determine whether a constraint is demonstrably false; that is, both of its
+/// sides are concrete after substitution, and they are incompatible.
+pub fn disprove_constraint(
+  symbol_table: &symbol_table::SymbolTable,
+  constraint: &Constraint,
+  substitution_env: &symbol_table::SubstitutionEnv,
+) -> bool {
+  evaluate_constraint(symbol_table, constraint, substitution_env) == Some(false)
+}
+
+/// Combine two fallible results into a single result holding both values.
+///
+/// If either result failed, the diagnostics of all failed results are
+/// returned instead, and any successful value is discarded.
+pub(crate) fn combine_results<T, U>(
+  result_a: diagnostic::Maybe<T>,
+  result_b: diagnostic::Maybe<U>,
+) -> diagnostic::Maybe<(T, U)> {
+  match (result_a, result_b) {
+    (Ok(value_a), Ok(value_b)) => Ok((value_a, value_b)),
+    (Err(mut diagnostics_a), Err(diagnostics_b)) => {
+      diagnostics_a.extend(diagnostics_b);
+
+      Err(diagnostics_a)
+    }
+    (Err(diagnostics), Ok(_)) | (Ok(_), Err(diagnostics)) => Err(diagnostics),
+  }
+}
+
+/// Combine two fallible results using the given combiner, which is always
+/// invoked with whichever values are available.
+///
+/// This allows inference to continue with partial information after a
+/// failure (ie. a type mismatch), while still collecting the diagnostics of
+/// all failed results.
+pub(crate) fn try_combine_results<T, U, R>(
+  result_a: diagnostic::Maybe<T>,
+  result_b: diagnostic::Maybe<U>,
+  combiner: impl FnOnce(Option<T>, Option<U>) -> R,
+) -> (R, Vec<diagnostic::Diagnostic>) {
+  fn split<V>(
+    result: diagnostic::Maybe<V>,
+    diagnostics: &mut Vec<diagnostic::Diagnostic>,
+  ) -> Option<V> {
+    match result {
+      Ok(value) => Some(value),
+      Err(result_diagnostics) => {
+        diagnostics.extend(result_diagnostics);
+
+        None
+      }
+    }
+  }
+
+  let mut diagnostics = Vec::new();
+  let value_a = split(result_a, &mut diagnostics);
+  let value_b = split(result_b, &mut diagnostics);
+
+  (combiner(value_a, value_b), diagnostics)
+}
+
+pub(crate) trait Infer<'a> {
+  fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
+    // Default implementations to unit type.
     parent.inherit(None).finalize(types::Type::Unit)
   }
+
+  /// Retrieve the type id of the node being inferred, if it has one.
+  ///
+  /// Constraints on the node's type record it, so that their diagnostics
+  /// can be located in the source code.
+  fn find_node_type_id(&self) -> Option<symbol_table::TypeId> {
+    None
+  }
 }
 
 impl Infer<'_> for ast::Expr {
@@ -375,6 +1476,10 @@ impl Infer<'_> for ast::Expr {
       ast::Expr::With(with) => parent.transient(with.as_ref()),
     }
   }
+
+  fn find_node_type_id(&self) -> Option<symbol_table::TypeId> {
+    self.find_type_id().copied()
+  }
 }
 
 impl Infer<'_> for ast::Item {
@@ -433,15 +1538,18 @@ impl Infer<'_> for ast::BinaryOp {
     let mut context = parent.inherit(None);
 
     let ty = match self.operator {
+      ast::BinaryOperator::Divide if context.division_semantics == DivisionSemantics::AlwaysReal => {
+        types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width64))
+      }
       ast::BinaryOperator::Add
       | ast::BinaryOperator::Subtract
       | ast::BinaryOperator::Multiply
       // NOTE: Although the logical approach would be to infer the
       // result of a division operation as a real number, prefer leaving
-      // it as a type variable for greater flexibility. The result's type
-      // will thus depend on the operands' types.
+      // it as a type variable for greater flexibility (unless configured
+      // otherwise). The result's type will thus depend on the operands' types.
       | ast::BinaryOperator::Divide => context.create_type_variable("binary_op.arithmetic"),
-      // TODO: The resulting type of modulo operations should be an integer, but with its bit-width corresponding with the bitwidth of the operands. Floats and integers alike should be allowed as operands. This will be a bit tricky, because those types cannot be inspected at this point (only post-unification are types revealed). Note that modulo operations can also result in negative integers. For now, `int64` is a good initial value because it encompasses all possible results (at the cost of possible redundancy).
+      // TODO: The resulting type of modulo operations should be an integer, but with its bit-width corresponding with the bitwidth of the operands. Operands are restricted to signed integers (see `TypePattern::AnySignedInteger`), which is checked during the semantic check pass. This will be a bit tricky, because those types cannot be inspected at this point (only post-unification are types revealed). Note that modulo operations can also result in negative integers. For now, `int64` is a good initial value because it encompasses all possible results (at the cost of possible redundancy).
       ast::BinaryOperator::Modulo => types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width64, true)),
       ast::BinaryOperator::Equality
       | ast::BinaryOperator::Inequality
@@ -459,26 +1567,54 @@ impl Infer<'_> for ast::BinaryOp {
     };
 
     // TODO: Handle modulo operator.
-    let operand_type = if let ast::BinaryOperator::Add
-    | ast::BinaryOperator::Subtract
-    | ast::BinaryOperator::Multiply
-    | ast::BinaryOperator::Divide = self.operator
-    {
-      let operand_type = context.create_type_variable("binary_op.operand.numeric");
+    let operand_type = match self.operator {
+      // The operands are independent of the (real) result. Since they are
+      // generally unknown at this point, that they are numeric is verified
+      // during the semantic check pass, once their types are resolved.
+      ast::BinaryOperator::Divide
+        if context.division_semantics == DivisionSemantics::AlwaysReal =>
+      {
+        context.create_type_variable("binary_op.operand.numeric")
+      }
+      ast::BinaryOperator::Add
+      | ast::BinaryOperator::Subtract
+      | ast::BinaryOperator::Multiply
+      | ast::BinaryOperator::Divide => {
+        let operand_type = context.create_type_variable("binary_op.operand.numeric");
 
-      context.add_constraint(operand_type.clone(), ty.clone());
+        context.add_constraint_from(
+          operand_type.clone(),
+          ty.clone(),
+          ConstraintOrigin::BinaryOperand,
+        );
 
-      operand_type
-    } else {
-      context.create_type_variable("binary_op.operand")
+        operand_type
+      }
+      // Short-circuiting logical operators only operate on booleans, unlike
+      // bitwise operators which may also operate on integers.
+      ast::BinaryOperator::And
+      | ast::BinaryOperator::Or
+      | ast::BinaryOperator::Nand
+      | ast::BinaryOperator::Nor => types::Type::Primitive(types::PrimitiveType::Bool),
+      _ => context.create_type_variable("binary_op.operand"),
     };
 
     context
       .type_env
       .insert(self.operand_type_id, operand_type.clone());
 
-    context.constrain(&self.left_operand, operand_type.clone());
-    context.constrain(&self.right_operand, operand_type.clone());
+    context.constrain_from(
+      &self.left_operand,
+      operand_type.clone(),
+      ConstraintOrigin::BinaryOperand,
+    );
+
+    context.constrain_from(
+      &self.right_operand,
+      operand_type.clone(),
+      ConstraintOrigin::BinaryOperand,
+    );
+
     context.type_env.insert(self.type_id, ty.clone());
 
     context.finalize(ty)
@@ -500,7 +1636,13 @@ impl Infer<'_> for ast::ForeignCluster {
 impl Infer<'_> for ast::ClosureCapture {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
-    let ty = context.visit_target_via_link(&self.target_link_id).unwrap();
+
+    // Within the body of the closure being inferred, the capture's type is
+    // shared among all of its uses.
+    let ty = match context.capture_types.get(&self.registry_id).cloned() {
+      Some(capture_type) => capture_type,
+      None => context.infer_capture_target(self),
+    };
 
     context.type_env.insert(self.type_id, ty.clone());
 
@@ -511,32 +1653,42 @@ impl Infer<'_> for ast::ClosureCapture {
 impl Infer<'_> for ast::Constant {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
+    let ty = context.instantiate_typeof_types(&self.ty);
+    let value_type = context.constrain(self.value.as_ref(), ty.clone());
 
-    context.constrain(self.value.as_ref(), self.ty.to_owned());
+    // Report obvious mismatches early, which yields a more specific
+    // diagnostic. Otherwise, unification will take care of the rest.
+    if !value_type.shallow_equal(&ty) {
+      context
+        .diagnostics
+        .push(diagnostic::Diagnostic::TypeMismatch(ty.clone(), value_type));
+    }
 
-    context.finalize(self.ty.to_owned())
+    context.finalize(ty)
   }
 }
 
 impl Infer<'_> for ast::UnionVariant {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let context = parent.inherit(None);
+    let union_type = context.find_union_type(&self.union_id);
 
-    let union_item = context
-      .symbol_table
-      .registry
-      .get(&self.union_id)
-      .expect(auxiliary::BUG_NAME_RESOLUTION);
-
-    let union = assert_extract!(union_item, symbol_table::RegistryItem::Union);
-
-    context.finalize(types::Type::Union(std::rc::Rc::clone(union)))
+    context.finalize(union_type)
   }
 }
 
 impl Infer<'_> for ast::PointerAssignment {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
+
+    // Writing to an object's field stores the value into the field itself,
+    // rather than through a pointer held by the field.
+    if let ast::Expr::ObjectAccess(object_access) = &self.pointer {
+      context.constrain_field_write(object_access, &self.value);
+
+      return context.finalize(types::Type::Unit);
+    }
+
     let pointee_type = context.create_type_variable("pointer_assignment.pointer.pointee");
     let pointer_type = types::Type::Pointer(Box::new(pointee_type.clone()));
 
@@ -562,6 +1714,13 @@ impl Infer<'_> for ast::PointerIndexing {
       )),
     );
 
+    // Unlike pointers, indexing an array yields its element type. Constant
+    // indices are checked against its length by the semantic check, once
+    // the array's type is resolved.
+    if let types::Type::Array { element, .. } = &ty {
+      return context.finalize(element.as_ref().to_owned());
+    }
+
     context.finalize(ty)
   }
 }
@@ -582,7 +1741,8 @@ impl Infer<'_> for ast::TupleIndex {
     let tuple_type = context.create_type_variable("tuple.access");
     let element_type = context.create_type_variable("tuple.access.element");
 
-    // BUG: (test:tuple_indexing_simple) This should be panicking with a `not yet implemented` message, since the unification's handling of `TupleElementOf` constraints is not yet implemented, but it's not panicking. Instead, unsolved type variable diagnostics are produced.
+    // The index is validated against the tuple's length during unification,
+    // once the tuple's type is known.
     context.add_other_constraint(Constraint::TupleElementOf {
       tuple_type: tuple_type.clone(),
       element_type: element_type.clone(),
@@ -603,27 +1763,6 @@ impl Infer<'_> for ast::TupleIndex {
 impl Infer<'_> for ast::UnionInstance {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
-    let value_type = context.create_type_variable("union_instance.value");
-
-    match &self.value {
-      ast::UnionInstanceValue::Value(value) => {
-        context.constrain(value, value_type);
-      }
-      ast::UnionInstanceValue::String(_) => context.add_constraint(
-        value_type,
-        types::Type::Primitive(types::PrimitiveType::CString),
-      ),
-      ast::UnionInstanceValue::Singleton(..) => context.add_constraint(
-        value_type,
-        types::Type::Primitive(types::PrimitiveType::Integer(
-          types::BitWidth::Width64,
-          false,
-        )),
-      ),
-    };
-
-    // BUG: Value type isn't constrained with anything for when the value is `String` or `Singleton` variant!
-    todo!();
 
     let union_variant = assert_extract!(
       context
@@ -642,25 +1781,87 @@ impl Infer<'_> for ast::UnionInstance {
       symbol_table::RegistryItem::Union
     );
 
-    context.finalize(types::Type::Union(std::rc::Rc::clone(union)))
-  }
-}
+    // NOTE: Type schemes are only available after the instantiation phase,
+    // thus declared payload types which require instantiation (ie. those
+    // involving polymorphic stub types) cannot be resolved yet. Those are
+    // left unresolved, and are resolved through the universe stack during
+    // unification instead.
+    let universes = instantiation::TypeSchemes::new();
+    let resolution_helper = resolution::BaseResolutionHelper::new(&universes, context.symbol_table);
+
+    let declared_payload_type =
+      union
+        .variant_payload_type(&union_variant.registry_id)
+        .map(|declared_payload_type| {
+          resolution_helper
+            .resolve_union_variant_type(union, &union_variant.name, context.universe_stack.clone())
+            .unwrap_or_else(|_| declared_payload_type.to_owned())
+        });
+
+    // The provided value is constrained directly against the variant's
+    // declared payload type, if any.
+    let value_type = declared_payload_type
+      .clone()
+      .unwrap_or_else(|| context.create_type_variable("union_instance.value"));
 
-impl Infer<'_> for ast::Tuple {
-  fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
-    let mut context = parent.inherit(None);
+    match &self.value {
+      ast::UnionInstanceValue::Value(value) => {
+        context.constrain(value, value_type.clone());
+      }
+      ast::UnionInstanceValue::String(_) => context.add_constraint(
+        value_type.clone(),
+        types::Type::Primitive(types::PrimitiveType::CString),
+      ),
+      ast::UnionInstanceValue::Singleton(..) => context.add_constraint(
+        value_type.clone(),
+        types::Type::Primitive(types::PrimitiveType::Integer(
+          types::BitWidth::Width64,
+          false,
+        )),
+      ),
+    };
 
-    let element_types = self
-      .elements
-      .iter()
-      .map(|element| context.visit(element))
-      .collect();
+    // Otherwise, the variant's implicit payload type (string or integer
+    // discriminant) is used instead.
+    if declared_payload_type.is_none() {
+      let payload_type = resolution::BaseResolutionHelper::find_union_variant_payload_type(
+        union,
+        &union_variant.name,
+      )
+      .expect(auxiliary::BUG_NAME_RESOLUTION);
 
-    let ty = types::Type::Tuple(types::TupleType(element_types));
+      context.add_constraint(value_type, payload_type);
+    }
 
-    context.type_env.insert(self.type_id, ty.clone());
+    let union_type = context.find_union_type(&union_variant.union_id);
 
-    context.finalize(ty)
+    context.finalize(union_type)
+  }
+}
+
+impl Infer<'_> for ast::Tuple {
+  fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
+    parent.scoped(|context| {
+      let element_types = self
+        .elements
+        .iter()
+        .map(|element| context.visit(element))
+        .collect();
+
+      let tuple_type = types::TupleType(element_types);
+
+      // The empty tuple is normalized into the unit type, so that later
+      // passes only encounter a single representation of unit.
+      let ty = if tuple_type.is_unit_singleton() {
+        types::Type::Unit
+      } else {
+        types::Type::Tuple(tuple_type)
+      };
+
+      context.type_env.insert(self.type_id, ty.clone());
+
+      ty
+    })
   }
 }
 
@@ -680,26 +1881,33 @@ impl Infer<'_> for ast::Range {
 
 impl Infer<'_> for ast::TypeDef {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
-    parent.inherit(None).finalize(self.body.to_owned())
+    let mut context = parent.inherit(None);
+    let body = context.instantiate_typeof_types(&self.body);
+
+    context.finalize(body)
   }
 }
 
 impl Infer<'_> for ast::Block {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
-    let mut context = parent.inherit(None);
+    parent.scoped(|context| {
+      for statement in &self.statements {
+        // Statement's types are irrelevant. However, they still need to be
+        // visited. It should be noted that let-binding statements do have a
+        // type themselves, but it is irrelevant in this context.
+        context.visit(statement.as_ref());
+      }
 
-    for statement in &self.statements {
-      // Statement's types are irrelevant. However, they still need to be
-      // visited. It should be noted that let-binding statements do have a
-      // type themselves, but it is irrelevant in this context.
-      context.visit(statement.as_ref());
-    }
+      let ty = context.visit(&self.yield_value);
 
-    let ty = context.visit(&self.yield_value);
+      context.type_env.insert(self.type_id, ty.clone());
 
-    context.type_env.insert(self.type_id, ty.clone());
+      ty
+    })
+  }
 
-    context.finalize(ty)
+  fn find_node_type_id(&self) -> Option<symbol_table::TypeId> {
+    Some(self.type_id)
   }
 }
 
@@ -708,7 +1916,18 @@ impl Infer<'_> for ast::Statement {
     let mut context = parent.inherit(None);
 
     match self {
-      ast::Statement::Binding(binding) => context.visit(binding.as_ref()),
+      ast::Statement::Binding(binding) => {
+        let value_type = context.visit(binding.as_ref());
+
+        // The statement itself is of unit type, but references to the
+        // binding still rely on its type id holding the value's type.
+        debug_assert!(
+          context.type_env.contains_key(&binding.type_id),
+          "binding's type should be registered in the type environment"
+        );
+
+        value_type
+      }
       ast::Statement::Constant(constant) => context.visit(constant.as_ref()),
       ast::Statement::InlineExpr(expr) => context.visit(expr),
       ast::Statement::PointerAssignment(pointer_assignment) => {
@@ -732,9 +1951,21 @@ impl Infer<'_> for ast::Function {
       .type_env
       .insert(self.type_id, types::Type::from(signature_type.clone()));
 
-    context.constrain(
+    // The body may yield a subtype of the return type (body <: return),
+    // which is contravariant with respect to the return type being the
+    // first type of the constraint. The lowering phase converts the body's
+    // value into the return type.
+    let variance = if context.is_subtyping_enabled {
+      Variance::Contravariant
+    } else {
+      Variance::Invariant
+    };
+
+    context.constrain_with_variance(
       self.body.as_ref(),
       signature_type.return_type.as_ref().clone(),
+      variance,
+      Some(ConstraintOrigin::Return),
     );
 
     context.finalize(types::Type::from(signature_type))
@@ -762,29 +1993,72 @@ impl Infer<'_> for ast::Literal {
       ast::LiteralKind::String(..) => types::Type::Primitive(types::PrimitiveType::CString),
       ast::LiteralKind::Char(..) => types::Type::Primitive(types::PrimitiveType::Char),
       ast::LiteralKind::Nullptr(type_hint) => {
+        let assumed_pointer_type = context
+          .get_assumed_type()
+          .filter(|assumed_type| matches!(assumed_type, types::Type::Pointer(..)))
+          .cloned();
+
         let ty = type_hint
           .as_ref()
           .map(|type_hint| type_hint.to_owned())
+          .or(assumed_pointer_type)
           .unwrap_or_else(|| context.create_type_variable("nullptr").into_pointer_type());
 
         ty
       }
       ast::LiteralKind::Number {
-        bit_width,
-        type_hint,
+        type_hint: Some(type_hint),
         is_real,
         ..
       } => {
-        type_hint
-          // OPTIMIZE: Cloning regardless.
-          .to_owned()
-          .map(|raw_type_hint| raw_type_hint)
-          .unwrap_or(types::Type::Primitive(if *is_real {
-            types::PrimitiveType::Real(bit_width.to_owned())
-          } else {
-            // Default to a signed integer type.
-            types::PrimitiveType::Integer(bit_width.to_owned(), true)
-          }))
+        // NOTE: Integer literals with a real type hint are implicitly
+        // promoted, since that conversion is lossless. Type hints that are
+        // stub types (ie. aliases) cannot be checked, since they are not
+        // resolved until unification.
+        if *is_real
+          && matches!(
+            type_hint,
+            types::Type::Primitive(types::PrimitiveType::Integer(..))
+          )
+        {
+          context
+            .diagnostics
+            .push(diagnostic::Diagnostic::RealLiteralWithIntegerTypeHint(
+              type_hint.to_owned(),
+            ));
+        }
+
+        type_hint.to_owned()
+      }
+      ast::LiteralKind::Number {
+        bit_width,
+        type_hint: None,
+        is_real,
+        value,
+      } => {
+        let default_type = types::Type::Primitive(if *is_real {
+          types::PrimitiveType::Real(bit_width.to_owned())
+        } else {
+          // Default to a signed integer type.
+          types::PrimitiveType::Integer(bit_width.to_owned(), true)
+        });
+
+        // Leave the literal's type open, so that it may adopt the type
+        // of the context in which it is used.
+        let type_variable = context.create_type_variable("literal.number");
+
+        let substitution_id =
+          assert_extract!(&type_variable, types::Type::Variable).substitution_id;
+
+        context.literal_origins.insert(
+          substitution_id,
+          LiteralOrigin {
+            values: vec![*value],
+            default_type,
+          },
+        );
+
+        type_variable
       }
     };
 
@@ -803,33 +2077,39 @@ impl Infer<'_> for ast::Cast {
       .type_env
       .insert(self.operand_type_id, operand_type.clone());
 
-    context
-      .type_env
-      .insert(self.type_id, self.cast_type.to_owned());
+    let cast_type = context.instantiate_typeof_types(&self.cast_type);
 
-    context.finalize(self.cast_type.to_owned())
+    context.type_env.insert(self.type_id, cast_type.clone());
+
+    context.finalize(cast_type)
   }
 }
 
 impl Infer<'_> for ast::Binding {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
-    let mut context = parent.inherit(None);
-
-    // TRACE: (test:vector_generics) Could it be that the bug related to the binding is due to the possibility that the value type here below is a generic type without any universe stack entry? It seems to be a type variable when printed to the console! Which may mean that it would be substituted to a generic type? If that's the case, that's a good indicator that the current inference system is quite fragile, especially around type variables, and the inference context and utility method logic needs to be more tightly isolated to prevent contamination or accidental logic bugs.
-    let value_type = if let Some(type_hint) = &self.type_hint {
-      context.constrain(&self.value, type_hint.to_owned())
-    } else {
-      context.visit(&self.value)
-    };
-
-    // Register the binding's type in the environment as that of its
-    // value. This allows for references to attain the type of the binding's
-    // value.
-    context.type_env.insert(self.type_id, value_type.clone());
+    parent.scoped(|context| {
+      // TRACE: (test:vector_generics) Could it be that the bug related to the binding is due to the possibility that the value type here below is a generic type without any universe stack entry? It seems to be a type variable when printed to the console! Which may mean that it would be substituted to a generic type? If that's the case, that's a good indicator that the current inference system is quite fragile, especially around type variables, and the inference context and utility method logic needs to be more tightly isolated to prevent contamination or accidental logic bugs.
+      let value_type = if let Some(type_hint) = &self.type_hint {
+        let type_hint = context.instantiate_typeof_types(type_hint);
+
+        context.assume_type(&self.value, type_hint)
+      } else {
+        context.visit(&self.value)
+      };
+
+      // Register the binding's type in the environment as that of its
+      // value. This allows for references to attain the type of the binding's
+      // value.
+      context.type_env.insert(self.type_id, value_type.clone());
+
+      if let Some(pattern) = &self.pattern {
+        context.infer_pattern(pattern, &value_type);
+      }
 
-    // The binding's overall type is unit, since it is a statement. However,
-    // references to the binding should have the type of the binding's value.
-    context.finalize(value_type)
+      // The binding's overall type is unit, since it is a statement. However,
+      // references to the binding should have the type of the binding's value.
+      value_type
+    })
   }
 }
 
@@ -837,13 +2117,36 @@ impl Infer<'_> for ast::UnaryOp {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
 
+    if self.operator == ast::UnaryOperator::Dereference {
+      let operand_type = context.visit(&self.operand);
+
+      // Dereferencing a reference yields its referent directly. Otherwise,
+      // the operand must be a pointer, and the result is its pointee.
+      let ty = match operand_type.peel_reference() {
+        Some(referent) => referent.to_owned(),
+        None => {
+          let pointee_type = context.create_type_variable("unary_op.deref.operand");
+
+          context.add_constraint(
+            operand_type.clone(),
+            pointee_type.clone().into_pointer_type(),
+          );
+
+          pointee_type
+        }
+      };
+
+      context.type_env.insert(self.operand_type_id, operand_type);
+      context.type_env.insert(self.type_id, ty.clone());
+
+      return context.finalize(ty);
+    }
+
     let operand_type = match &self.operator {
       ast::UnaryOperator::Not => types::Type::Primitive(types::PrimitiveType::Bool),
       ast::UnaryOperator::Negate => context.create_type_variable("unary_op.ty"),
       ast::UnaryOperator::ReferenceOf => context.create_type_variable("unary_op.ref.operand"),
-      ast::UnaryOperator::Dereference => context
-        .create_type_variable("unary_op.deref.operand")
-        .into_pointer_type(),
+      ast::UnaryOperator::Dereference => unreachable!("dereference should have been handled"),
     };
 
     context
@@ -854,21 +2157,9 @@ impl Infer<'_> for ast::UnaryOp {
       ast::UnaryOperator::Not => types::Type::Primitive(types::PrimitiveType::Bool),
       ast::UnaryOperator::Negate => operand_type.clone(),
       ast::UnaryOperator::ReferenceOf => types::Type::Reference(Box::new(operand_type.clone())),
-      ast::UnaryOperator::Dereference => match &operand_type {
-        types::Type::Pointer(pointee) => pointee.as_ref().to_owned(),
-        // REVISE: Attempt to revise the code to get rid of this assumption.
-        _ => unreachable!("overall type should be a pointer"),
-      },
+      ast::UnaryOperator::Dereference => unreachable!("dereference should have been handled"),
     };
 
-    // FIXME: This logic wrong. The type is already passed into the pointer creator on operand's type above, when the operator is a dereference. Something's wrong.
-    // If the operator is a dereference, then the operand's type
-    // must be a pointer, and the unary operation's type is the
-    // pointee type.
-    if let types::Type::Pointer(pointee_type) = &operand_type {
-      context.add_constraint(ty.clone(), pointee_type.as_ref().clone());
-    }
-
     context.type_env.insert(self.type_id, ty.clone());
     context.constrain(&self.operand, operand_type);
 
@@ -888,23 +2179,27 @@ impl Infer<'_> for ast::If {
     // FIXME: Need to slightly rework the type constraining process of the `if` statement. Currently, it is too monotone and restrictive. A field indicating whether the if produces a value or not is necessary. This is because different branches ARE allowed to have differing types, in the case that they don't yield a value, but instead currently it's forcing them to be `unit`.
 
     // The if expression will always have a unit type if it is missing
-    // its else branch.
+    // its else branch. If the assumed type is already a unit synonym, prefer
+    // it so that the canonical representation is preserved.
     let ty = if self.else_branch.is_none() {
-      types::Type::Unit
+      match parent.get_assumed_type() {
+        Some(assumed_type) if assumed_type.is_unit() => assumed_type.to_owned(),
+        _ => types::Type::Unit,
+      }
     } else {
       context.create_type_variable("if")
     };
 
     context.type_env.insert(self.type_id, ty.clone());
-    context.constrain(&self.then_branch, ty.clone());
+    context.constrain_from(&self.then_branch, ty.clone(), ConstraintOrigin::IfBranch);
 
     for (condition, alternative_branch) in &self.elif_branches {
       context.constrain(condition, CONDITION_TYPE);
-      context.constrain(alternative_branch, ty.clone());
+      context.constrain_from(alternative_branch, ty.clone(), ConstraintOrigin::IfBranch);
     }
 
     if let Some(else_value) = &self.else_branch {
-      context.constrain(else_value, ty.clone());
+      context.constrain_from(else_value, ty.clone(), ConstraintOrigin::IfBranch);
     }
 
     context.finalize(ty)
@@ -923,8 +2218,6 @@ impl Infer<'_> for ast::CallSite {
 
     // TODO: (test:generics_hints_mismatch) Need to constrain call site's generic hints vs. parameters (this may need to be done by first resolving the callee's signature, and then unifying (creating constraints) against it). Obviously, cannot resolve callee's signature at this point (during inference), so it would need to be some sort of deferred constraining (the usual: creating a signature type with type variables for the callee's signature, and constraining it against the call site's signature).
 
-    // TODO: Handle variadic functions more explicitly and carefully here.
-
     // Only account universe stack if the call site is to a polymorphic callee,
     // otherwise it is not considered an artifact.
     let universe_id_opt = if !self.generic_hints.is_empty() {
@@ -934,44 +2227,108 @@ impl Infer<'_> for ast::CallSite {
     };
 
     let mut context = parent.inherit(universe_id_opt);
-    let return_type = context.create_type_variable("call_site.return");
 
-    context.type_env.insert(self.type_id, return_type.clone());
+    // Generic hints are substituted as-is through the call site's universe,
+    // thus their instantiation is discarded. However, the expressions of any
+    // `typeof` types within them must still be inferred, so that unification
+    // and later passes can determine their types.
+    for generic_hint in &self.generic_hints {
+      context.instantiate_typeof_types(generic_hint);
+    }
 
     // BUG: The assumption that the callee is a callable will not always hold true by this point; unification hasn't yet occurred! This will panic if the callee is indeed not a callable, instead of being more graceful with a diagnostic.
     let callee = self.strip_callee(context.symbol_table).unwrap();
 
-    let callee_arity_mode = context.determine_arity_mode_for_callable(&callee);
+    // Multiple return values are modeled as a returned tuple. If the callee
+    // is hinted to return a tuple, give the call's result the shape of that
+    // tuple upfront, so that destructuring it does not need to wait for
+    // unification. The element types are still determined by constraining
+    // the callee's signature below.
+    let return_type = match &callee.get_signature().return_type_hint {
+      Some(types::Type::Tuple(tuple_type)) => types::Type::Tuple(types::TupleType(
+        tuple_type
+          .iter()
+          .map(|_| context.create_type_variable("call_site.return.element"))
+          .collect(),
+      )),
+      _ => context.create_type_variable("call_site.return"),
+    };
 
-    let argument_types = self
-      .arguments
-      .iter()
-      .map(|argument| {
-        let ty = context.visit(&argument.value);
+    context.type_env.insert(self.type_id, return_type.clone());
 
-        context.type_env.insert(argument.type_id, ty.clone());
+    // Monomorphic callees have the same type regardless of the call site, so
+    // if the callee expression directly references one, its inferred type is
+    // cached and reused by subsequent call sites.
+    let cacheable_callee = match (&self.callee_expr, callee.get_registry_id()) {
+      (ast::Expr::Reference(reference), Some(callee_id))
+        if !matches!(&callee, ast::Callable::Function(function) if function.is_polymorphic())
+          && context
+            .symbol_table
+            .follow_link(&reference.path.link_id)
+            .and_then(|target| target.into_item())
+            .and_then(|target_item| target_item.find_registry_id().copied())
+            == Some(callee_id) =>
+      {
+        Some((reference, callee_id))
+      }
+      _ => None,
+    };
 
-        ty
-      })
-      .collect::<Vec<_>>();
+    let callee_arity_mode = context.determine_arity_mode_for_callable(&callee);
 
-    // FIXME: The parameter types are being created as type variables, so that they make take the 'form' of generics. But! They are also being constrained against the argument types. So what happens if those type variables get unified against argument types BEFORE being unified against the generics?! Actually, the unification order shouldn't even matter! If they get unified against generics, they become generics, then unified against arguments, it's argument type vs. generic. If they are just a clone of the argument types, it's argument type vs. generic. In other words, nothing changes! Add a note here about this, so that the same mistake isn't made in the future thinking that parameter types need to be type variables to take the 'form' of generics.
+    // NOTE: Arguments past the required parameters of a variadic callee are
+    // promoted or decayed by the lowering phase, based on their resolved
+    // types, thus they are inferred like any other argument.
+    let (parameter_types, _) = context.infer_argument_list(&self.arguments);
 
-    let callee_type = types::Type::Signature(types::SignatureType {
-      parameter_types: argument_types,
-      return_type: Box::new(return_type.clone()),
+    let callee_type = types::Type::Signature(Box::new(types::SignatureType {
+      parameter_types,
+      return_type: Box::new(return_type),
       arity_mode: callee_arity_mode,
-    });
+    }));
 
     context
       .type_env
       .insert(self.callee_type_id, callee_type.clone());
 
-    context.constrain(&self.callee_expr, callee_type);
-
     // The type of the call expression is that of the callee's return
     // type.
-    context.finalize(return_type)
+    let call_type = callee_type
+      .as_return_type()
+      .expect("the callee's type should be a signature type")
+      .to_owned();
+
+    let cached_callee_type = cacheable_callee.and_then(|(reference, callee_id)| {
+      context
+        .callee_cache
+        .borrow()
+        .get(&callee_id)
+        .cloned()
+        .map(|cached_callee_type| (reference, callee_id, cached_callee_type))
+    });
+
+    if let Some((reference, callee_id, cached_callee_type)) = cached_callee_type {
+      if let Some(current_item_id) = context.current_item_id {
+        context.record_dependency(current_item_id, callee_id);
+      }
+
+      context
+        .type_env
+        .insert(reference.type_id, cached_callee_type.clone());
+
+      context.add_constraint(callee_type, cached_callee_type);
+    } else {
+      let inferred_callee_type = context.constrain(&self.callee_expr, callee_type);
+
+      if let Some((_, callee_id)) = cacheable_callee {
+        context
+          .callee_cache
+          .borrow_mut()
+          .insert(callee_id, inferred_callee_type);
+      }
+    }
+
+    context.finalize(call_type)
   }
 }
 
@@ -979,36 +2336,35 @@ impl Infer<'_> for ast::ForeignFunction {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
 
-    for parameter in &self.signature.parameters {
-      let parameter_type = parameter
-        .type_hint
-        .as_ref()
-        .expect(auxiliary::BUG_FOREIGN_FN_TYPE_HINTS)
-        .clone();
-
-      context.type_env.insert(parameter.type_id, parameter_type);
+    // NOTE: Missing type hints are reported, and replaced by type variables
+    // so that inference may proceed.
+    if let Err(diagnostics) = InferenceContext::validate_foreign_function_hints(self) {
+      context.diagnostics.extend(diagnostics);
     }
 
-    let return_type = self
-      .signature
-      .return_type_hint
-      .as_ref()
-      .expect(auxiliary::BUG_FOREIGN_FN_TYPE_HINTS)
-      .to_owned();
-
     let parameter_types = self
       .signature
       .parameters
       .iter()
       .map(|parameter| {
-        parameter
-          .type_hint
-          .as_ref()
-          .expect(auxiliary::BUG_FOREIGN_FN_TYPE_HINTS)
-          .to_owned()
+        let parameter_type = match &parameter.type_hint {
+          Some(type_hint) => type_hint.to_owned(),
+          None => context.create_type_variable("foreign.parameter"),
+        };
+
+        context
+          .type_env
+          .insert(parameter.type_id, parameter_type.clone());
+
+        parameter_type
       })
       .collect();
 
+    let return_type = match &self.signature.return_type_hint {
+      Some(return_type_hint) => return_type_hint.to_owned(),
+      None => context.create_type_variable("foreign.return"),
+    };
+
     let arity_mode = if self.signature.is_variadic {
       types::ArityMode::Variadic {
         minimum_required_parameters: self.signature.parameters.len(),
@@ -1017,11 +2373,11 @@ impl Infer<'_> for ast::ForeignFunction {
       types::ArityMode::Fixed
     };
 
-    let ty = types::Type::Signature(types::SignatureType {
+    let ty = types::Type::Signature(Box::new(types::SignatureType {
       return_type: Box::new(return_type),
       arity_mode,
       parameter_types,
-    });
+    }));
 
     context.type_env.insert(self.type_id, ty.clone());
 
@@ -1041,6 +2397,11 @@ impl Infer<'_> for ast::Sizeof {
     ));
 
     let mut context = parent.inherit(None);
+    let operand_type = context.instantiate_typeof_types(&self.ty);
+
+    if let Err(diagnostics) = context.constrain_sizeof_operand(&operand_type) {
+      context.diagnostics.extend(diagnostics);
+    }
 
     context.type_env.insert(self.type_id, ty.clone());
 
@@ -1048,6 +2409,18 @@ impl Infer<'_> for ast::Sizeof {
   }
 }
 
+impl Infer<'_> for ast::Typeof {
+  fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
+    parent.scoped(|context| {
+      let ty = context.visit(&self.expr);
+
+      context.type_env.insert(self.type_id, ty.clone());
+
+      ty
+    })
+  }
+}
+
 impl Infer<'_> for ast::ObjectAccess {
   fn infer(&self, parent: &InferenceContext<'_>) -> InferenceResult {
     let mut context = parent.inherit(None);
@@ -1056,6 +2429,9 @@ impl Infer<'_> for ast::ObjectAccess {
     context.type_env.insert(self.type_id, ty.clone());
 
     // The base expression must be an object containing at least this field.
+    // NOTE: The base expression's type is generally unknown until unification,
+    // which is why an open object type is used here instead of projecting the
+    // field directly (ie. through `Type::project_field`).
     let fields = types::ObjectFieldMap::from([(self.field_name.to_owned(), ty.clone())]);
 
     let base_type = types::Type::Object(types::ObjectType {
@@ -1083,11 +2459,11 @@ impl Infer<'_> for ast::Closure {
       types::Type::from(signature_type.clone()).clone(),
     );
 
-    for capture in &self.captures {
-      context.visit(capture);
-    }
-
-    context.constrain(&self.body, signature_type.return_type.as_ref().clone());
+    context.constrain_return(
+      &self.body,
+      signature_type.return_type.as_ref(),
+      &self.captures,
+    );
 
     context.finalize(types::Type::from(signature_type))
   }
@@ -1104,6 +2480,10 @@ impl Infer<'_> for ast::Object {
         // The object field's types are unknown.
         let field_type = context.create_type_variable("object.field");
 
+        // NOTE: Field constraints cannot be batched using `add_constraint_batch`,
+        // because each constraint must include the universe id of its field's
+        // inference result (ie. when the field's value is a call site to a
+        // polymorphic function), which only `constrain` takes into account.
         context.constrain(field, field_type.clone());
 
         (name.to_owned(), field_type)
@@ -1131,17 +2511,24 @@ impl Infer<'_> for ast::Match {
       .type_env
       .insert(self.subject_type_id, subject_type.clone());
 
-    for arm in &self.arms {
+    for (arm_index, arm) in self.arms.iter().enumerate() {
       // All arm cases and bodies must be the same type.
-      context.constrain(&arm.case, subject_type.clone());
+      context.constrain_from(
+        &arm.case,
+        subject_type.clone(),
+        ConstraintOrigin::MatchArmCase { arm_index },
+      );
+
       context.constrain(&arm.body, ty.clone());
     }
 
     context.type_env.insert(self.type_id, ty.clone());
 
-    // The default case is always present. Use that to infer the
-    // overall type of the match expression.
-    context.constrain(&self.default_case, ty.clone());
+    // NOTE: Exhaustiveness is checked during the semantic check pass, once
+    // the subject's type is known (see `check_pattern_exhaustiveness`).
+    if let Some(default_case) = &self.default_case {
+      context.constrain(default_case, ty.clone());
+    }
 
     context.finalize(ty)
   }
@@ -1152,3 +2539,1793 @@ impl Infer<'_> for ast::Group {
     parent.transient(&self.0)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn mock_integer_literal(value: f64) -> ast::Expr {
+    ast::Expr::Literal(ast::Literal {
+      type_id: symbol_table::TypeId(0),
+      kind: ast::LiteralKind::Number {
+        value,
+        is_real: false,
+        bit_width: types::BitWidth::Width32,
+        type_hint: Some(types::Type::Primitive(types::PrimitiveType::Integer(
+          types::BitWidth::Width32,
+          true,
+        ))),
+      },
+    })
+  }
+
+  fn mock_division() -> ast::BinaryOp {
+    ast::BinaryOp {
+      type_id: symbol_table::TypeId(1),
+      operand_type_id: symbol_table::TypeId(2),
+      operator: ast::BinaryOperator::Divide,
+      left_operand: mock_integer_literal(1_f64),
+      right_operand: mock_integer_literal(2_f64),
+    }
+  }
+
+  fn mock_result_data(
+    id_count: usize,
+    substitution_ids: &[symbol_table::SubstitutionId],
+  ) -> InferenceResultData {
+    InferenceResultData {
+      id_count,
+      type_var_substitutions: substitution_ids
+        .iter()
+        .map(|substitution_id| (*substitution_id, types::Type::Unit))
+        .collect(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn merge() {
+    let data = mock_result_data(1, &[symbol_table::SubstitutionId(0)]);
+    let other = mock_result_data(2, &[symbol_table::SubstitutionId(1)]);
+    let merged = data.merge(other).unwrap();
+
+    assert_eq!(2, merged.id_count);
+    assert_eq!(2, merged.type_var_substitutions.len());
+  }
+
+  #[test]
+  fn merge_regressing_id_count() {
+    let data = mock_result_data(2, &[]);
+    let other = mock_result_data(1, &[]);
+
+    assert_eq!(
+      Some(MergeError::RegressingIdCount {
+        current: 2,
+        other: 1
+      }),
+      data.merge(other).err()
+    );
+  }
+
+  #[test]
+  fn merge_duplicate_substitution_id() {
+    let data = mock_result_data(1, &[symbol_table::SubstitutionId(0)]);
+    let other = mock_result_data(1, &[symbol_table::SubstitutionId(0)]);
+
+    assert_eq!(
+      Some(MergeError::DuplicateSubstitutionId(
+        symbol_table::SubstitutionId(0)
+      )),
+      data.merge(other).err()
+    );
+  }
+
+  #[test]
+  fn division_follows_operands() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    assert!(matches!(
+      context.visit(&mock_division()),
+      types::Type::Variable(..)
+    ));
+  }
+
+  #[test]
+  fn constrain_with_variance() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    context.constrain_with_variance(
+      &mock_integer_literal(1.0),
+      types::Type::Unit,
+      Variance::Covariant,
+      None,
+    );
+
+    assert!(matches!(
+      context.constraints.last(),
+      Some((
+        _,
+        Constraint::Compatibility {
+          variance: Variance::Covariant,
+          ..
+        }
+      ))
+    ));
+  }
+
+  #[test]
+  fn constrain_from_records_node_type_id() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 1);
+
+    context.constrain(&mock_integer_literal(1.0), types::Type::Unit);
+
+    context.constrain_from(
+      &mock_integer_literal(2.0),
+      types::Type::Unit,
+      ConstraintOrigin::BinaryOperand,
+    );
+
+    // Only the originated constraint is located.
+    assert!(matches!(
+      context
+        .constraints
+        .iter()
+        .map(|(_, constraint)| constraint)
+        .collect::<Vec<_>>()
+        .as_slice(),
+      [
+        Constraint::Compatibility { type_id: None, .. },
+        Constraint::Compatibility {
+          type_id: Some(symbol_table::TypeId(0)),
+          ..
+        }
+      ]
+    ));
+  }
+
+  #[test]
+  fn function_body_subtype_of_return() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    let function = ast::Function {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("a"),
+      signature: std::rc::Rc::new(ast::Signature {
+        parameters: Vec::new(),
+        return_type_hint: Some(types::Type::Primitive(types::PrimitiveType::Integer(
+          types::BitWidth::Width64,
+          true,
+        ))),
+        is_variadic: false,
+        kind: ast::SignatureKind::Function,
+        return_type_id: symbol_table::TypeId(1),
+      }),
+      body: std::rc::Rc::new(ast::Block {
+        type_id: symbol_table::TypeId(2),
+        statements: Vec::new(),
+        // The body yields an `int8`, which is widenable into the `int64`
+        // return type.
+        yield_value: ast::Expr::Cast(std::rc::Rc::new(ast::Cast {
+          type_id: symbol_table::TypeId(3),
+          operand_type_id: symbol_table::TypeId(4),
+          operand: mock_integer_literal(1.0),
+          cast_type: types::Type::Primitive(types::PrimitiveType::Integer(
+            types::BitWidth::Width8,
+            true,
+          )),
+        })),
+      }),
+      generics: ast::Generics {
+        parameters: Vec::new(),
+      },
+    };
+
+    context.set_subtyping_enabled(true);
+    context.visit(&function);
+
+    assert!(matches!(
+      context.constraints.last(),
+      Some((
+        _,
+        Constraint::Compatibility {
+          variance: Variance::Contravariant,
+          origin: Some(ConstraintOrigin::Return),
+          ..
+        }
+      ))
+    ));
+  }
+
+  #[test]
+  fn monomorphic_callee_is_inferred_once() {
+    let function = ast::Function {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      signature: std::rc::Rc::new(ast::Signature {
+        parameters: Vec::new(),
+        return_type_hint: None,
+        is_variadic: false,
+        kind: ast::SignatureKind::Function,
+        return_type_id: symbol_table::TypeId(2),
+      }),
+      body: std::rc::Rc::new(ast::Block {
+        type_id: symbol_table::TypeId(3),
+        statements: Vec::new(),
+        yield_value: mock_integer_literal(1.0),
+      }),
+      generics: ast::Generics {
+        parameters: Vec::new(),
+      },
+    };
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Function(std::rc::Rc::new(function)),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    let mock_call_site = |id: usize| {
+      ast::Expr::CallSite(std::rc::Rc::new(ast::CallSite {
+        registry_id: symbol_table::RegistryId(id),
+        universe_id: symbol_table::UniverseId(id, String::from("test")),
+        type_id: symbol_table::TypeId(id),
+        callee_expr: ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+          type_id: symbol_table::TypeId(id + 1),
+          path: ast::Path {
+            link_id: symbol_table::LinkId(0),
+            qualifier: None,
+            base_name: String::from("a"),
+            sub_name: None,
+            symbol_kind: symbol_table::SymbolKind::Declaration,
+          },
+        })),
+        callee_type_id: symbol_table::TypeId(id + 2),
+        arguments: Vec::new(),
+        generic_hints: Vec::new(),
+      }))
+    };
+
+    let call_sites = ast::Tuple {
+      type_id: symbol_table::TypeId(4),
+      elements: vec![mock_call_site(5), mock_call_site(8)],
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 11);
+
+    context.visit(&call_sites);
+
+    assert_eq!(1, context.callee_cache.borrow().len());
+
+    let find_callee_return_type_id =
+      |type_id| match context.type_env.get(&symbol_table::TypeId(type_id)) {
+        Some(types::Type::Signature(signature_type)) => match signature_type.return_type.as_ref() {
+          types::Type::Variable(type_variable) => Some(type_variable.substitution_id),
+          _ => None,
+        },
+        _ => None,
+      };
+
+    // Both call sites refer to the very same inferred callee type.
+    assert!(find_callee_return_type_id(6).is_some());
+    assert_eq!(find_callee_return_type_id(6), find_callee_return_type_id(9));
+  }
+
+  #[test]
+  fn dereference_reference_yields_referent() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    let reference_of = ast::Expr::UnaryOp(std::rc::Rc::new(ast::UnaryOp {
+      type_id: symbol_table::TypeId(0),
+      operand_type_id: symbol_table::TypeId(1),
+      operator: ast::UnaryOperator::ReferenceOf,
+      operand: mock_integer_literal(1.0),
+    }));
+
+    let dereference = ast::UnaryOp {
+      type_id: symbol_table::TypeId(2),
+      operand_type_id: symbol_table::TypeId(3),
+      operator: ast::UnaryOperator::Dereference,
+      operand: reference_of,
+    };
+
+    context.visit(&dereference);
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(3)),
+      Some(types::Type::Reference(..))
+    ));
+
+    assert!(!matches!(
+      context.type_env.get(&symbol_table::TypeId(2)),
+      Some(types::Type::Reference(..) | types::Type::Pointer(..))
+    ));
+  }
+
+  #[test]
+  fn constrain_return() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 1);
+
+    let body = ast::Expr::Literal(ast::Literal {
+      type_id: symbol_table::TypeId(0),
+      kind: ast::LiteralKind::Char('a'),
+    });
+
+    context.constrain_return(
+      &body,
+      &types::Type::Primitive(types::PrimitiveType::Char),
+      &[],
+    );
+
+    assert!(context.diagnostics.is_empty());
+
+    assert!(matches!(
+      context.constraints.last(),
+      Some((
+        _,
+        Constraint::Compatibility {
+          origin: Some(ConstraintOrigin::ClosureReturn),
+          ..
+        }
+      ))
+    ));
+  }
+
+  #[test]
+  fn combine_results() {
+    let ok = || diagnostic::Maybe::Ok(1);
+    let err = |diagnostic| diagnostic::Maybe::<i32>::Err(vec![diagnostic]);
+
+    assert!(matches!(super::combine_results(ok(), ok()), Ok((1, 1))));
+
+    assert!(matches!(
+      super::combine_results(ok(), err(diagnostic::Diagnostic::RedundantCast))
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::RedundantCast]
+    ));
+
+    assert!(matches!(
+      super::combine_results(err(diagnostic::Diagnostic::RedundantCast), ok())
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::RedundantCast]
+    ));
+
+    assert!(matches!(
+      super::combine_results(
+        err(diagnostic::Diagnostic::RedundantCast),
+        err(diagnostic::Diagnostic::InvalidCastType)
+      )
+      .unwrap_err()
+      .as_slice(),
+      [
+        diagnostic::Diagnostic::RedundantCast,
+        diagnostic::Diagnostic::InvalidCastType
+      ]
+    ));
+  }
+
+  #[test]
+  fn try_combine_results() {
+    let (partial, diagnostics) = super::try_combine_results(
+      diagnostic::Maybe::Ok(1),
+      diagnostic::Maybe::<i32>::Err(vec![diagnostic::Diagnostic::RedundantCast]),
+      |value_a, value_b| (value_a, value_b),
+    );
+
+    assert_eq!((Some(1), None), partial);
+    assert_eq!(1, diagnostics.len());
+  }
+
+  #[test]
+  fn prove_binary_op_constraints() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    context.visit(&mock_division());
+
+    let substitution_env_with = |ty: types::Type| {
+      context
+        .type_var_substitutions
+        .keys()
+        .map(|substitution_id| (*substitution_id, ty.clone()))
+        .collect::<symbol_table::SubstitutionEnv>()
+    };
+
+    let integer_substitution_env = substitution_env_with(types::Type::Primitive(
+      types::PrimitiveType::Integer(types::BitWidth::Width32, true),
+    ));
+
+    let bool_substitution_env =
+      substitution_env_with(types::Type::Primitive(types::PrimitiveType::Bool));
+
+    assert!(!context.constraints.is_empty());
+
+    for (_, constraint) in &context.constraints {
+      assert!(prove_constraint(
+        &symbol_table,
+        constraint,
+        &integer_substitution_env
+      ));
+      assert!(!disprove_constraint(
+        &symbol_table,
+        constraint,
+        &integer_substitution_env
+      ));
+    }
+
+    // The operands are integer literals, thus they cannot be booleans.
+    assert!(context
+      .constraints
+      .iter()
+      .any(|(_, constraint)| disprove_constraint(
+        &symbol_table,
+        constraint,
+        &bool_substitution_env
+      )));
+
+    // Unsolved type variables (which point to themselves) cannot be
+    // evaluated either way.
+    let unsolved_substitution_env = &context.type_var_substitutions;
+
+    assert!(context.constraints.iter().any(|(_, constraint)| {
+      !prove_constraint(&symbol_table, constraint, unsolved_substitution_env)
+        && !disprove_constraint(&symbol_table, constraint, unsolved_substitution_env)
+    }));
+  }
+
+  #[test]
+  fn infer_argument_list() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 2);
+
+    let arguments = [
+      ast::CallSiteArg {
+        type_id: symbol_table::TypeId(0),
+        value: mock_integer_literal(1_f64),
+      },
+      ast::CallSiteArg {
+        type_id: symbol_table::TypeId(1),
+        value: ast::Expr::Literal(ast::Literal {
+          type_id: symbol_table::TypeId(2),
+          kind: ast::LiteralKind::Bool(true),
+        }),
+      },
+    ];
+
+    let (parameter_types, type_env_entries) = context.infer_argument_list(&arguments);
+
+    assert_eq!(2, parameter_types.len());
+    assert!(parameter_types
+      .iter()
+      .all(|ty| matches!(ty, types::Type::Variable(..))));
+
+    assert!(matches!(
+      type_env_entries.as_slice(),
+      [
+        (
+          symbol_table::TypeId(0),
+          types::Type::Primitive(types::PrimitiveType::Integer(..))
+        ),
+        (
+          symbol_table::TypeId(1),
+          types::Type::Primitive(types::PrimitiveType::Bool)
+        )
+      ]
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(1)),
+      Some(types::Type::Primitive(types::PrimitiveType::Bool))
+    ));
+
+    assert!(context.constraints.iter().any(|(_, constraint)| matches!(
+      constraint,
+      Constraint::Compatibility {
+        origin: Some(ConstraintOrigin::CallArgument { index: 1 }),
+        ..
+      }
+    )));
+  }
+
+  #[test]
+  fn constrain_field_write() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 4);
+
+    let object_access = ast::ObjectAccess {
+      type_id: symbol_table::TypeId(3),
+      base_expr_type_id: symbol_table::TypeId(4),
+      object: ast::Expr::Object(std::rc::Rc::new(ast::Object {
+        type_id: symbol_table::TypeId(0),
+        fields: std::collections::HashMap::from([(
+          String::from("a"),
+          ast::Expr::Literal(ast::Literal {
+            type_id: symbol_table::TypeId(1),
+            kind: ast::LiteralKind::Bool(true),
+          }),
+        )]),
+      })),
+      field_name: String::from("a"),
+    };
+
+    let value = ast::Expr::Literal(ast::Literal {
+      type_id: symbol_table::TypeId(2),
+      kind: ast::LiteralKind::Char('a'),
+    });
+
+    assert!(matches!(
+      context.constrain_field_write(&object_access, &value),
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&object_access.type_id),
+      Some(types::Type::Primitive(types::PrimitiveType::Char))
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&object_access.base_expr_type_id),
+      Some(types::Type::Object(..))
+    ));
+
+    assert!(context.constraints.iter().any(|(_, constraint)| matches!(
+      constraint,
+      Constraint::Compatibility {
+        a: types::Type::Object(types::ObjectType {
+          fields,
+          kind: types::ObjectKind::Open(..),
+        }),
+        b: types::Type::Object(..),
+        ..
+      } if matches!(
+        fields.get("a"),
+        Some(types::Type::Primitive(types::PrimitiveType::Char))
+      )
+    )));
+  }
+
+  #[test]
+  fn assume_type() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 2);
+    let pointer_type = types::Type::Primitive(types::PrimitiveType::Char).into_pointer_type();
+
+    let binding = ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("a"),
+      value: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(1),
+        kind: ast::LiteralKind::Nullptr(None),
+      }),
+      type_hint: Some(pointer_type),
+      pattern: None,
+    };
+
+    context.visit(&binding);
+
+    // The type hint is propagated into the value, instead of the value
+    // requiring a fresh type variable.
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(0)),
+      Some(types::Type::Pointer(pointee)) if matches!(
+        pointee.as_ref(),
+        types::Type::Primitive(types::PrimitiveType::Char)
+      )
+    ));
+
+    // The assumed type is not leaked outside of the assumption.
+    assert!(context.get_assumed_type().is_none());
+  }
+
+  #[test]
+  fn statement_binding_reference_type() {
+    let binding = std::rc::Rc::new(ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      value: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Char('a'),
+      }),
+      type_hint: None,
+      pattern: None,
+    });
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Binding(std::rc::Rc::clone(&binding)),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    let statement = std::rc::Rc::new(ast::Statement::Binding(binding));
+
+    let block = ast::Block {
+      type_id: symbol_table::TypeId(3),
+      statements: vec![std::rc::Rc::clone(&statement)],
+      yield_value: ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+        type_id: symbol_table::TypeId(2),
+        path: ast::Path {
+          link_id: symbol_table::LinkId(0),
+          qualifier: None,
+          base_name: String::from("a"),
+          sub_name: None,
+          symbol_kind: symbol_table::SymbolKind::Declaration,
+        },
+      })),
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 4);
+
+    assert!(matches!(
+      context.visit(statement.as_ref()),
+      types::Type::Unit
+    ));
+
+    context.visit(&block);
+
+    // The binding and references to it are of the value's type, while the
+    // statement holding it is of unit type.
+    for type_id in [1, 2, 3] {
+      assert!(matches!(
+        context.type_env.get(&symbol_table::TypeId(type_id)),
+        Some(types::Type::Primitive(types::PrimitiveType::Char))
+      ));
+    }
+  }
+
+  #[test]
+  fn closure_capture_mismatch() {
+    let binding = std::rc::Rc::new(ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      value: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Number {
+          value: 1_f64,
+          is_real: false,
+          bit_width: types::BitWidth::Width32,
+          type_hint: None,
+        },
+      }),
+      type_hint: None,
+      pattern: None,
+    });
+
+    let mock_capture = || ast::ClosureCapture {
+      name: String::from("a"),
+      mode: ast::CaptureMode::ByValue,
+      registry_id: symbol_table::RegistryId(1),
+      closure_registry_id: symbol_table::RegistryId(2),
+      index: 0,
+      target_link_id: symbol_table::LinkId(0),
+      type_id: symbol_table::TypeId(2),
+    };
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Binding(binding),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(1),
+      symbol_table::RegistryItem::ClosureCapture(std::rc::Rc::new(mock_capture())),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(1), symbol_table::RegistryId(1));
+
+    let mock_capture_reference = |type_id| {
+      ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+        type_id: symbol_table::TypeId(type_id),
+        path: ast::Path {
+          link_id: symbol_table::LinkId(1),
+          qualifier: None,
+          base_name: String::from("a"),
+          sub_name: None,
+          symbol_kind: symbol_table::SymbolKind::Declaration,
+        },
+      }))
+    };
+
+    // Both uses of the capture refer to the same captured value, thus they
+    // cannot be of different types.
+    let body = ast::Expr::Tuple(std::rc::Rc::new(ast::Tuple {
+      type_id: symbol_table::TypeId(3),
+      elements: vec![mock_capture_reference(4), mock_capture_reference(5)],
+    }));
+
+    let declared_return_type = types::Type::Tuple(types::TupleType(vec![
+      types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width8, true)),
+      types::Type::Primitive(types::PrimitiveType::Integer(
+        types::BitWidth::Width16,
+        true,
+      )),
+    ]));
+
+    let mut context = InferenceContext::new(&symbol_table, None, 6);
+
+    context.constrain_return(&body, &declared_return_type, &[mock_capture()]);
+
+    let inference_results = context.into_overall_result();
+    let universes = crate::instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+      &symbol_table,
+      inference_results.type_var_substitutions,
+      &universes,
+    );
+
+    unification_ctx.register_literal_origins(inference_results.literal_origins);
+
+    assert!(unification_ctx
+      .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+      .is_err());
+  }
+
+  #[test]
+  fn constrain_return_with_capture() {
+    let binding = std::rc::Rc::new(ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      value: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Char('a'),
+      }),
+      type_hint: None,
+      pattern: None,
+    });
+
+    let capture = ast::ClosureCapture {
+      name: String::from("a"),
+      mode: ast::CaptureMode::ByValue,
+      registry_id: symbol_table::RegistryId(1),
+      closure_registry_id: symbol_table::RegistryId(2),
+      index: 0,
+      target_link_id: symbol_table::LinkId(0),
+      type_id: symbol_table::TypeId(2),
+    };
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Binding(binding),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(1),
+      symbol_table::RegistryItem::ClosureCapture(std::rc::Rc::new(capture.clone())),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(1), symbol_table::RegistryId(1));
+
+    let body = ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+      type_id: symbol_table::TypeId(3),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(1),
+        qualifier: None,
+        base_name: String::from("a"),
+        sub_name: None,
+        symbol_kind: symbol_table::SymbolKind::Declaration,
+      },
+    }));
+
+    let solve = |declared_return_type: types::Type| {
+      let mut context = InferenceContext::new(&symbol_table, None, 4);
+
+      context.constrain_return(&body, &declared_return_type, &[capture.clone()]);
+      assert!(context.diagnostics.is_empty());
+
+      let inference_results = context.into_overall_result();
+
+      assert!(matches!(
+        inference_results.type_env.get(&capture.type_id),
+        Some(types::Type::Variable(..))
+      ));
+
+      let universes = crate::instantiation::TypeSchemes::new();
+
+      let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+        &symbol_table,
+        inference_results.type_var_substitutions,
+        &universes,
+      );
+
+      unification_ctx
+        .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+        .is_ok()
+    };
+
+    assert!(solve(types::Type::Primitive(types::PrimitiveType::Char)));
+
+    // The capture's type is tied to that of its target, thus returning it
+    // under a different type is reported by unification.
+    assert!(!solve(types::Type::Primitive(types::PrimitiveType::Bool)));
+  }
+
+  #[test]
+  fn closure_capture_by_reference() {
+    let binding = std::rc::Rc::new(ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      value: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Char('a'),
+      }),
+      type_hint: None,
+      pattern: None,
+    });
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Binding(binding),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    let mock_capture = |mode, type_id| ast::ClosureCapture {
+      name: String::from("a"),
+      mode,
+      registry_id: symbol_table::RegistryId(1),
+      closure_registry_id: symbol_table::RegistryId(2),
+      index: 0,
+      target_link_id: symbol_table::LinkId(0),
+      type_id: symbol_table::TypeId(type_id),
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 4);
+
+    let by_value_type = context.visit(&mock_capture(ast::CaptureMode::ByValue, 2));
+    let by_reference_type = context.visit(&mock_capture(ast::CaptureMode::ByReference, 3));
+
+    assert!(matches!(
+      by_value_type,
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+
+    assert!(matches!(
+      by_reference_type,
+      types::Type::Reference(referent)
+        if matches!(referent.as_ref(), types::Type::Primitive(types::PrimitiveType::Char))
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(3)),
+      Some(types::Type::Reference(..))
+    ));
+  }
+
+  #[test]
+  fn infer_parameter_tuple_pattern() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    let pattern = ast::Pattern::Tuple(vec![
+      ast::PatternBinding {
+        name: String::from("a"),
+        type_id: symbol_table::TypeId(1),
+      },
+      ast::PatternBinding {
+        name: String::from("b"),
+        type_id: symbol_table::TypeId(2),
+      },
+    ]);
+
+    let mut parameter = ast::Parameter {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("pair"),
+      position: 0,
+      type_hint: Some(types::Type::Tuple(types::TupleType(vec![
+        types::Type::Primitive(types::PrimitiveType::Bool),
+        types::Type::Primitive(types::PrimitiveType::Char),
+      ]))),
+      pattern: Some(pattern),
+    };
+
+    context.infer_parameter(&parameter);
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(1)),
+      Some(types::Type::Primitive(types::PrimitiveType::Bool))
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(2)),
+      Some(types::Type::Primitive(types::PrimitiveType::Char))
+    ));
+
+    // Without a type hint, the destructured names are given fresh type
+    // variables, and the parameter's type is constrained to a tuple.
+    parameter.type_hint = None;
+    context.infer_parameter(&parameter);
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(1)),
+      Some(types::Type::Variable(..))
+    ));
+
+    assert!(matches!(
+      context.constraints.last(),
+      Some((
+        _,
+        Constraint::Compatibility {
+          a: types::Type::Variable(..),
+          b: types::Type::Tuple(..),
+          ..
+        }
+      ))
+    ));
+  }
+
+  #[test]
+  fn infer_binding_tuple_pattern_from_call_site() {
+    let two_value_fn = std::rc::Rc::new(ast::ForeignFunction {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("two_value_fn"),
+      signature: std::rc::Rc::new(ast::Signature {
+        parameters: Vec::new(),
+        return_type_hint: Some(types::Type::Tuple(types::TupleType(vec![
+          types::Type::Primitive(types::PrimitiveType::Bool),
+          types::Type::Primitive(types::PrimitiveType::Char),
+        ]))),
+        is_variadic: false,
+        kind: ast::SignatureKind::ForeignFunction,
+        return_type_id: symbol_table::TypeId(1),
+      }),
+    });
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::ForeignFunction(two_value_fn),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    // (a, b) = two_value_fn()
+    let binding = ast::Binding {
+      registry_id: symbol_table::RegistryId(1),
+      type_id: symbol_table::TypeId(2),
+      name: String::from("pair"),
+      value: ast::Expr::CallSite(std::rc::Rc::new(ast::CallSite {
+        registry_id: symbol_table::RegistryId(2),
+        universe_id: symbol_table::UniverseId(2, String::from("two_value_fn")),
+        type_id: symbol_table::TypeId(3),
+        callee_expr: ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+          type_id: symbol_table::TypeId(4),
+          path: ast::Path {
+            link_id: symbol_table::LinkId(0),
+            qualifier: None,
+            base_name: String::from("two_value_fn"),
+            sub_name: None,
+            symbol_kind: symbol_table::SymbolKind::Declaration,
+          },
+        })),
+        callee_type_id: symbol_table::TypeId(5),
+        arguments: Vec::new(),
+        generic_hints: Vec::new(),
+      })),
+      type_hint: None,
+      pattern: Some(ast::Pattern::Tuple(vec![
+        ast::PatternBinding {
+          name: String::from("a"),
+          type_id: symbol_table::TypeId(6),
+        },
+        ast::PatternBinding {
+          name: String::from("b"),
+          type_id: symbol_table::TypeId(7),
+        },
+      ])),
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 8);
+
+    context.visit(&binding);
+
+    // The call's result is shaped as a tuple during inference, thus the
+    // pattern is decomposed directly.
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(3)),
+      Some(types::Type::Tuple(tuple_type)) if tuple_type.len() == 2
+    ));
+
+    let inference_results = context.into_overall_result();
+    let universes = crate::instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+      &symbol_table,
+      inference_results.type_var_substitutions,
+      &universes,
+    );
+
+    let solutions = unification_ctx
+      .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+      .unwrap();
+
+    assert!(matches!(
+      solutions[&symbol_table::TypeId(6)],
+      types::Type::Primitive(types::PrimitiveType::Bool)
+    ));
+
+    assert!(matches!(
+      solutions[&symbol_table::TypeId(7)],
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+  }
+
+  #[test]
+  fn binding_discard_with_type_hint() {
+    let symbol_table = symbol_table::SymbolTable::default();
+
+    let binding = ast::Binding {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(1),
+      name: String::from("a"),
+      value: ast::Expr::Discard(std::rc::Rc::new(ast::Discard(ast::Expr::Literal(
+        ast::Literal {
+          type_id: symbol_table::TypeId(0),
+          kind: ast::LiteralKind::Char('a'),
+        },
+      )))),
+      type_hint: Some(types::Type::Primitive(types::PrimitiveType::Char)),
+      pattern: None,
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 2);
+
+    context.visit(&binding);
+
+    let inference_results = context.into_overall_result();
+    let universes = crate::instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+      &symbol_table,
+      inference_results.type_var_substitutions,
+      &universes,
+    );
+
+    let diagnostics = unification_ctx
+      .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+      .unwrap_err();
+
+    assert!(matches!(
+      diagnostics.as_slice(),
+      [diagnostic::Diagnostic::UnexpectedUnit {
+        expected: types::Type::Primitive(types::PrimitiveType::Char)
+      }]
+    ));
+  }
+
+  #[test]
+  fn union_instance_payload_type() {
+    let variant = std::rc::Rc::new(ast::UnionVariant {
+      registry_id: symbol_table::RegistryId(1),
+      union_id: symbol_table::RegistryId(0),
+      name: String::from("Flag"),
+      kind: ast::UnionVariantKind::Type(types::Type::Primitive(types::PrimitiveType::Bool)),
+    });
+
+    let union = std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("Setting"),
+      variants: std::collections::BTreeMap::from([(
+        String::from("Flag"),
+        std::rc::Rc::clone(&variant),
+      )]),
+    });
+
+    assert!(matches!(
+      union.variant_payload_type(&symbol_table::RegistryId(1)),
+      Some(types::Type::Primitive(types::PrimitiveType::Bool))
+    ));
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Union(std::rc::Rc::clone(&union)),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(1),
+      symbol_table::RegistryItem::UnionVariant(variant),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(1));
+
+    let solve_for_payload = |payload_kind: ast::LiteralKind| {
+      let union_instance = ast::UnionInstance {
+        path: ast::Path {
+          link_id: symbol_table::LinkId(0),
+          qualifier: None,
+          base_name: String::from("Setting"),
+          sub_name: Some(String::from("Flag")),
+          symbol_kind: symbol_table::SymbolKind::Type,
+        },
+        value: ast::UnionInstanceValue::Value(ast::Expr::Literal(ast::Literal {
+          type_id: symbol_table::TypeId(0),
+          kind: payload_kind,
+        })),
+      };
+
+      let mut context = InferenceContext::new(&symbol_table, None, 1);
+
+      context.register_union_type(&union);
+      context.visit(&union_instance);
+
+      let inference_results = context.into_overall_result();
+      let universes = crate::instantiation::TypeSchemes::new();
+
+      let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+        &symbol_table,
+        inference_results.type_var_substitutions,
+        &universes,
+      );
+
+      unification_ctx
+        .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+        .is_ok()
+    };
+
+    assert!(solve_for_payload(ast::LiteralKind::Bool(true)));
+    assert!(!solve_for_payload(ast::LiteralKind::Char('a')));
+  }
+
+  #[test]
+  fn union_instance_resolves_declared_payload_type() {
+    // type Toggle = bool
+    // union Setting { Flag(Toggle) }
+    let toggle_path = ast::Path {
+      link_id: symbol_table::LinkId(1),
+      qualifier: None,
+      base_name: String::from("Toggle"),
+      sub_name: None,
+      symbol_kind: symbol_table::SymbolKind::Type,
+    };
+
+    let variant = std::rc::Rc::new(ast::UnionVariant {
+      registry_id: symbol_table::RegistryId(1),
+      union_id: symbol_table::RegistryId(0),
+      name: String::from("Flag"),
+      kind: ast::UnionVariantKind::Type(types::Type::Stub(Box::new(types::StubType {
+        universe_id: symbol_table::UniverseId(1, String::from("Toggle")),
+        path: toggle_path,
+        generic_hints: Vec::new(),
+      }))),
+    });
+
+    let union = std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("Setting"),
+      variants: std::collections::BTreeMap::from([(
+        String::from("Flag"),
+        std::rc::Rc::clone(&variant),
+      )]),
+    });
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Union(std::rc::Rc::clone(&union)),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(1),
+      symbol_table::RegistryItem::UnionVariant(variant),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(2),
+      symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+        registry_id: symbol_table::RegistryId(2),
+        name: String::from("Toggle"),
+        body: types::Type::Primitive(types::PrimitiveType::Bool),
+        generics: ast::Generics {
+          parameters: Vec::new(),
+        },
+      })),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(1));
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(1), symbol_table::RegistryId(2));
+
+    let union_instance = ast::UnionInstance {
+      path: ast::Path {
+        link_id: symbol_table::LinkId(0),
+        qualifier: None,
+        base_name: String::from("Setting"),
+        sub_name: Some(String::from("Flag")),
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      value: ast::UnionInstanceValue::Value(ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Bool(true),
+      })),
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 1);
+
+    context.register_union_type(&union);
+    context.visit(&union_instance);
+
+    // The value is constrained against the alias' target, instead of the
+    // stub type itself.
+    assert!(context.constraints.iter().all(|(_, constraint)| !matches!(
+      constraint,
+      Constraint::Compatibility {
+        a: types::Type::Stub(..),
+        ..
+      } | Constraint::Compatibility {
+        b: types::Type::Stub(..),
+        ..
+      }
+    )));
+
+    assert!(context.constraints.iter().any(|(_, constraint)| matches!(
+      constraint,
+      Constraint::Compatibility {
+        a: types::Type::Primitive(types::PrimitiveType::Bool),
+        ..
+      } | Constraint::Compatibility {
+        b: types::Type::Primitive(types::PrimitiveType::Bool),
+        ..
+      }
+    )));
+  }
+
+  #[test]
+  fn instantiate_typeof_types() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 2);
+
+    let typeof_type = types::Type::Typeof(Box::new(ast::Typeof {
+      type_id: symbol_table::TypeId(1),
+      expr: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(0),
+        kind: ast::LiteralKind::Char('a'),
+      }),
+    }));
+
+    let instantiated_type = context.instantiate_typeof_types(&typeof_type.into_pointer_type());
+
+    assert!(matches!(
+      &instantiated_type,
+      types::Type::Pointer(pointee) if matches!(pointee.as_ref(), types::Type::Variable(..))
+    ));
+
+    assert!(matches!(
+      context.constraints.last(),
+      Some((
+        _,
+        Constraint::Compatibility {
+          a: types::Type::Variable(..),
+          b: types::Type::Primitive(types::PrimitiveType::Char),
+          ..
+        }
+      ))
+    ));
+
+    assert!(matches!(
+      context.type_env.get(&symbol_table::TypeId(1)),
+      Some(types::Type::Primitive(types::PrimitiveType::Char))
+    ));
+  }
+
+  #[test]
+  fn typeof_cast_and_constant() {
+    let symbol_table = symbol_table::SymbolTable::default();
+
+    let make_typeof_type = |type_id, literal_type_id, kind| {
+      types::Type::Typeof(Box::new(ast::Typeof {
+        type_id: symbol_table::TypeId(type_id),
+        expr: ast::Expr::Literal(ast::Literal {
+          type_id: symbol_table::TypeId(literal_type_id),
+          kind,
+        }),
+      }))
+    };
+
+    let cast = ast::Cast {
+      type_id: symbol_table::TypeId(0),
+      operand_type_id: symbol_table::TypeId(1),
+      operand: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(2),
+        kind: ast::LiteralKind::Bool(true),
+      }),
+      cast_type: make_typeof_type(3, 4, ast::LiteralKind::Char('a')),
+    };
+
+    let constant = ast::Constant {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("a"),
+      ty: make_typeof_type(5, 6, ast::LiteralKind::Bool(false)),
+      value: Box::new(ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(7),
+        kind: ast::LiteralKind::Bool(true),
+      })),
+      was_declared_locally: true,
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 8);
+
+    context.visit(&cast);
+    context.visit(&constant);
+
+    let inference_results = context.into_overall_result();
+    let universes = crate::instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = crate::unification::TypeUnificationContext::new(
+      &symbol_table,
+      inference_results.type_var_substitutions,
+      &universes,
+    );
+
+    unification_ctx.set_partial_type_env(&inference_results.type_env);
+
+    let solutions = unification_ctx
+      .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+      .unwrap();
+
+    assert!(matches!(
+      solutions[&symbol_table::TypeId(0)],
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+
+    // Later passes resolve the `typeof` types within the AST directly.
+    let resolution_helper =
+      crate::resolution::ResolutionHelper::new(&universes, &symbol_table, &solutions);
+
+    assert!(matches!(
+      resolution_helper
+        .base
+        .resolve(&cast.cast_type, Vec::new())
+        .unwrap()
+        .as_ref(),
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+
+    assert!(matches!(
+      resolution_helper
+        .base
+        .resolve(&constant.ty, Vec::new())
+        .unwrap()
+        .as_ref(),
+      types::Type::Primitive(types::PrimitiveType::Bool)
+    ));
+  }
+
+  #[test]
+  fn array_indexing() {
+    let element_type = types::Type::Primitive(types::PrimitiveType::Char);
+
+    let parameter = std::rc::Rc::new(ast::Parameter {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("array"),
+      position: 0,
+      type_hint: Some(types::Type::Array {
+        element: Box::new(element_type),
+        length: 3,
+      }),
+      pattern: None,
+    });
+
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      symbol_table::RegistryItem::Parameter(parameter),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    let array_indexing = |index: f64| ast::PointerIndexing {
+      type_id: symbol_table::TypeId(1),
+      pointer: ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+        type_id: symbol_table::TypeId(2),
+        path: ast::Path {
+          link_id: symbol_table::LinkId(0),
+          qualifier: None,
+          base_name: String::from("array"),
+          sub_name: None,
+          symbol_kind: symbol_table::SymbolKind::Declaration,
+        },
+      })),
+      index: mock_integer_literal(index),
+    };
+
+    let mut context = InferenceContext::new(&symbol_table, None, 3);
+
+    assert!(matches!(
+      context.visit(&array_indexing(2.0)),
+      types::Type::Primitive(types::PrimitiveType::Char)
+    ));
+
+    // Out-of-bounds indices are only reported by the semantic check.
+    context.visit(&array_indexing(3.0));
+    assert!(context.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn empty_tuple_is_unit() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let context = InferenceContext::new(&symbol_table, None, 2);
+
+    let tuple = ast::Tuple {
+      type_id: symbol_table::TypeId(0),
+      elements: Vec::new(),
+    };
+
+    let result = tuple.infer(&context);
+
+    assert!(result.ty.is_a_unit());
+    assert!(result.data.type_env[&tuple.type_id].is_a_unit());
+  }
+
+  #[test]
+  fn scoped() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let context = InferenceContext::new(&symbol_table, None, 2);
+
+    let tuple = ast::Tuple {
+      type_id: symbol_table::TypeId(1),
+      elements: vec![
+        mock_integer_literal(1.0),
+        ast::Expr::Literal(ast::Literal {
+          type_id: symbol_table::TypeId(0),
+          kind: ast::LiteralKind::Nullptr(None),
+        }),
+      ],
+    };
+
+    let scoped_result = tuple.infer(&context);
+
+    let manual_result = {
+      let mut context = context.inherit(None);
+
+      let element_types = tuple
+        .elements
+        .iter()
+        .map(|element| context.visit(element))
+        .collect();
+
+      let ty = types::Type::Tuple(types::TupleType(element_types));
+
+      context.type_env.insert(tuple.type_id, ty.clone());
+
+      context.finalize(ty)
+    };
+
+    assert_eq!(
+      format!("{:?}", scoped_result.ty),
+      format!("{:?}", manual_result.ty)
+    );
+    assert_eq!(scoped_result.data.id_count, manual_result.data.id_count);
+
+    assert_eq!(
+      scoped_result.data.constraints.len(),
+      manual_result.data.constraints.len()
+    );
+
+    assert_eq!(
+      format!("{:?}", scoped_result.data.type_var_substitutions),
+      format!("{:?}", manual_result.data.type_var_substitutions)
+    );
+
+    assert_eq!(
+      scoped_result.data.type_env.len(),
+      manual_result.data.type_env.len()
+    );
+  }
+
+  #[test]
+  fn number_literal_type_hint_consistency() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 2);
+
+    let number_literal = |value, is_real, type_hint| ast::Literal {
+      type_id: symbol_table::TypeId(0),
+      kind: ast::LiteralKind::Number {
+        value,
+        is_real,
+        bit_width: types::BitWidth::Width32,
+        type_hint: Some(types::Type::Primitive(type_hint)),
+      },
+    };
+
+    // An integer literal with a real type hint is implicitly promoted.
+    assert!(matches!(
+      context.visit(&number_literal(
+        5_f64,
+        false,
+        types::PrimitiveType::Real(types::BitWidth::Width64)
+      )),
+      types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width64))
+    ));
+
+    assert!(context.diagnostics.is_empty());
+
+    context.visit(&number_literal(
+      2.5,
+      true,
+      types::PrimitiveType::Integer(types::BitWidth::Width32, true),
+    ));
+
+    assert!(matches!(
+      context.diagnostics.as_slice(),
+      [diagnostic::Diagnostic::RealLiteralWithIntegerTypeHint(..)]
+    ));
+  }
+
+  #[test]
+  fn division_always_real() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    context.set_division_semantics(DivisionSemantics::AlwaysReal);
+
+    assert!(matches!(
+      context.visit(&mock_division()),
+      types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width64))
+    ));
+  }
+
+  #[test]
+  fn constraint_deduplication() {
+    let symbol_table = symbol_table::SymbolTable::default();
+
+    // A match expression whose arm bodies all share the same type.
+    let match_ = ast::Match {
+      type_id: symbol_table::TypeId(1),
+      subject_type_id: symbol_table::TypeId(2),
+      subject: mock_integer_literal(0_f64),
+      arms: (0..50)
+        .map(|index| ast::MatchArm {
+          case: mock_integer_literal(index as f64),
+          body: mock_integer_literal(1_f64),
+        })
+        .collect(),
+      default_case: Some(mock_integer_literal(1_f64)),
+    };
+
+    let count_constraints = |is_constraint_deduplication_enabled| {
+      let mut context = InferenceContext::new(&symbol_table, None, 3);
+
+      context.set_constraint_deduplication_enabled(is_constraint_deduplication_enabled);
+      context.visit(&match_);
+
+      context.into_overall_result().constraints.len()
+    };
+
+    assert_eq!(101, count_constraints(false));
+
+    // The arm bodies' constraints (along with the default case's) collapse
+    // into one; those of the arm cases are distinguished by their origin.
+    assert_eq!(51, count_constraints(true));
+  }
+
+  #[test]
+  fn validate_foreign_function_hints() {
+    let mock_parameter = |name: &str, type_hint| {
+      std::rc::Rc::new(ast::Parameter {
+        registry_id: symbol_table::RegistryId(1),
+        type_id: symbol_table::TypeId(1),
+        name: name.to_string(),
+        position: 0,
+        type_hint,
+        pattern: None,
+      })
+    };
+
+    let foreign_function = ast::ForeignFunction {
+      registry_id: symbol_table::RegistryId(0),
+      type_id: symbol_table::TypeId(0),
+      name: String::from("puts"),
+      signature: std::rc::Rc::new(ast::Signature {
+        parameters: vec![
+          mock_parameter("a", Some(types::Type::Opaque)),
+          mock_parameter("b", None),
+        ],
+        return_type_hint: None,
+        is_variadic: false,
+        kind: ast::SignatureKind::ForeignFunction,
+        return_type_id: symbol_table::TypeId(2),
+      }),
+    };
+
+    assert!(matches!(
+      InferenceContext::validate_foreign_function_hints(&foreign_function)
+        .unwrap_err()
+        .as_slice(),
+      [
+        diagnostic::Diagnostic::ForeignFunctionMissingTypeHint {
+          parameter_name: Some(parameter_name),
+          ..
+        },
+        diagnostic::Diagnostic::ForeignFunctionMissingTypeHint {
+          parameter_name: None,
+          ..
+        },
+      ] if parameter_name == "b"
+    ));
+
+    // Inference proceeds regardless, reporting the missing type hints.
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 3);
+
+    context.visit(&foreign_function);
+
+    assert_eq!(2, context.diagnostics.len());
+  }
+
+  #[test]
+  fn constrain_sizeof_operand() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let context = InferenceContext::new(&symbol_table, None, 0);
+
+    let tuple_type = types::Type::Tuple(types::TupleType(vec![
+      types::Type::Primitive(types::PrimitiveType::Bool),
+      types::Type::Opaque.into_pointer_type(),
+    ]));
+
+    assert!(context.constrain_sizeof_operand(&tuple_type).is_ok());
+
+    assert!(matches!(
+      context
+        .constrain_sizeof_operand(&types::Type::Opaque)
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::SizeofOperandUnsized(
+        types::Type::Opaque
+      )]
+    ));
+
+    // Unsized types nested within composite types are also detected.
+    assert!(context
+      .constrain_sizeof_operand(&types::Type::Tuple(types::TupleType(vec![
+        types::Type::Range(0, 1)
+      ])))
+      .is_err());
+  }
+
+  #[test]
+  fn register_union_type() {
+    let union = std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("Shape"),
+      variants: std::collections::BTreeMap::new(),
+    });
+
+    // The union is intentionally absent from the symbol table, thus it can
+    // only be found if it was pre-registered.
+    let symbol_table = symbol_table::SymbolTable::default();
+    let mut context = InferenceContext::new(&symbol_table, None, 0);
+
+    context.register_union_type(&union);
+
+    let child_context = context.inherit(None);
+
+    assert!(matches!(
+      child_context.find_union_type(&symbol_table::RegistryId(0)),
+      types::Type::Union(registered_union) if std::rc::Rc::ptr_eq(&registered_union, &union)
+    ));
+  }
+
+  #[test]
+  fn check_pattern_exhaustiveness() {
+    let mock_variant = |name: &str, relative_index| {
+      (
+        name.to_string(),
+        std::rc::Rc::new(ast::UnionVariant {
+          registry_id: symbol_table::RegistryId(relative_index as usize + 1),
+          union_id: symbol_table::RegistryId(0),
+          name: name.to_string(),
+          kind: ast::UnionVariantKind::Singleton {
+            name: name.to_string(),
+            relative_index,
+            explicit_value: None,
+          },
+        }),
+      )
+    };
+
+    let union_type = types::Type::Union(std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("color"),
+      variants: std::collections::BTreeMap::from([mock_variant("red", 0), mock_variant("blue", 1)]),
+    }));
+
+    let red_case = ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+      type_id: symbol_table::TypeId(0),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(0),
+        qualifier: None,
+        base_name: String::from("color"),
+        sub_name: Some(String::from("red")),
+        symbol_kind: symbol_table::SymbolKind::Declaration,
+      },
+    }));
+
+    let default_case = ast::Expr::Pass(std::rc::Rc::new(ast::Pass));
+
+    assert!(matches!(
+      InferenceContext::check_pattern_exhaustiveness(&union_type, &[&red_case], None)
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::NonExhaustiveMatch { missing_variants, .. }]
+        if missing_variants == &[String::from("blue")]
+    ));
+
+    assert!(InferenceContext::check_pattern_exhaustiveness(
+      &union_type,
+      &[&red_case],
+      Some(&default_case)
+    )
+    .is_ok());
+
+    // The cases of non-union subjects cannot be enumerated.
+    assert!(matches!(
+      InferenceContext::check_pattern_exhaustiveness(&types::Type::Unit, &[&red_case], None)
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::MatchMissingDefaultCase(
+        types::Type::Unit
+      )]
+    ));
+  }
+}
@@ -5,7 +5,9 @@
 //! which mainly consists of the creation of "universes", which are then used on later
 //! phases of the compiler to resolve generics, polymorphic types, and other artifacts.
 
-use crate::{ast, auxiliary, diagnostic, inference, resolution, symbol_table, types, unification};
+use crate::{
+  ast, auxiliary, diagnostic, inference, resolution, substitution, symbol_table, types, unification,
+};
 
 pub(crate) type ReverseUniverseTracker =
   std::collections::HashMap<symbol_table::RegistryId, Vec<symbol_table::UniverseId>>;
@@ -91,6 +93,11 @@ impl<'a> InstantiationHelper<'a> {
     type_b: types::Type,
     symbol_table: &symbol_table::SymbolTable,
   ) -> bool {
+    // Avoid unification entirely when possible.
+    if let Some(result) = type_a.concrete_eq(&type_b) {
+      return result;
+    }
+
     // Both input types should be fully monomorphic, otherwise
     // instantiation would be needed to unify them properly.
     if type_a.is_a_generic()
@@ -110,10 +117,16 @@ impl<'a> InstantiationHelper<'a> {
       &universes,
     );
 
-    let constraints = vec![inference::Constraint::Equality(type_a, type_b)]
-      .into_iter()
-      .map(|constraint| (resolution::UniverseStack::new(), constraint))
-      .collect();
+    let constraints = vec![inference::Constraint::Compatibility {
+      a: type_a,
+      b: type_b,
+      variance: inference::Variance::Invariant,
+      origin: None,
+      type_id: None,
+    }]
+    .into_iter()
+    .map(|constraint| (resolution::UniverseStack::new(), constraint))
+    .collect();
 
     type_unification_context
       .solve_constraints(&symbol_table::TypeEnvironment::new(), &constraints)
@@ -127,6 +140,91 @@ impl<'a> InstantiationHelper<'a> {
     }
   }
 
+  /// Find the substitution of a generic type within the given universe stack.
+  ///
+  /// Universes are searched from the innermost (last) to the outermost (first),
+  /// so that closer universes take priority. The universes are given explicitly,
+  /// since this lookup is also performed after instantiation has concluded (ie.
+  /// during resolution), once the universes are no longer owned by a helper.
+  pub(crate) fn find_substitution<'b>(
+    universes: &'b TypeSchemes,
+    generic_id: &symbol_table::SubstitutionId,
+    universe_stack: &resolution::UniverseStack,
+  ) -> Result<&'b types::Type, resolution::TypeResolutionError> {
+    // Generics cannot be resolved without any context artifact id and
+    // thus no corresponding substitution environment.
+    if universe_stack.is_empty() {
+      return Err(resolution::TypeResolutionError::EmptyUniverseStackWhenResolvingGeneric);
+    } else if universes.is_empty() {
+      return Err(resolution::TypeResolutionError::NoUniversesWhenResolvingGeneric);
+    }
+
+    // REVIEW: More research and review is needed to ensure that this approach works for all cases. Perhaps implement more tests, but also need to intuitively understand how this works 100%.
+    // FIXME: Regarding the case where an infinite loop would occur, what happens then if the search is still done in reverse, but finds no entry at the beginning (goes until the end), then it would reach an infinite loop causing stack-overflow? If that's the case, we'd need an assertion and a minor tweak to prevent such a case. Of course, we'd only use an assertion if such case is considered a logic bug.
+    // NOTE: The iteration order here is crucial; by iterating in reverse,
+    // closer universes are prioritized. This is important because in the case
+    // that an artifact uses a polymorphic item multiple times, at the same time,
+    // (ie. `Id<Id<T>>`), the innermost artifact should be granted priority,
+    // otherwise, it may lead to a situation where the first artifact keeps
+    // on getting selected as the resolution universe, and may lead to an
+    // infinite loop (which may cause a stack overflow).
+    for universe_id in universe_stack.iter().rev() {
+      let universe = universes
+        .get(universe_id)
+        .ok_or(resolution::TypeResolutionError::MissingUniverse)?;
+
+      if let Some(substitution) = universe.get(generic_id) {
+        return Ok(substitution);
+      }
+    }
+
+    Err(resolution::TypeResolutionError::CouldNotFindSubstitutionInAnyUniverseInUniverseStack)
+  }
+
+  /// Specialize the given type environment for a universe, by substituting all
+  /// generics within its entries with their corresponding monomorphic types.
+  ///
+  /// Like `find_substitution`, this is performed once instantiation has
+  /// concluded, thus the universes are given explicitly.
+  pub(crate) fn apply_universe_to_type_env(
+    symbol_table: &symbol_table::SymbolTable,
+    universes: &TypeSchemes,
+    base_env: &symbol_table::TypeEnvironment,
+    universe_id: &symbol_table::UniverseId,
+  ) -> diagnostic::Maybe<symbol_table::TypeEnvironment> {
+    let universe = universes
+      .get(universe_id)
+      .expect("the universe should have been created during instantiation");
+
+    let substitution_helper = substitution::UnificationSubstitutionHelper {
+      symbol_table,
+      substitution_env: universe,
+    };
+
+    substitution_helper
+      .substitute_batch(base_env)
+      .map_err(|(type_id, substitution_error)| match substitution_error {
+        substitution::SubstitutionError::RecursiveStubType(stub_type) => {
+          vec![diagnostic::Diagnostic::RecursiveType(types::Type::Stub(
+            Box::new(stub_type),
+          ))]
+        }
+        substitution::SubstitutionError::TypeStripError(
+          types::TypeStripError::RecursionDetected,
+        ) => {
+          vec![diagnostic::Diagnostic::RecursiveType(
+            base_env[&type_id].to_owned(),
+          )]
+        }
+        substitution::SubstitutionError::TypeStripError(
+          types::TypeStripError::SymbolTableMissingEntry,
+        )
+        | substitution::SubstitutionError::DirectRecursionCheckError(
+          types::DirectRecursionCheckError::SymbolTableMissingEntry,
+        ) => unreachable!("{}", auxiliary::BUG_NAME_RESOLUTION),
+      })
+  }
+
   /// The entry point of the instantiation process.
   pub(crate) fn instantiate_all_artifacts(mut self) -> (TypeSchemes, Vec<diagnostic::Diagnostic>) {
     let mut diagnostics_helper = diagnostic::DiagnosticsHelper::default();
@@ -185,6 +283,24 @@ impl<'a> InstantiationHelper<'a> {
       "stub type should have generic hints, and thus be polymorphic, otherwise it should not be registered as an artifact"
     );
 
+    match self.create_universe_for_stub_type(stub_type) {
+      Ok(_) => Vec::default(),
+      Err(diagnostics) => diagnostics,
+    }
+  }
+
+  /// Create and register the universe of a polymorphic stub type, in which
+  /// the generic parameters of its target are substituted with its generic
+  /// hints.
+  ///
+  /// The hints are stripped of their monomorphic stub layers beforehand, so
+  /// that substituting a generic parameter yields the hint's target directly.
+  /// The universe id is allocated when the stub type is parsed, and is
+  /// returned once its universe is registered.
+  pub(crate) fn create_universe_for_stub_type(
+    &mut self,
+    stub_type: &types::StubType,
+  ) -> diagnostic::Maybe<symbol_table::UniverseId> {
     // REVIEW: What if the target is an artifact that accepts generics, but none were provided? Should that be reported here?
     assert!(
       !stub_type.generic_hints.is_empty(),
@@ -202,12 +318,36 @@ impl<'a> InstantiationHelper<'a> {
       _ => todo!(),
     };
 
+    let stripped_hints = stub_type
+      .generic_hints
+      .iter()
+      .map(|hint| {
+        hint
+          .clone()
+          .try_strip_all_monomorphic_stub_layers(self.symbol_table)
+          .map_err(|type_strip_error| match type_strip_error {
+            types::TypeStripError::RecursionDetected => {
+              vec![diagnostic::Diagnostic::RecursiveType(hint.to_owned())]
+            }
+            types::TypeStripError::SymbolTableMissingEntry => {
+              unreachable!("{}", auxiliary::BUG_NAME_RESOLUTION)
+            }
+          })
+      })
+      .collect::<diagnostic::Maybe<Vec<_>>>()?;
+
     // TODO: When unions are handled, this will need to be changed to a match case to extract the generics object. This way, the logic is more generalized to the generics object, and not just type defs.
-    self.create_universe_for(
+    let diagnostics = self.create_universe_for(
       stub_type.universe_id.to_owned(),
-      &stub_type.generic_hints,
+      &stripped_hints,
       &target_type_def.generics,
-    )
+    );
+
+    if !diagnostics.is_empty() {
+      return Err(diagnostics);
+    }
+
+    Ok(stub_type.universe_id.to_owned())
   }
 
   fn instantiate_call_site(&mut self, call_site: &ast::CallSite) -> Vec<diagnostic::Diagnostic> {
@@ -237,3 +377,165 @@ impl<'a> InstantiationHelper<'a> {
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn create_universe_for_stub_type() {
+    let mut symbol_table = symbol_table::SymbolTable::default();
+    let generic_id = symbol_table::SubstitutionId(0);
+
+    let mock_type_def = |registry_id, body, parameters| {
+      symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+        registry_id,
+        name: String::from("test"),
+        body,
+        generics: ast::Generics { parameters },
+      }))
+    };
+
+    let mock_stub_type = |link_id, universe_id, generic_hints| types::StubType {
+      universe_id: symbol_table::UniverseId(universe_id, String::from("test")),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(link_id),
+        qualifier: None,
+        base_name: String::from("test"),
+        sub_name: None,
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints,
+    };
+
+    let generic_type = types::GenericType {
+      name: String::from("T"),
+      registry_id: symbol_table::RegistryId(2),
+      substitution_id: generic_id,
+    };
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(0),
+      mock_type_def(
+        symbol_table::RegistryId(0),
+        types::Type::Generic(generic_type.clone()),
+        vec![generic_type],
+      ),
+    );
+
+    symbol_table.registry.insert(
+      symbol_table::RegistryId(1),
+      mock_type_def(
+        symbol_table::RegistryId(1),
+        types::Type::Primitive(types::PrimitiveType::Bool),
+        Vec::new(),
+      ),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(0), symbol_table::RegistryId(0));
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(1), symbol_table::RegistryId(1));
+
+    let mut instantiation_helper = InstantiationHelper::new(&symbol_table);
+    let hint = types::Type::Stub(Box::new(mock_stub_type(1, 1, Vec::new())));
+    let stub_type = mock_stub_type(0, 0, vec![hint]);
+
+    let universe_id = instantiation_helper
+      .create_universe_for_stub_type(&stub_type)
+      .unwrap();
+
+    assert_eq!(stub_type.universe_id, universe_id);
+
+    // The hint's monomorphic stub layers should have been stripped.
+    assert!(matches!(
+      instantiation_helper.universes[&universe_id].get(&generic_id),
+      Some(types::Type::Primitive(types::PrimitiveType::Bool))
+    ));
+  }
+
+  #[test]
+  fn find_substitution_in_outer_universe() {
+    let outer_universe_id = symbol_table::UniverseId(0, String::from("outer"));
+    let inner_universe_id = symbol_table::UniverseId(1, String::from("inner"));
+    let generic_id = symbol_table::SubstitutionId(0);
+
+    let universes = TypeSchemes::from([
+      (
+        outer_universe_id.clone(),
+        symbol_table::SubstitutionEnv::from([(generic_id, types::Type::Opaque)]),
+      ),
+      (
+        inner_universe_id.clone(),
+        symbol_table::SubstitutionEnv::new(),
+      ),
+    ]);
+
+    let universe_stack = vec![outer_universe_id, inner_universe_id];
+
+    assert!(matches!(
+      InstantiationHelper::find_substitution(&universes, &generic_id, &universe_stack),
+      Ok(types::Type::Opaque)
+    ));
+  }
+
+  #[test]
+  fn find_substitution_missing() {
+    let universe_id = symbol_table::UniverseId(0, String::from("test"));
+
+    let universes =
+      TypeSchemes::from([(universe_id.clone(), symbol_table::SubstitutionEnv::new())]);
+
+    assert!(matches!(
+      InstantiationHelper::find_substitution(
+        &universes,
+        &symbol_table::SubstitutionId(0),
+        &vec![universe_id]
+      ),
+      Err(resolution::TypeResolutionError::CouldNotFindSubstitutionInAnyUniverseInUniverseStack)
+    ));
+  }
+
+  #[test]
+  fn apply_universe_to_type_env() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universe_id = symbol_table::UniverseId(0, String::from("test"));
+
+    let generic_type = types::Type::Generic(types::GenericType {
+      name: String::from("T"),
+      registry_id: symbol_table::RegistryId(0),
+      substitution_id: symbol_table::SubstitutionId(0),
+    });
+
+    let universes = TypeSchemes::from([(
+      universe_id.clone(),
+      symbol_table::SubstitutionEnv::from([(symbol_table::SubstitutionId(0), types::Type::Unit)]),
+    )]);
+
+    let base_env = symbol_table::TypeEnvironment::from([
+      (symbol_table::TypeId(0), generic_type.clone()),
+      (symbol_table::TypeId(1), generic_type.into_pointer_type()),
+    ]);
+
+    let type_env = InstantiationHelper::apply_universe_to_type_env(
+      &symbol_table,
+      &universes,
+      &base_env,
+      &universe_id,
+    )
+    .unwrap();
+
+    assert!(matches!(
+      type_env.get(&symbol_table::TypeId(0)),
+      Some(types::Type::Unit)
+    ));
+
+    assert!(matches!(
+      type_env.get(&symbol_table::TypeId(1)),
+      Some(types::Type::Pointer(pointee)) if matches!(pointee.as_ref(), types::Type::Unit)
+    ));
+  }
+}
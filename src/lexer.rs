@@ -84,6 +84,7 @@ pub enum TokenKind {
   FatArrow,
   EllipsisLong,
   Sizeof,
+  Typeof,
   Pipe,
   Const,
   Elif,
@@ -113,6 +114,8 @@ pub struct Lexer {
   seen_only_whitespace_this_line: bool,
   indent_level: usize,
   indent_counter: usize,
+  /// The position at which the token currently being lexed begins.
+  token_start: usize,
 }
 
 // TODO: Handle unicode vs. ASCII. Consider emitting error string (`Result`) when a unicode (or non-ASCII) character is encountered.
@@ -156,6 +159,7 @@ impl Lexer {
       "false" => TokenKind::Bool(false),
       "import" => TokenKind::Import,
       "sizeof" => TokenKind::Sizeof,
+      "typeof" => TokenKind::Typeof,
       "const" => TokenKind::Const,
       "elif" => TokenKind::Elif,
       "as" => TokenKind::As,
@@ -217,6 +221,7 @@ impl Lexer {
       seen_only_whitespace_this_line: true,
       indent_level: 0,
       indent_counter: 0,
+      token_start: 0,
     }
   }
 
@@ -661,6 +666,9 @@ impl Lexer {
 
     self.update_indentation_counter();
 
+    // The indentation that was skipped is not part of the token being lexed.
+    self.token_start = self.index;
+
     // Update the current character, since it has changed because of the
     // `read_char` sub-calls. Otherwise if there are no more characters, then
     // `EOF` has been reached.
@@ -695,10 +703,11 @@ impl Lexer {
   }
 
   fn lex_token(&mut self) -> diagnostic::Maybe<Option<Token>> {
-    let start = self.index;
+    self.token_start = self.index;
+
     let token_kind = self.lex_token_kind()?;
 
-    Ok(token_kind.map(|token_kind| Token(token_kind, start)))
+    Ok(token_kind.map(|token_kind| Token(token_kind, self.token_start)))
   }
 }
 
@@ -970,6 +979,17 @@ mod tests {
     assert_eq!(7, result.len());
   }
 
+  #[test]
+  fn token_position_excludes_indentation() {
+    let result = Lexer::lex_all("a:\n  b").expect("lexing should succeed");
+
+    let identifier = result
+      .iter()
+      .find(|token| matches!(&token.0, TokenKind::Identifier(name) if name == "b"));
+
+    assert!(matches!(identifier, Some(Token(_, 5))));
+  }
+
   #[test]
   fn read_while() {
     const TEST_INPUT: &str = "123456";
@@ -102,7 +102,7 @@ impl<'a> visit::Visitor for LifetimeAnalysisContext<'a> {
       .expect(auxiliary::BUG_MISSING_TYPE);
 
     // SAFETY: What if the reference comes from the arguments? We might need to attach lifetimes to declarations.
-    if matches!(yield_type.as_ref(), types::Type::Reference(_)) {
+    if yield_type.is_reference() {
       self
         .diagnostics
         .push(diagnostic::Diagnostic::CannotYieldTemporaryReference);
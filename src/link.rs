@@ -231,10 +231,9 @@ impl<'a> visit::Visitor for LinkContext<'a> {
       } else {
         self
           .diagnostics
-          .push(diagnostic::Diagnostic::QualifiedSymbolNotFound(format!(
-            "{}::{}::{}",
-            qualifier.package_name, qualifier.module_name, path.base_name
-          )));
+          .push(diagnostic::Diagnostic::QualifiedSymbolNotFound(
+            path.to_string(),
+          ));
       }
 
       return;
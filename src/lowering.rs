@@ -164,15 +164,30 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
   ) -> Option<inkwell::values::BasicValueEnum<'llvm>> {
     // TODO: Assert `tuple_indexing.indexed_tuple` is an LLVM struct type.
 
-    let llvm_struct_ptr = self
+    let llvm_tuple = self
       // Do not access the tuple, since the following code expects a pointer
-      // to a struct alloca, and there are no constant structs.
+      // to a struct alloca, unless the tuple is a value (ie. a parameter).
       .lower_with_access_mode(
         &tuple_indexing.indexed_tuple,
         lowering_ctx::AccessMode::None,
       )
-      .expect(lowering_ctx::BUG_LLVM_VALUE)
-      .into_pointer_value();
+      .expect(lowering_ctx::BUG_LLVM_VALUE);
+
+    if !llvm_tuple.is_pointer_value() {
+      return Some(
+        self
+          .llvm_builder
+          .build_extract_value(
+            llvm_tuple.into_struct_value(),
+            tuple_indexing.index,
+            "tuple.field.extract",
+          )
+          // REVISE: Prefer `expect`, and consider using a const global reason.
+          .unwrap(),
+      );
+    }
+
+    let llvm_struct_ptr = llvm_tuple.into_pointer_value();
 
     let llvm_field_type = self.lower_type_by_id(&tuple_indexing.type_id);
 
@@ -220,6 +235,10 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
 
   fn visit_tuple(&mut self, tuple: &ast::Tuple) -> Option<inkwell::values::BasicValueEnum<'llvm>> {
     // If the tuple's type is unit, do not proceed to allocate it.
+    if tuple.elements.is_empty() {
+      return Some(self.make_llvm_unit_value().as_basic_value_enum());
+    }
+
     let llvm_struct_type = self.lower_type_by_id(&tuple.type_id).into_struct_type();
 
     let llvm_struct_alloca = self.alloca(llvm_struct_type, "tuple.alloca");
@@ -398,9 +417,43 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
     &mut self,
     sizeof: &ast::Sizeof,
   ) -> Option<inkwell::values::BasicValueEnum<'llvm>> {
+    let resolved_type = self.resolve_type(&sizeof.ty);
+
     // NOTE: Unit types have a size of zero. If they are pointers, or any
     // other composite types, they are no longer considered unit types.
     // This is because even pointer types have a size.
+    if resolved_type.is_zst() {
+      // The size of zero-sized types is known at compile time, thus there
+      // is no need to defer to the layout of their lowered type.
+      return Some(
+        self
+          .llvm_module
+          .get_context()
+          .i64_type()
+          .const_zero()
+          .as_basic_value_enum(),
+      );
+    }
+
+    // Likewise, the size of primitive types with a fixed bit width is known
+    // at compile time. Sub-byte widths (ie. booleans) are rounded up to a
+    // whole byte.
+    let bit_width_opt = match resolved_type.as_ref() {
+      types::Type::Primitive(primitive_type) => primitive_type.bit_width(),
+      _ => None,
+    };
+
+    if let Some(bit_width) = bit_width_opt {
+      return Some(
+        self
+          .llvm_module
+          .get_context()
+          .i64_type()
+          .const_int(u64::from(bit_width.size_in_bytes()), false)
+          .as_basic_value_enum(),
+      );
+    }
+
     let llvm_size = self
       .lower_type(&sizeof.ty)
       .size_of()
@@ -444,9 +497,8 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
     let llvm_field = if llvm_struct.is_pointer_value() {
       let llvm_struct_ptr = llvm_struct.into_pointer_value();
 
-      let target_field_type = object_type
-        .fields
-        .get(&object_access.field_name)
+      let target_field_type = base_expr_type
+        .project_field(&object_access.field_name)
         .expect(BUG_FIELD_MISSING);
 
       let llvm_field_type = self.lower_type(target_field_type);
@@ -623,6 +675,21 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
       types::Type::Primitive(types::PrimitiveType::Integer(..))
     );
 
+    let are_operands_signed = matches!(
+      operand_type.as_ref(),
+      types::Type::Primitive(types::PrimitiveType::Integer(_, true))
+    );
+
+    // When division is configured to always yield a real number, its
+    // operands may still be integers, in which case they must be converted
+    // before the division is performed.
+    let is_real_division_of_integers = binary_op.operator == ast::BinaryOperator::Divide
+      && both_operands_are_of_type_int
+      && matches!(
+        self.resolve_type_by_id(&binary_op.type_id).as_ref(),
+        types::Type::Primitive(types::PrimitiveType::Real(..))
+      );
+
     // A runtime assertion guard must be inserted on all division operations.
     if binary_op.operator == ast::BinaryOperator::Divide {
       let llvm_zero = self
@@ -720,6 +787,33 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
         )
         .expect(BUG_BUILDER_UNSET)
         .as_basic_value_enum(),
+      ast::BinaryOperator::Divide if is_real_division_of_integers => {
+        let llvm_real_type = self.lower_type_by_id(&binary_op.type_id).into_float_type();
+
+        let [llvm_left_operand, llvm_right_operand] =
+          [llvm_left_operand, llvm_right_operand].map(|llvm_operand| {
+            if are_operands_signed {
+              self.llvm_builder.build_signed_int_to_float(
+                llvm_operand.into_int_value(),
+                llvm_real_type,
+                "int.divide_op.to_real",
+              )
+            } else {
+              self.llvm_builder.build_unsigned_int_to_float(
+                llvm_operand.into_int_value(),
+                llvm_real_type,
+                "int.divide_op.to_real",
+              )
+            }
+            .expect(BUG_BUILDER_UNSET)
+          });
+
+        self
+          .llvm_builder
+          .build_float_div(llvm_left_operand, llvm_right_operand, "float.divide_op")
+          .expect(BUG_BUILDER_UNSET)
+          .as_basic_value_enum()
+      }
       // TODO: Support for unsgined division?
       ast::BinaryOperator::Divide if both_operands_are_of_type_int => self
         .llvm_builder
@@ -1044,21 +1138,11 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
       "the same foreign function should not be lowered twice"
     );
 
-    let signature_type = foreign_fn
-      .signature
-      .as_signature_type(
-        foreign_fn
-          .signature
-          .return_type_hint
-          .as_ref()
-          .expect(auxiliary::BUG_FOREIGN_FN_TYPE_HINTS)
-          .to_owned(),
-        self.resolution_helper,
-        self.universe_stack.clone(),
-      )
-      .expect(auxiliary::BUG_MISSING_TYPE);
-
-    let llvm_signature_type = self.lower_signature_type(&signature_type, None);
+    // NOTE: Type inference reports foreign functions with missing type hints,
+    // thus the inferred signature type is the one given by its hints.
+    let foreign_fn_type = self.resolve_type_by_id(&foreign_fn.type_id);
+    let signature_type = assert_extract!(foreign_fn_type.as_ref(), types::Type::Signature);
+    let llvm_signature_type = self.lower_signature_type(signature_type, None);
 
     assert!(
       self.llvm_module.get_function(&foreign_fn.name).is_none(),
@@ -1176,6 +1260,18 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
         bit_width,
         ..
       } => {
+        // NOTE: Number literals adopt the type of the context in which they
+        // are used, thus an integer literal may have been coerced into a
+        // different bit width, or promoted into a real number. The resolved
+        // type takes precedence over the literal's own kind and bit width.
+        let (is_real, bit_width) = match self.resolve_type_by_id(&literal.type_id).as_ref() {
+          types::Type::Primitive(types::PrimitiveType::Real(bit_width)) => (true, *bit_width),
+          types::Type::Primitive(types::PrimitiveType::Integer(bit_width, _)) => {
+            (false, *bit_width)
+          }
+          _ => (*is_real, *bit_width),
+        };
+
         let llvm_float_type = || match bit_width {
           types::BitWidth::Width16 => llvm_context.f16_type(),
           types::BitWidth::Width32 => llvm_context.f32_type(),
@@ -1184,17 +1280,9 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
           _ => llvm_context.f16_type(),
         };
 
-        let llvm_int_type = || {
-          llvm_context.custom_width_int_type(match bit_width {
-            types::BitWidth::Width8 => 8,
-            types::BitWidth::Width16 => 16,
-            types::BitWidth::Width32 => 32,
-            types::BitWidth::Width64 => 64,
-            types::BitWidth::Width128 => 128,
-          })
-        };
+        let llvm_int_type = || llvm_context.custom_width_int_type(bit_width.bits());
 
-        if *is_real {
+        if is_real {
           llvm_float_type().const_float(*value).as_basic_value_enum()
         } else {
           llvm_int_type()
@@ -1277,7 +1365,18 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
           .resolve_by_id(&unary_op.operand_type_id, self.universe_stack.clone())
           .expect(auxiliary::BUG_MISSING_TYPE);
 
-        let pointee_type = assert_extract!(operand_type.as_ref(), types::Type::Pointer);
+        // References are lowered as pointers, but can never be null, so
+        // they don't require a null guard.
+        let (pointee_type, is_reference) = match operand_type.peel_reference() {
+          Some(referent) => (referent.to_owned(), true),
+          None => (
+            assert_extract!(operand_type.as_ref(), types::Type::Pointer)
+              .as_ref()
+              .to_owned(),
+            false,
+          ),
+        };
+
         let llvm_pointe_type = self.lower_type(&pointee_type);
 
         let llvm_pointer_value = self
@@ -1285,28 +1384,30 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
           .expect(lowering_ctx::BUG_LLVM_VALUE)
           .into_pointer_value();
 
-        let llvm_is_null = self
-          .llvm_builder
-          .build_is_null(llvm_pointer_value, "is_null")
-          .expect(BUG_BUILDER_UNSET)
-          .as_basic_value_enum();
+        if !is_reference {
+          let llvm_is_null = self
+            .llvm_builder
+            .build_is_null(llvm_pointer_value, "is_null")
+            .expect(BUG_BUILDER_UNSET)
+            .as_basic_value_enum();
 
-        let llvm_is_not_null = self
-          .llvm_builder
-          .build_int_compare(
-            inkwell::IntPredicate::EQ,
-            llvm_is_null.into_int_value(),
-            self.llvm_module.get_context().bool_type().const_zero(),
-            "is_not_null",
-          )
-          .expect(BUG_BUILDER_UNSET);
+          let llvm_is_not_null = self
+            .llvm_builder
+            .build_int_compare(
+              inkwell::IntPredicate::EQ,
+              llvm_is_null.into_int_value(),
+              self.llvm_module.get_context().bool_type().const_zero(),
+              "is_not_null",
+            )
+            .expect(BUG_BUILDER_UNSET);
 
-        // All dereference operations must be checked for null pointers.
-        // Dereferencing a null pointer is undefined behavior.
-        self.insert_runtime_guard(
-          llvm_is_not_null,
-          lowering_ctx::RuntimeGuard::NullDereference,
-        );
+          // All dereference operations must be checked for null pointers.
+          // Dereferencing a null pointer is undefined behavior.
+          self.insert_runtime_guard(
+            llvm_is_not_null,
+            lowering_ctx::RuntimeGuard::NullDereference,
+          );
+        }
 
         // BUG: (tag:access) If the value is a reference to a let-statement, the pointer of the let-statement will be removed, but the actual pointer value will not be accessed. Perhaps will need to remove special treatment of let-statements.
         self.force_access(llvm_pointe_type, llvm_pointer_value, "dereference_op")
@@ -1342,7 +1443,7 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
       .expect(lowering_ctx::BUG_LLVM_VALUE)
       .as_basic_value_enum();
 
-    let is_reference = matches!(value_type, types::Type::Reference(_));
+    let is_reference = value_type.is_reference();
 
     let llvm_final_value = if is_reference {
       let llvm_alloca = self.alloca(llvm_value_type, "binding.reference.alloca");
@@ -1371,14 +1472,42 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
   ) -> Option<inkwell::values::BasicValueEnum<'llvm>> {
     // REVISE: Break function apart and avoid repeated code.
 
+    let call_site_signature_type = assert_extract!(
+      self
+        .resolve_type_by_id(&call_site.callee_type_id)
+        .into_owned(),
+      types::Type::Signature
+    );
+
+    // Arguments past the required parameters of a variadic callee undergo
+    // C's default argument promotions, and pointer decay.
+    let first_variadic_index = match call_site_signature_type.arity_mode {
+      types::ArityMode::Variadic {
+        minimum_required_parameters,
+      } => Some(minimum_required_parameters),
+      types::ArityMode::Fixed => None,
+    };
+
     let mut llvm_arguments = call_site
       .arguments
       .iter()
-      .map(|argument| {
-        self
+      .enumerate()
+      .map(|(index, argument)| {
+        let llvm_argument = self
           .lower_with_access_mode(&argument.value, lowering_ctx::AccessMode::Value)
-          .unwrap_or_else(|| self.make_llvm_unit_value().as_basic_value_enum())
-          .into()
+          .unwrap_or_else(|| self.make_llvm_unit_value().as_basic_value_enum());
+
+        let is_variadic_argument = matches!(
+          first_variadic_index,
+          Some(first_variadic_index) if index >= first_variadic_index
+        );
+
+        if is_variadic_argument {
+          self.build_variadic_promotion(llvm_argument, &argument.type_id)
+        } else {
+          llvm_argument
+        }
+        .into()
       })
       .collect::<Vec<_>>();
 
@@ -1614,6 +1743,13 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
         .build_return(None)
         .expect(BUG_BUILDER_UNSET);
     } else {
+      // The body may yield a subtype of the return type if subtyping is
+      // enabled, in which case its value must be converted.
+      let body_type = self.resolve_type_by_id(&function.body.type_id).into_owned();
+
+      let llvm_body = llvm_body
+        .map(|llvm_body| self.build_coercion(llvm_body, &body_type, &signature_type.return_type));
+
       self.try_build_ret(llvm_body);
     }
 
@@ -1793,8 +1929,16 @@ impl<'a, 'llvm> visit::Visitor<Option<inkwell::values::BasicValueEnum<'llvm>>>
     &mut self,
     pointer_indexing: &ast::PointerIndexing,
   ) -> Option<inkwell::values::BasicValueEnum<'llvm>> {
-    let pointer_type = self.resolve_type_by_id(&pointer_indexing.type_id);
-    let pointee_type = assert_extract!(pointer_type.as_ref(), types::Type::Pointer);
+    // OPTIMIZE: Avoid cloning; only cloning to satisfy borrow checker.
+    let pointer_type = self
+      .resolve_type_by_id(&pointer_indexing.type_id)
+      .into_owned();
+
+    if let types::Type::Array { element, .. } = &pointer_type {
+      return Some(self.lower_array_indexing(pointer_indexing, &pointer_type, element));
+    }
+
+    let pointee_type = assert_extract!(pointer_type, types::Type::Pointer);
     let llvm_pointee_type = self.lower_type(&pointee_type);
 
     let llvm_pointer = self
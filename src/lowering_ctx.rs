@@ -9,6 +9,9 @@ use inkwell::{
 
 pub(crate) const BUG_LLVM_VALUE: &str = "should always yield an LLVM value";
 
+const BUG_ARRAY_LENGTH: &str =
+  "array lengths should have been verified to fit within 32 bits during semantic checking";
+
 const BUG_INSTANTIATION: &str =
   "a corresponding instantiation should exist, and the universe id should be valid";
 
@@ -227,9 +230,15 @@ impl<'a, 'llvm> LoweringContext<'a, 'llvm> {
     let constraints = set_a
       .iter()
       .zip(set_b.iter())
-      .map(|(monomorphism_type, given_type)| {
-        inference::Constraint::Equality(monomorphism_type.to_owned(), given_type.to_owned())
-      })
+      .map(
+        |(monomorphism_type, given_type)| inference::Constraint::Compatibility {
+          a: monomorphism_type.to_owned(),
+          b: given_type.to_owned(),
+          variance: inference::Variance::Invariant,
+          origin: None,
+          type_id: None,
+        },
+      )
       .map(|constraint| (resolution::UniverseStack::new(), constraint))
       .collect::<Vec<_>>();
 
@@ -503,11 +512,15 @@ impl<'a, 'llvm> LoweringContext<'a, 'llvm> {
           .struct_type(&llvm_field_types, false)
           .as_basic_type_enum()
       }
+      types::Type::Array { element, length } => self
+        .lower_type(element)
+        .array_type(u32::try_from(*length).expect(BUG_ARRAY_LENGTH))
+        .as_basic_type_enum(),
       types::Type::Stub(_) => unreachable!(
         "stub type layers should have been stripped when the type being matched was resolved"
       ),
       types::Type::Generic(..) => unreachable!("generic types should have been fully resolved"),
-      types::Type::Range(..) | types::Type::Variable { .. } => {
+      types::Type::Range(..) | types::Type::Variable { .. } | types::Type::Typeof(..) => {
         unreachable!("meta types should not be present after the type unification phase")
       }
     }
@@ -527,17 +540,13 @@ impl<'a, 'llvm> LoweringContext<'a, 'llvm> {
         .ptr_type(inkwell::AddressSpace::default())
         .as_basic_type_enum(),
       types::PrimitiveType::Integer(width, _) => llvm_context
-        .custom_width_int_type(match width {
-          types::BitWidth::Width8 => 8,
-          types::BitWidth::Width16 => 16,
-          types::BitWidth::Width32 => 32,
-          types::BitWidth::Width64 => 64,
-          types::BitWidth::Width128 => 128,
-        })
+        .custom_width_int_type(width.bits())
         .as_basic_type_enum(),
       types::PrimitiveType::Real(width) => match width {
-        types::BitWidth::Width8 => {
-          unreachable!("8-bit width floating-point numbers are purposely not supported by LLVM")
+        types::BitWidth::Width1 | types::BitWidth::Width8 => {
+          unreachable!(
+            "sub-16-bit width floating-point numbers are purposely not supported by LLVM"
+          )
         }
         types::BitWidth::Width16 => llvm_context.f16_type(),
         types::BitWidth::Width32 => llvm_context.f32_type(),
@@ -601,6 +610,202 @@ impl<'a, 'llvm> LoweringContext<'a, 'llvm> {
     self.make_llvm_unit_type().const_null()
   }
 
+  /// Lower the indexing of an array, yielding the indexed element.
+  ///
+  /// Since the index may not be constant, array values that are not
+  /// addressable are first stored on the stack, in order to be indexed.
+  pub(crate) fn lower_array_indexing(
+    &mut self,
+    pointer_indexing: &ast::PointerIndexing,
+    array_type: &types::Type,
+    element_type: &types::Type,
+  ) -> inkwell::values::BasicValueEnum<'llvm> {
+    let llvm_array_type = self.lower_type(array_type);
+
+    let llvm_array = self
+      .lower_with_access_mode(&pointer_indexing.pointer, AccessMode::None)
+      .expect(BUG_LLVM_VALUE);
+
+    let llvm_array_ptr = if llvm_array.is_pointer_value() {
+      llvm_array.into_pointer_value()
+    } else {
+      let llvm_stack_allocation = self.alloca(llvm_array_type, "array_indexing.alloca");
+
+      self
+        .llvm_builder
+        .build_store(llvm_stack_allocation, llvm_array)
+        .expect(lowering::BUG_BUILDER_UNSET);
+
+      llvm_stack_allocation
+    };
+
+    let llvm_index = self
+      .lower_with_access_mode(&pointer_indexing.index, AccessMode::Value)
+      .expect(BUG_LLVM_VALUE)
+      .into_int_value();
+
+    let llvm_zero = self.llvm_module.get_context().i64_type().const_zero();
+
+    let llvm_element_gep = unsafe {
+      self
+        .llvm_builder
+        .build_gep(
+          llvm_array_type,
+          llvm_array_ptr,
+          &[llvm_zero, llvm_index],
+          "array_indexing.gep",
+        )
+        .expect(lowering::BUG_BUILDER_UNSET)
+    };
+
+    let llvm_element_type = self.lower_type(element_type);
+
+    self.access_if_mode_applies(llvm_element_type, llvm_element_gep, "array_indexing.access")
+  }
+
+  /// Convert an argument passed to the variadic portion of a variadic callee
+  /// into the type that it is passed as.
+  ///
+  /// Following C's default argument promotions, arguments of narrow integer,
+  /// boolean, character or real types are promoted, as determined by their resolved types (see
+  /// `types::Type::promote_variadic`). Typed pointers decay into opaque
+  /// pointers. Otherwise, the argument is returned as-is.
+  pub(crate) fn build_variadic_promotion(
+    &mut self,
+    llvm_argument: inkwell::values::BasicValueEnum<'llvm>,
+    argument_type_id: &symbol_table::TypeId,
+  ) -> inkwell::values::BasicValueEnum<'llvm> {
+    let argument_type = self.resolve_type_by_id(argument_type_id).into_owned();
+
+    if let Some(promoted_type) = argument_type.promote_variadic() {
+      let llvm_promoted_type = self.lower_type(&promoted_type);
+
+      return match argument_type {
+        types::Type::Primitive(types::PrimitiveType::Integer(_, is_signed)) => {
+          let llvm_promoted_int_type = llvm_promoted_type.into_int_type();
+
+          if is_signed {
+            self.llvm_builder.build_int_s_extend(
+              llvm_argument.into_int_value(),
+              llvm_promoted_int_type,
+              "call.promote.int",
+            )
+          } else {
+            self.llvm_builder.build_int_z_extend(
+              llvm_argument.into_int_value(),
+              llvm_promoted_int_type,
+              "call.promote.int",
+            )
+          }
+          .expect(lowering::BUG_BUILDER_UNSET)
+          .as_basic_value_enum()
+        }
+        // Booleans and characters are unsigned in their lowered form, so they
+        // are zero-extended regardless of the promoted type's signedness.
+        types::Type::Primitive(types::PrimitiveType::Bool | types::PrimitiveType::Char) => self
+          .llvm_builder
+          .build_int_z_extend(
+            llvm_argument.into_int_value(),
+            llvm_promoted_type.into_int_type(),
+            "call.promote.int",
+          )
+          .expect(lowering::BUG_BUILDER_UNSET)
+          .as_basic_value_enum(),
+        types::Type::Primitive(types::PrimitiveType::Real(..)) => self
+          .llvm_builder
+          .build_float_ext(
+            llvm_argument.into_float_value(),
+            llvm_promoted_type.into_float_type(),
+            "call.promote.real",
+          )
+          .expect(lowering::BUG_BUILDER_UNSET)
+          .as_basic_value_enum(),
+        _ => unreachable!("only integer, boolean, character and real types should be promoted"),
+      };
+    }
+
+    match argument_type {
+      types::Type::Pointer(..) => self
+        .llvm_builder
+        .build_pointer_cast(
+          llvm_argument.into_pointer_value(),
+          self.lower_type(&types::Type::Opaque).into_pointer_type(),
+          "call.decay",
+        )
+        .expect(lowering::BUG_BUILDER_UNSET)
+        .as_basic_value_enum(),
+      _ => llvm_argument,
+    }
+  }
+
+  /// Convert a value into a type that its own type is coercible to (see
+  /// `types::Type::is_coercible_to`), such as a function body yielding a
+  /// subtype of its function's return type.
+  ///
+  /// Integers are extended based on the signedness of the source type, and
+  /// may be converted into reals. Otherwise, the value is returned as-is.
+  pub(crate) fn build_coercion(
+    &mut self,
+    llvm_value: inkwell::values::BasicValueEnum<'llvm>,
+    source_type: &types::Type,
+    target_type: &types::Type,
+  ) -> inkwell::values::BasicValueEnum<'llvm> {
+    if source_type == target_type || !types::Type::is_coercible_to(source_type, target_type) {
+      return llvm_value;
+    }
+
+    let llvm_target_type = self.lower_type(target_type);
+
+    match (source_type, target_type) {
+      (
+        types::Type::Primitive(types::PrimitiveType::Integer(_, is_signed)),
+        types::Type::Primitive(types::PrimitiveType::Integer(..)),
+      ) => if *is_signed {
+        self.llvm_builder.build_int_s_extend(
+          llvm_value.into_int_value(),
+          llvm_target_type.into_int_type(),
+          "coerce.int",
+        )
+      } else {
+        self.llvm_builder.build_int_z_extend(
+          llvm_value.into_int_value(),
+          llvm_target_type.into_int_type(),
+          "coerce.int",
+        )
+      }
+      .expect(lowering::BUG_BUILDER_UNSET)
+      .as_basic_value_enum(),
+      (
+        types::Type::Primitive(types::PrimitiveType::Integer(_, is_signed)),
+        types::Type::Primitive(types::PrimitiveType::Real(..)),
+      ) => if *is_signed {
+        self.llvm_builder.build_signed_int_to_float(
+          llvm_value.into_int_value(),
+          llvm_target_type.into_float_type(),
+          "coerce.int_to_real",
+        )
+      } else {
+        self.llvm_builder.build_unsigned_int_to_float(
+          llvm_value.into_int_value(),
+          llvm_target_type.into_float_type(),
+          "coerce.int_to_real",
+        )
+      }
+      .expect(lowering::BUG_BUILDER_UNSET)
+      .as_basic_value_enum(),
+      (types::Type::Primitive(types::PrimitiveType::Real(..)), _) => self
+        .llvm_builder
+        .build_float_ext(
+          llvm_value.into_float_value(),
+          llvm_target_type.into_float_type(),
+          "coerce.real",
+        )
+        .expect(lowering::BUG_BUILDER_UNSET)
+        .as_basic_value_enum(),
+      _ => unreachable!("only integer and real types should be coercible"),
+    }
+  }
+
   /// Lower the given signature type into its corresponding LLVM function type.
   ///
   /// This does not affect the current LLVM buffers.
@@ -1177,28 +1382,60 @@ impl<'a, 'llvm> LoweringContext<'a, 'llvm> {
         .follow_link(&capture.target_link_id)
         .expect(auxiliary::BUG_NAME_RESOLUTION);
 
-      let llvm_capture_target_opt = match capture_registry_item {
-        symbol_table::RegistryItem::Parameter(parameter) => {
-          self.visit_item(&ast::Item::Parameter(parameter.clone()))
-        }
+      // By-reference captures must yield the address of their target,
+      // instead of its value.
+      let capture_access_mode = match capture.mode {
+        ast::CaptureMode::ByValue => AccessMode::Value,
+        ast::CaptureMode::ByReference => AccessMode::None,
+      };
+
+      // Whether the lowered target is already the address of its value. Only
+      // reference bindings are stored on the stack by their lowering; all other
+      // targets (such as parameters) yield their value directly.
+      let (llvm_capture_target_opt, is_addressable) = match capture_registry_item {
+        symbol_table::RegistryItem::Parameter(parameter) => (
+          self.visit_item(&ast::Item::Parameter(parameter.clone())),
+          false,
+        ),
         symbol_table::RegistryItem::Binding(binding) => {
           let saved_access_mode = self.access_mode;
 
-          self.access_mode = AccessMode::Value;
+          self.access_mode = capture_access_mode;
 
           let llvm_binding = self.visit_item(&ast::Item::Binding(binding.clone()));
 
           self.access_mode = saved_access_mode;
 
-          llvm_binding
+          let is_reference = self.resolve_type_by_id(&binding.type_id).is_reference();
+
+          (llvm_binding, is_reference)
         }
         expr_registry_item => {
           let capture_expr = expr_registry_item
             .into_expr()
             .expect("capture target should be an expression by this point");
 
-          self.lower_with_access_mode(&capture_expr, AccessMode::Value)
+          (
+            self.lower_with_access_mode(&capture_expr, capture_access_mode),
+            false,
+          )
+        }
+      };
+
+      // Targets that are not addressable must first be stored on the stack,
+      // in order for a reference to them to be captured.
+      let llvm_capture_target_opt = match llvm_capture_target_opt {
+        Some(llvm_value) if capture.mode == ast::CaptureMode::ByReference && !is_addressable => {
+          let llvm_stack_allocation = self.alloca(llvm_value.get_type(), "closure.capture.alloca");
+
+          self
+            .llvm_builder
+            .build_store(llvm_stack_allocation, llvm_value)
+            .expect(lowering::BUG_BUILDER_UNSET);
+
+          Some(llvm_stack_allocation.as_basic_value_enum())
         }
+        llvm_capture_target_opt => llvm_capture_target_opt,
       };
 
       // If the capture target yielded a unit value, then
@@ -19,6 +19,7 @@ pub struct Parser {
   tokens: Vec<lexer::Token>,
   index: usize,
   id_generator: auxiliary::IdGenerator,
+  span_table: symbol_table::SpanTable,
 }
 
 // TODO: When parsing, utility methods and general parsing techniques should take into account whitespace tokens, and comments, and simply ignore them. But the important thing is that they are currently not considered by default. Instead, they should be explicitly ignored during parsing, to avoid having to filter out whitespace, comments, etc. after lexing. Or, perhaps require that the parser is only fed a filtered version of the lexer's output (separation of concerns)?
@@ -89,6 +90,7 @@ impl Parser {
       tokens,
       index: 0,
       id_generator: auxiliary::IdGenerator::default(),
+      span_table: symbol_table::SpanTable::new(),
     }
   }
 
@@ -136,6 +138,45 @@ impl Parser {
     self.id_generator.get_counter()
   }
 
+  pub fn get_span_table(&self) -> &symbol_table::SpanTable {
+    &self.span_table
+  }
+
+  /// Retrieve the source position of the current token.
+  ///
+  /// If the index is out of bounds, the position of the last token
+  /// is used instead.
+  fn get_position(&self) -> usize {
+    self
+      .tokens
+      .get(self.index)
+      .or(self.tokens.last())
+      .map(|token| token.1)
+      .unwrap_or(0)
+  }
+
+  /// Create a new type id, and associate it with the span of the node
+  /// currently being parsed, which begins at the given source position.
+  ///
+  /// This should be called once the node's tokens have been consumed, since
+  /// the span ends at the last consumed token.
+  fn next_type_id_spanning(&mut self, start: usize) -> symbol_table::TypeId {
+    let type_id = self.id_generator.next_type_id();
+
+    let end = self
+      .index
+      .checked_sub(1)
+      .and_then(|last_index| self.tokens.get(last_index))
+      .map(|last_token| last_token.1)
+      .unwrap_or(start);
+
+    self
+      .span_table
+      .insert(type_id, symbol_table::Span { start, end });
+
+    type_id
+  }
+
   /// Determine whether the current token is a valid unary operator.
   ///
   /// If there is no current token (`EOF` has been reached), `false`
@@ -407,6 +448,8 @@ impl Parser {
 
   /// %indent (%statement)+ %dedent
   fn parse_block(&mut self) -> diagnostic::Maybe<Block> {
+    let start = self.get_position();
+
     // CONSIDER: Instead of implicitly returning the last statement, have an optional keyword at the last statement be 'return' to indicate that a value was indeed returned. To avoid problems when there is a single statement, simply consider having a flag on whether the statement is returned or not, then consider this on type-sensitive operations during lowering or anywhere that the return value is used. There is a problem with this approach: all blocks would need to "return" their values, even those inside if-expressions! This would be too much. Perhaps special case function bodies? For example, a parameter could be passed to the "parse_block" parsing function (this), so that it knows when it's parsing a function block. This could be a good idea. Consider simply accepting a parameter here to indicate whether this block must use the return parameter to yield (ie. it is a function body).
 
     let mut statements = Vec::new();
@@ -449,7 +492,7 @@ impl Parser {
 
     Ok(Block {
       statements,
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       yield_value,
     })
   }
@@ -512,7 +555,7 @@ impl Parser {
           substitution_id: symbol_table::SubstitutionId(self.id_generator.next()),
         })
       }
-      lexer::TokenKind::Identifier(_) => types::Type::Stub(self.parse_stub_type()?),
+      lexer::TokenKind::Identifier(_) => types::Type::Stub(Box::new(self.parse_stub_type()?)),
       lexer::TokenKind::BraceL => types::Type::Object(self.parse_object_type()?),
       lexer::TokenKind::TypeUnit => self.parse_unit_type()?,
       lexer::TokenKind::TypeBool => types::Type::Primitive(self.parse_bool_type()?),
@@ -553,14 +596,17 @@ impl Parser {
 
         types::Type::Primitive(types::PrimitiveType::CString)
       }
+      lexer::TokenKind::Typeof => self.parse_typeof_type()?,
       _ => return Err(self.expected("type")),
     };
 
     Ok(match ty {
       // Promote to a signature type, if applicable.
       types::Type::Tuple(tuple_type) if self.is(&lexer::TokenKind::Arrow) => {
-        types::Type::Signature(self.parse_signature_type(tuple_type.0)?)
+        types::Type::Signature(Box::new(self.parse_signature_type(tuple_type.0)?))
       }
+      // The empty tuple type `()` is a synonym for the unit type.
+      types::Type::Tuple(tuple_type) if tuple_type.is_unit_singleton() => types::Type::Unit,
       ty => ty,
     })
   }
@@ -640,10 +686,135 @@ impl Parser {
     })
   }
 
-  /// %name (':' %type)?
+  /// '(' {%name (,)}* ')' | '{' {%name ':' %name (,)}* '}'
+  fn parse_pattern(&mut self) -> diagnostic::Maybe<ast::Pattern> {
+    if self.is(&lexer::TokenKind::BraceL) {
+      const TERMINATOR: lexer::TokenKind = lexer::TokenKind::BraceR;
+
+      self.skip()?;
+
+      let mut bindings = Vec::new();
+
+      while self.until_terminator(&TERMINATOR)? {
+        let field_name = self.parse_name()?;
+
+        self.skip_one(&lexer::TokenKind::Colon)?;
+
+        bindings.push((
+          field_name,
+          ast::PatternBinding {
+            name: self.parse_name()?,
+            type_id: self.id_generator.next_type_id(),
+          },
+        ));
+
+        self.skip_comma(&TERMINATOR)?;
+      }
+
+      return Ok(ast::Pattern::Object(bindings));
+    }
+
+    const TERMINATOR: lexer::TokenKind = lexer::TokenKind::ParenthesesR;
+
+    self.skip_one(&lexer::TokenKind::ParenthesesL)?;
+
+    let mut bindings = Vec::new();
+
+    while self.until_terminator(&TERMINATOR)? {
+      bindings.push(ast::PatternBinding {
+        name: self.parse_name()?,
+        type_id: self.id_generator.next_type_id(),
+      });
+
+      self.skip_comma(&TERMINATOR)?;
+    }
+
+    Ok(ast::Pattern::Tuple(bindings))
+  }
+
+  /// Desugar the destructuring patterns of the given parameters into
+  /// bindings, which are to be placed at the start of the body.
+  ///
+  /// Each sub-binding accesses its part of the parameter's value, and
+  /// shares its type id with its corresponding pattern binding.
+  fn destructure_parameters(
+    &mut self,
+    parameters: &[std::rc::Rc<ast::Parameter>],
+  ) -> diagnostic::Maybe<Vec<std::rc::Rc<ast::Statement>>> {
+    let mut statements = Vec::new();
+
+    for parameter in parameters {
+      // Tuple pattern bindings access their element by position, whereas
+      // object pattern bindings access their field by name.
+      let bindings = match &parameter.pattern {
+        Some(ast::Pattern::Tuple(bindings)) => bindings
+          .iter()
+          .map(|binding| (binding, None))
+          .collect::<Vec<_>>(),
+        Some(ast::Pattern::Object(bindings)) => bindings
+          .iter()
+          .map(|(field_name, binding)| (binding, Some(field_name)))
+          .collect(),
+        None => continue,
+      };
+
+      for (index, (binding, field_name)) in bindings.into_iter().enumerate() {
+        let parameter_reference = ast::Expr::Reference(std::rc::Rc::new(ast::Reference {
+          type_id: self.id_generator.next_type_id(),
+          path: ast::Path {
+            link_id: self.id_generator.next_link_id(),
+            qualifier: None,
+            base_name: parameter.name.clone(),
+            sub_name: None,
+            symbol_kind: symbol_table::SymbolKind::Declaration,
+          },
+        }));
+
+        let value = match field_name {
+          Some(field_name) => ast::Expr::ObjectAccess(std::rc::Rc::new(ast::ObjectAccess {
+            object: parameter_reference,
+            type_id: self.id_generator.next_type_id(),
+            base_expr_type_id: self.id_generator.next_type_id(),
+            field_name: field_name.to_owned(),
+          })),
+          None => ast::Expr::TupleIndexing(std::rc::Rc::new(ast::TupleIndex {
+            index: Self::get_llvm_size(index)?,
+            indexed_tuple: parameter_reference,
+            indexed_tuple_type_id: self.id_generator.next_type_id(),
+            type_id: self.id_generator.next_type_id(),
+          })),
+        };
+
+        statements.push(std::rc::Rc::new(ast::Statement::Binding(std::rc::Rc::new(
+          ast::Binding {
+            registry_id: self.id_generator.next_registry_id(),
+            type_id: binding.type_id,
+            name: binding.name.clone(),
+            value,
+            type_hint: None,
+            pattern: None,
+          },
+        ))));
+      }
+    }
+
+    Ok(statements)
+  }
+
+  /// %name ('@' %pattern)? (':' %type)?
   fn parse_parameter(&mut self, position: LlvmSize) -> diagnostic::Maybe<ast::Parameter> {
+    let start = self.get_position();
+
     let name = self.parse_name()?;
 
+    let pattern = if self.is(&lexer::TokenKind::At) {
+      self.skip()?;
+
+      Some(self.parse_pattern()?)
+    } else {
+      None
+    };
+
     let type_hint = if self.is(&lexer::TokenKind::Colon) {
       self.skip()?;
 
@@ -655,9 +826,10 @@ impl Parser {
     Ok(ast::Parameter {
       name,
       type_hint,
+      pattern,
       position,
       registry_id: self.id_generator.next_registry_id(),
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
@@ -726,6 +898,8 @@ impl Parser {
 
   /// func %name (%generics)? %signature %block
   fn parse_function(&mut self) -> diagnostic::Maybe<ast::Function> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::Func)?;
 
     let name = self.parse_name()?;
@@ -740,7 +914,16 @@ impl Parser {
 
     self.skip_one(&lexer::TokenKind::Colon)?;
 
-    let body = std::rc::Rc::new(self.parse_block()?);
+    let mut body = self.parse_block()?;
+
+    // Destructured parameters are bound before the rest of the body.
+    body.statements = self
+      .destructure_parameters(&signature.parameters)?
+      .into_iter()
+      .chain(body.statements)
+      .collect();
+
+    let body = std::rc::Rc::new(body);
 
     Ok(ast::Function {
       name,
@@ -748,12 +931,14 @@ impl Parser {
       body,
       registry_id: self.id_generator.next_registry_id(),
       generics,
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
   /// func %name %signature
   fn parse_foreign_function(&mut self) -> diagnostic::Maybe<ast::ForeignFunction> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::Func)?;
 
     let name = self.parse_name()?;
@@ -763,7 +948,7 @@ impl Parser {
       name,
       signature: std::rc::Rc::new(signature),
       registry_id: self.id_generator.next_registry_id(),
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
@@ -891,6 +1076,8 @@ impl Parser {
 
   /// let %name (':' %type) {'=', '=>', ':='} %expr
   fn parse_binding(&mut self) -> diagnostic::Maybe<ast::Binding> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::Let)?;
 
     let name = self.parse_name()?;
@@ -909,15 +1096,18 @@ impl Parser {
 
     Ok(ast::Binding {
       registry_id: self.id_generator.next_registry_id(),
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       name,
       value,
       type_hint,
+      pattern: None,
     })
   }
 
   /// if %expr ':' %expr (elif %expr ':' %expr)* (else ':' %expr)? end
   fn parse_if(&mut self) -> diagnostic::Maybe<ast::If> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::If)?;
 
     let condition = self.parse_expr()?;
@@ -949,7 +1139,7 @@ impl Parser {
     };
 
     Ok(ast::If {
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       condition,
       then_branch,
       elif_branches,
@@ -1050,10 +1240,28 @@ impl Parser {
     Ok(ast::LiteralKind::Nullptr(type_hint_opt))
   }
 
+  /// typeof '(' %expr ')'
+  fn parse_typeof_type(&mut self) -> diagnostic::Maybe<types::Type> {
+    let start = self.get_position();
+
+    self.skip_many(&[lexer::TokenKind::Typeof, lexer::TokenKind::ParenthesesL])?;
+
+    let expr = self.parse_expr()?;
+
+    self.skip_one(&lexer::TokenKind::ParenthesesR)?;
+
+    Ok(types::Type::Typeof(Box::new(ast::Typeof {
+      type_id: self.next_type_id_spanning(start),
+      expr,
+    })))
+  }
+
   // TODO: Still need to implement other utilities for working with memory, such as alignof.
 
   /// sizeof '::' '<' %type '>'
   fn parse_sizeof(&mut self) -> diagnostic::Maybe<ast::Sizeof> {
+    let start = self.get_position();
+
     self.skip_many(&[
       lexer::TokenKind::Sizeof,
       lexer::TokenKind::ColonDouble,
@@ -1065,12 +1273,14 @@ impl Parser {
     self.skip_one(&lexer::TokenKind::GreaterThan)?;
 
     Ok(ast::Sizeof {
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       ty,
     })
   }
 
   fn parse_literal(&mut self) -> diagnostic::Maybe<ast::Literal> {
+    let start = self.get_position();
+
     let kind = match self.get_token()? {
       lexer::TokenKind::Bool(_) => self.parse_bool_literal()?,
       lexer::TokenKind::Number(..) => self.parse_number_literal()?,
@@ -1081,7 +1291,7 @@ impl Parser {
     };
 
     Ok(ast::Literal {
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       kind,
     })
   }
@@ -1402,11 +1612,13 @@ impl Parser {
 
   /// %operator %expr
   fn parse_unary_op(&mut self) -> diagnostic::Maybe<ast::UnaryOp> {
+    let start = self.get_position();
+
     let operator = self.parse_unary_operator()?;
     let operand = self.parse_expr()?;
 
     Ok(ast::UnaryOp {
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       operand_type_id: self.id_generator.next_type_id(),
       operator,
       operand,
@@ -1483,11 +1695,13 @@ impl Parser {
 
   /// %path
   fn parse_reference(&mut self) -> diagnostic::Maybe<ast::Reference> {
+    let start = self.get_position();
+
     // REVIEW: Would there be an instance where this method can accept which symbol kind to parse?
     let path = self.parse_path(symbol_table::SymbolKind::Declaration)?;
 
     Ok(ast::Reference {
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
       path,
     })
   }
@@ -1576,6 +1790,8 @@ impl Parser {
 
   /// '{' (%name ':' %expr ',')+ '}'
   fn parse_object(&mut self) -> diagnostic::Maybe<ast::Object> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::BraceL)?;
 
     let mut fields = std::collections::HashMap::new();
@@ -1628,11 +1844,13 @@ impl Parser {
 
     Ok(ast::Object {
       fields,
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
   fn parse_closure(&mut self) -> diagnostic::Maybe<ast::Closure> {
+    let start = self.get_position();
+
     let mut captures = Vec::new();
     let registry_id = self.id_generator.next_registry_id();
     let mut parameters = Vec::new();
@@ -1651,8 +1869,18 @@ impl Parser {
 
         self.skip()?;
 
+        // Captures are by value, unless prefixed with an ampersand.
+        let mode = if self.is(&lexer::TokenKind::Ampersand) {
+          self.skip()?;
+
+          ast::CaptureMode::ByReference
+        } else {
+          ast::CaptureMode::ByValue
+        };
+
         captures.push(ast::ClosureCapture {
           name: self.parse_name()?,
+          mode,
           closure_registry_id: registry_id,
           target_link_id: self.id_generator.next_link_id(),
           type_id: self.id_generator.next_type_id(),
@@ -1684,13 +1912,26 @@ impl Parser {
     };
 
     let body = self.parse_expr()?;
+    let destructuring_statements = self.destructure_parameters(&signature.parameters)?;
+
+    // Destructured parameters are bound before the closure's body, which
+    // requires the body to be wrapped in a block.
+    let body = if destructuring_statements.is_empty() {
+      body
+    } else {
+      ast::Expr::Block(std::rc::Rc::new(ast::Block {
+        type_id: self.id_generator.next_type_id(),
+        statements: destructuring_statements,
+        yield_value: body,
+      }))
+    };
 
     Ok(ast::Closure {
       captures,
       signature: std::rc::Rc::new(signature),
       body,
       registry_id,
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
@@ -1764,8 +2005,10 @@ impl Parser {
     Ok(generic_hints)
   }
 
-  /// match %expr ':' %indent (%expr '=>' %expr)* '_' '=>' %expr %dedent
+  /// match %expr ':' %indent (%expr '=>' %expr)* ('_' '=>' %expr)? %dedent
   fn parse_match(&mut self) -> diagnostic::Maybe<ast::Match> {
+    let start = self.get_position();
+
     self.skip_one(&lexer::TokenKind::Match)?;
 
     let subject = self.parse_expr()?;
@@ -1773,35 +2016,38 @@ impl Parser {
     self.skip_many(&[lexer::TokenKind::Colon, lexer::TokenKind::Indent])?;
 
     let mut cases = Vec::new();
-    let default_case;
-
-    loop {
-      let is_default_case = self.is(&lexer::TokenKind::Wildcard);
 
-      let expr = if !is_default_case {
-        self.parse_expr()?
-      } else {
+    let default_case = loop {
+      if self.is(&lexer::TokenKind::Wildcard) {
         self.skip()?;
         self.skip_one(&lexer::TokenKind::FatArrow)?;
-        default_case = self.parse_expr()?;
+
+        let default_case = self.parse_expr()?;
+
         self.skip_one(&lexer::TokenKind::Dedent)?;
 
-        break;
-      };
+        break Some(default_case);
+      } else if !cases.is_empty() && self.is(&lexer::TokenKind::Dedent) {
+        self.skip()?;
+
+        break None;
+      }
+
+      let expr = self.parse_expr()?;
 
       self.skip_one(&lexer::TokenKind::FatArrow)?;
 
       let body = self.parse_expr()?;
 
       cases.push(ast::MatchArm { case: expr, body });
-    }
+    };
 
     Ok(ast::Match {
       subject,
       arms: cases,
       default_case,
       subject_type_id: self.id_generator.next_type_id(),
-      type_id: self.id_generator.next_type_id(),
+      type_id: self.next_type_id_spanning(start),
     })
   }
 
@@ -1955,6 +2201,47 @@ mod tests {
     assert!(parser.is(&lexer::TokenKind::Func));
   }
 
+  #[test]
+  fn parse_parameter_pattern() {
+    let mut parser = create_parser(&[
+      lexer::TokenKind::Identifier(String::from("pair")),
+      lexer::TokenKind::At,
+      lexer::TokenKind::ParenthesesL,
+      lexer::TokenKind::Identifier(String::from("a")),
+      lexer::TokenKind::Comma,
+      lexer::TokenKind::Identifier(String::from("b")),
+      lexer::TokenKind::ParenthesesR,
+      lexer::TokenKind::Colon,
+      lexer::TokenKind::ParenthesesL,
+      lexer::TokenKind::TypeInt32,
+      lexer::TokenKind::Comma,
+      lexer::TokenKind::TypeInt32,
+      lexer::TokenKind::ParenthesesR,
+      lexer::TokenKind::ParenthesesR,
+    ]);
+
+    let parameter = parser.parse_parameter(0).unwrap();
+
+    assert_eq!("pair", parameter.name);
+    assert!(matches!(parameter.type_hint, Some(types::Type::Tuple(..))));
+
+    assert!(matches!(
+      parameter.pattern.as_ref(),
+      Some(ast::Pattern::Tuple(bindings)) if bindings.len() == 2 && bindings[1].name == "b"
+    ));
+
+    // The pattern's sub-bindings are bound by name, when destructured.
+    let statements = parser
+      .destructure_parameters(&[std::rc::Rc::new(parameter)])
+      .unwrap();
+
+    assert!(matches!(
+      statements.last().map(|statement| statement.as_ref()),
+      Some(ast::Statement::Binding(binding)) if binding.name == "b"
+        && matches!(&binding.value, ast::Expr::TupleIndexing(tuple_index) if tuple_index.index == 1)
+    ));
+  }
+
   #[test]
   fn skip() {
     let mut parser = create_parser(&[lexer::TokenKind::Func, lexer::TokenKind::Func]);
@@ -2144,6 +2431,23 @@ mod tests {
   //   assert!(parser.is_index_out_of_bounds());
   // }
 
+  #[test]
+  fn binding_span() {
+    let mut parser = create_parser(&[
+      lexer::TokenKind::Let,
+      lexer::TokenKind::Identifier(String::from("a")),
+      lexer::TokenKind::Equal,
+      lexer::TokenKind::Bool(true),
+    ]);
+
+    let binding = parser.parse_binding().unwrap();
+
+    assert_eq!(
+      Some(&symbol_table::Span { start: 0, end: 3 }),
+      parser.get_span_table().get(&binding.type_id)
+    );
+  }
+
   #[test]
   fn check_llvm_size() {
     const OK_SIZE: usize = 0;
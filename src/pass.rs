@@ -96,10 +96,16 @@ impl Pass for SemanticCheckPass {
   ) -> PassResult {
     let symbol_table = require_dependency!(&context.symbol_table);
     let type_env = require_dependency!(&context.type_env);
+    let substitution_env = require_dependency!(&context.substitution_env);
     let universes = require_dependency!(&context.universes);
-    let resolution_helper = resolution::ResolutionHelper::new(universes, symbol_table, type_env);
+    let mut resolution_helper =
+      resolution::ResolutionHelper::new(universes, symbol_table, type_env);
     let reverse_universe_tracker = require_dependency!(&context.reverse_universe_tracker);
 
+    resolution_helper
+      .base
+      .set_substitution_env(substitution_env);
+
     let mut semantic_check_ctx =
       semantics::SemanticCheckContext::new(&symbol_table, &resolution_helper);
 
@@ -133,8 +139,15 @@ impl Pass for LoweringPass {
   ) -> PassResult {
     let symbol_table = require_dependency!(&context.symbol_table);
     let type_env = require_dependency!(&context.type_env);
+    let substitution_env = require_dependency!(&context.substitution_env);
     let universes = require_dependency!(&context.universes);
-    let resolution_helper = resolution::ResolutionHelper::new(universes, symbol_table, type_env);
+    let mut resolution_helper =
+      resolution::ResolutionHelper::new(universes, symbol_table, type_env);
+
+    resolution_helper
+      .base
+      .set_substitution_env(substitution_env);
+
     let llvm_context = inkwell::context::Context::create();
     let llvm_module = llvm_context.create_module(&module.qualifier.to_string());
 
@@ -360,10 +373,68 @@ impl Pass for LinkPass {
   }
 }
 
-#[derive(Default)]
-pub struct TypeInferencePass;
+pub struct TypeInferencePass {
+  row_polymorphism: unification::RowPolymorphism,
+  type_variable_limit: Option<usize>,
+  step_limit: usize,
+  division_semantics: inference::DivisionSemantics,
+  is_subtyping_enabled: bool,
+  is_constraint_deduplication_enabled: bool,
+}
+
+impl Default for TypeInferencePass {
+  fn default() -> Self {
+    Self {
+      row_polymorphism: unification::RowPolymorphism::default(),
+      type_variable_limit: None,
+      step_limit: unification::DEFAULT_STEP_LIMIT,
+      division_semantics: inference::DivisionSemantics::default(),
+      is_subtyping_enabled: false,
+      is_constraint_deduplication_enabled: false,
+    }
+  }
+}
 
 impl TypeInferencePass {
+  /// Determine whether open object types may remain open once all type
+  /// constraints have been solved.
+  pub fn set_row_polymorphism(&mut self, row_polymorphism: unification::RowPolymorphism) {
+    self.row_polymorphism = row_polymorphism;
+  }
+
+  /// Limit the amount of type variable occurrences across all type
+  /// constraints, beyond which solving them is refused.
+  pub fn set_type_variable_limit(&mut self, type_variable_limit: Option<usize>) {
+    self.type_variable_limit = type_variable_limit;
+  }
+
+  /// Limit the amount of unification steps that solving the type
+  /// constraints may take, beyond which solving them is abandoned.
+  pub fn set_step_limit(&mut self, step_limit: usize) {
+    self.step_limit = step_limit;
+  }
+
+  /// Determine whether division operations yield a real number regardless
+  /// of their operands' types.
+  pub fn set_division_semantics(&mut self, division_semantics: inference::DivisionSemantics) {
+    self.division_semantics = division_semantics;
+  }
+
+  /// Allow function bodies to yield a subtype of their declared return type
+  /// (ie. a narrower integer), which is then widened into the return type.
+  pub fn set_subtyping_enabled(&mut self, is_subtyping_enabled: bool) {
+    self.is_subtyping_enabled = is_subtyping_enabled;
+  }
+
+  /// Discard type constraints which are structurally identical to others
+  /// before solving them.
+  pub fn set_constraint_deduplication_enabled(
+    &mut self,
+    is_constraint_deduplication_enabled: bool,
+  ) {
+    self.is_constraint_deduplication_enabled = is_constraint_deduplication_enabled;
+  }
+
   fn create_reverse_universe_tracker(
     symbol_table: &symbol_table::SymbolTable,
   ) -> instantiation::ReverseUniverseTracker {
@@ -385,7 +456,10 @@ impl TypeInferencePass {
           continue;
         }
 
-        callee.get_registry_id()
+        match callee.get_registry_id() {
+          Some(registry_id) => registry_id,
+          None => continue,
+        }
       } else if let instantiation::Artifact::StubType(stub_type) = &artifact {
         let target = symbol_table
           .follow_link(&stub_type.path.link_id)
@@ -448,6 +522,19 @@ impl Pass for TypeInferencePass {
     let mut inference_context =
       inference::InferenceContext::new(symbol_table, None, context.id_count);
 
+    inference_context.set_division_semantics(self.division_semantics);
+    inference_context.set_subtyping_enabled(self.is_subtyping_enabled);
+    inference_context
+      .set_constraint_deduplication_enabled(self.is_constraint_deduplication_enabled);
+
+    // Unions are registered upfront, so that references to them (including
+    // mutually recursive ones) resolve regardless of declaration order.
+    for global_item in &module.global_items {
+      if let ast::Item::Union(union) = global_item {
+        inference_context.register_union_type(union);
+      }
+    }
+
     for global_item in &module.global_items {
       let is_polymorphic = global_item
         .find_generics()
@@ -457,13 +544,19 @@ impl Pass for TypeInferencePass {
       // Do not infer types for polymorphic items which aren't
       // invoked by artifacts.
       if !is_polymorphic {
+        inference_context.enter_item(global_item.find_registry_id().copied());
         inference_context.visit(global_item);
       }
     }
 
+    let inference_results = inference_context.into_overall_result();
     let instantiation_helper = instantiation::InstantiationHelper::new(symbol_table);
     let (universes, instantiation_diagnostics) = instantiation_helper.instantiate_all_artifacts();
-    let diagnostics_helper = diagnostic::DiagnosticsHelper::from(instantiation_diagnostics);
+    let mut diagnostics_helper = diagnostic::DiagnosticsHelper::from(instantiation_diagnostics);
+
+    diagnostics_helper.add_many(inference_results.diagnostics);
+
+    let mut diagnostics_helper = diagnostics_helper.dedup();
 
     if diagnostics_helper.contains_errors() {
       return diagnostics_helper.into_pass_result();
@@ -474,24 +567,53 @@ impl Pass for TypeInferencePass {
       "each artifact should have a corresponding universe"
     );
 
-    let inference_results = inference_context.into_overall_result();
-
     let mut type_unification_context = unification::TypeUnificationContext::new(
       symbol_table,
       inference_results.type_var_substitutions,
       &universes,
     );
 
+    type_unification_context.register_literal_origins(inference_results.literal_origins);
+    type_unification_context.set_partial_type_env(&inference_results.type_env);
+    type_unification_context.set_row_polymorphism(self.row_polymorphism);
+    type_unification_context.set_type_variable_limit(self.type_variable_limit);
+    type_unification_context.set_step_limit(self.step_limit);
+
     let type_env = require_maybe_many!(type_unification_context
-      .solve_constraints(&inference_results.type_env, &inference_results.constraints));
+      .solve_constraints(&inference_results.type_env, &inference_results.constraints)
+      .map_err(
+        |diagnostics| diagnostic::DiagnosticsHelper::from(diagnostics)
+          .dedup()
+          .diagnostics
+      ));
+
+    // Specialize the solved type environment for each instantiation, so that
+    // generic hints which cannot be applied (ie. recursive type aliases) are
+    // reported here, instead of failing once lowering resolves them.
+    for universe_id in universes.keys() {
+      if let Err(diagnostics) = instantiation::InstantiationHelper::apply_universe_to_type_env(
+        symbol_table,
+        &universes,
+        &type_env,
+        universe_id,
+      ) {
+        diagnostics_helper.add_many(diagnostics);
+      }
+    }
+
+    if diagnostics_helper.contains_errors() {
+      return diagnostics_helper.dedup().into_pass_result();
+    }
 
+    let substitution_env = type_unification_context.get_substitutions().to_owned();
     let reverse_universe_tracker = Self::create_reverse_universe_tracker(&symbol_table);
 
-    assert!(!diagnostics_helper.contains_errors());
     context.type_env = Some(type_env);
+    context.substitution_env = Some(substitution_env);
     context.id_count = inference_results.next_id_count;
     context.universes = Some(universes);
     context.reverse_universe_tracker = Some(reverse_universe_tracker);
+    context.dependency_graph = Some(inference_results.dependency_graph);
 
     PassResult::Ok(diagnostics_helper.diagnostics)
   }
@@ -501,8 +623,14 @@ pub struct ExecutionContext<'a> {
   symbol_table: Option<symbol_table::SymbolTable>,
   call_graph: Option<auxiliary::CallGraph>,
   type_env: Option<symbol_table::TypeEnvironment>,
+  /// The substitutions produced by unification, which determine the final
+  /// version of open object types during resolution.
+  substitution_env: Option<symbol_table::SubstitutionEnv>,
   universes: Option<instantiation::TypeSchemes>,
   reverse_universe_tracker: Option<instantiation::ReverseUniverseTracker>,
+  /// Which items' types depend on which other items' types, as recorded
+  /// during type inference.
+  dependency_graph: Option<inference::DependencyGraph>,
   id_count: usize,
   // FIXME: Should be removed and prefer only using a single module.
   main_package: &'a ast::Package,
@@ -551,6 +679,9 @@ impl Ord for PassEntry {
 pub struct RunResult {
   pub diagnostics: Vec<diagnostic::Diagnostic>,
   pub results: PassResultsMap,
+  /// The inter-item type dependency graph, if type inference ran
+  /// successfully.
+  pub dependency_graph: Option<inference::DependencyGraph>,
 }
 
 pub type PassResultsMap = std::collections::HashMap<PassId, PassResult>;
@@ -614,8 +745,10 @@ impl<'a> PassManager<'a> {
       symbol_table: None,
       call_graph: None,
       type_env: None,
+      substitution_env: None,
       universes: None,
       reverse_universe_tracker: None,
+      dependency_graph: None,
       id_count: initial_id_count,
       main_package: self.main_package,
       declarations: declare::DeclarationMap::new(),
@@ -656,6 +789,7 @@ impl<'a> PassManager<'a> {
     RunResult {
       diagnostics: run_diagnostics,
       results,
+      dependency_graph: context.dependency_graph,
     }
   }
 }
@@ -1,5 +1,5 @@
 use crate::{
-  instantiation, symbol_table,
+  ast, instantiation, symbol_table,
   types::{self, TypeStripError},
 };
 
@@ -7,18 +7,76 @@ pub(crate) type UniverseStack = Vec<symbol_table::UniverseId>;
 
 #[derive(Debug)]
 pub(crate) enum TypeResolutionError {
-  StubTypeMissingSymbolTableEntry,
+  /// The stub type with the given path does not point to any entry in the
+  /// symbol table.
+  StubTypeMissingSymbolTableEntry(ast::Path),
+  /// The stub type with the given path eventually points back to itself
+  /// through its chain of monomorphic stub layers.
+  RecursiveStubType(ast::Path),
+  SymbolTableMissingEntry,
   EmptyUniverseStackWhenResolvingGeneric,
   CouldNotFindSubstitutionInAnyUniverseInUniverseStack,
   MissingUniverse,
   NoUniversesWhenResolvingGeneric,
+  UnionVariantNotFound,
+  /// A `typeof` type was encountered, but no type environment was provided
+  /// to determine the type of its expression from.
+  TypeofWithoutTypeEnv,
+  /// The expression of a `typeof` type has no entry in the type environment.
+  TypeofMissingTypeEnvEntry(symbol_table::TypeId),
+}
+
+impl std::fmt::Display for TypeResolutionError {
+  fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    formatter.write_str(match self {
+      TypeResolutionError::StubTypeMissingSymbolTableEntry(path) => {
+        return formatter.write_fmt(format_args!(
+          "could not resolve type `{}`, as it does not point to any type definition in the symbol table",
+          path
+        ));
+      }
+      TypeResolutionError::RecursiveStubType(path) => {
+        return formatter.write_fmt(format_args!(
+          "could not resolve type `{}`, as it is defined in terms of itself",
+          path
+        ));
+      }
+      TypeResolutionError::SymbolTableMissingEntry => {
+        "type refers to an entity that is missing from the symbol table"
+      }
+      TypeResolutionError::EmptyUniverseStackWhenResolvingGeneric => {
+        "generic type parameter encountered outside of any instantiation context"
+      }
+      TypeResolutionError::CouldNotFindSubstitutionInAnyUniverseInUniverseStack => {
+        "generic type parameter has no substitution in any of the active instantiation contexts"
+      }
+      TypeResolutionError::MissingUniverse => {
+        "instantiation context (universe) referenced by the universe stack does not exist"
+      }
+      TypeResolutionError::NoUniversesWhenResolvingGeneric => {
+        "generic type parameter encountered, but no instantiations have been registered"
+      }
+      TypeResolutionError::UnionVariantNotFound => {
+        "union does not contain a variant with the given name"
+      }
+      TypeResolutionError::TypeofWithoutTypeEnv => {
+        "`typeof` type encountered, but its expression's type is not available"
+      }
+      TypeResolutionError::TypeofMissingTypeEnvEntry(type_id) => {
+        return formatter.write_fmt(format_args!(
+          "could not resolve `typeof` type, as its expression has no type (type id {})",
+          type_id.0
+        ));
+      }
+    })
+  }
 }
 
 impl From<types::DirectRecursionCheckError> for TypeResolutionError {
   fn from(error: types::DirectRecursionCheckError) -> Self {
     match error {
       types::DirectRecursionCheckError::SymbolTableMissingEntry => {
-        TypeResolutionError::StubTypeMissingSymbolTableEntry
+        TypeResolutionError::SymbolTableMissingEntry
       }
     }
   }
@@ -36,10 +94,22 @@ impl From<types::DirectRecursionCheckError> for TypeStripError {
 
 #[derive(Debug)]
 pub(crate) enum TypeResolutionByIdError {
-  MissingEntryForTypeId,
+  MissingEntryForTypeId(symbol_table::TypeId),
   TypeResolutionError(TypeResolutionError),
 }
 
+impl std::fmt::Display for TypeResolutionByIdError {
+  fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TypeResolutionByIdError::MissingEntryForTypeId(type_id) => formatter.write_fmt(format_args!(
+        "type id `{}` has no corresponding entry in the type environment",
+        type_id.0
+      )),
+      TypeResolutionByIdError::TypeResolutionError(error) => error.fmt(formatter),
+    }
+  }
+}
+
 pub(crate) fn push_to_universe_stack(
   mut universe_stack: UniverseStack,
   new_universe_id: symbol_table::UniverseId,
@@ -64,7 +134,9 @@ impl<'a> ResolutionHelper<'a> {
     symbol_table: &'a symbol_table::SymbolTable,
     type_env: &'a symbol_table::TypeEnvironment,
   ) -> Self {
-    let base = BaseResolutionHelper::new(universes, symbol_table);
+    let mut base = BaseResolutionHelper::new(universes, symbol_table);
+
+    base.set_type_env(type_env);
 
     Self { base, type_env }
   }
@@ -77,20 +149,43 @@ impl<'a> ResolutionHelper<'a> {
     let ty = self
       .type_env
       .get(type_id)
-      .ok_or(TypeResolutionByIdError::MissingEntryForTypeId)?;
+      .ok_or_else(|| TypeResolutionByIdError::MissingEntryForTypeId(type_id.to_owned()))?;
 
     self
       .base
-      .resolve(ty, universe_stack)
+      .resolve_memoized(ty, universe_stack)
       .map_err(|type_resolution_error| {
         TypeResolutionByIdError::TypeResolutionError(type_resolution_error)
       })
   }
 }
 
+/// A memoization cache of resolved types, keyed by the address of the
+/// type that was resolved along with the universe stack it was resolved
+/// under.
+type ResolutionCache = std::collections::HashMap<(usize, UniverseStack), types::Type>;
+
+/// A cache of the chains of types that monomorphic stub types resolve
+/// through, keyed by the link id of the stub type's path.
+///
+/// Entries are ordered from least to most recently used, so that the least
+/// recently used chain is evicted once the cache reaches its capacity.
+type StubChainCache = indexmap::IndexMap<symbol_table::LinkId, Vec<types::Type>>;
+
+/// The maximum amount of chains kept in the stub chain cache.
+const STUB_CHAIN_CACHE_CAPACITY: usize = 256;
+
 pub(crate) struct BaseResolutionHelper<'a> {
   universes: &'a instantiation::TypeSchemes,
   symbol_table: &'a symbol_table::SymbolTable,
+  resolution_cache: std::cell::RefCell<ResolutionCache>,
+  stub_chain_cache: std::cell::RefCell<StubChainCache>,
+  /// The substitutions produced by unification, if any, which are used to
+  /// determine the final version of open object types.
+  substitution_env: Option<&'a symbol_table::SubstitutionEnv>,
+  /// The type environment, if any, which is used to resolve `typeof` types
+  /// to the type of their expression.
+  type_env: Option<&'a symbol_table::TypeEnvironment>,
 }
 
 impl<'a> BaseResolutionHelper<'a> {
@@ -101,39 +196,91 @@ impl<'a> BaseResolutionHelper<'a> {
     Self {
       universes,
       symbol_table,
+      resolution_cache: std::cell::RefCell::new(ResolutionCache::new()),
+      stub_chain_cache: std::cell::RefCell::new(StubChainCache::new()),
+      substitution_env: None,
+      type_env: None,
     }
   }
 
+  /// Provide the substitutions produced by unification, so that open object
+  /// types are resolved to their final field set and closed status.
+  ///
+  /// Any previously memoized resolutions are discarded, since they may
+  /// have been resolved without the substitutions.
+  pub(crate) fn set_substitution_env(
+    &mut self,
+    substitution_env: &'a symbol_table::SubstitutionEnv,
+  ) {
+    self.substitution_env = Some(substitution_env);
+    self.resolution_cache.borrow_mut().clear();
+  }
+
+  /// Provide the type environment, so that `typeof` types are resolved to
+  /// the type of their expression.
+  pub(crate) fn set_type_env(&mut self, type_env: &'a symbol_table::TypeEnvironment) {
+    self.type_env = Some(type_env);
+    self.resolution_cache.borrow_mut().clear();
+  }
+
   pub(crate) fn get_universes(&self) -> &instantiation::TypeSchemes {
     self.universes
   }
 
-  fn get_in_universe_stack(
+  /// Find the named variant in the given union, and determine the type of
+  /// its payload, without resolving it.
+  ///
+  /// String variants carry a string payload, and singleton variants carry
+  /// their integer discriminant.
+  pub(crate) fn find_union_variant_payload_type(
+    union: &ast::Union,
+    variant_name: &str,
+  ) -> Result<types::Type, TypeResolutionError> {
+    let variant = union
+      .variants
+      .get(variant_name)
+      .ok_or(TypeResolutionError::UnionVariantNotFound)?;
+
+    Ok(match &variant.kind {
+      ast::UnionVariantKind::Type(ty) => ty.to_owned(),
+      ast::UnionVariantKind::String(..) => types::Type::Primitive(types::PrimitiveType::CString),
+      ast::UnionVariantKind::Singleton { .. } => types::Type::Primitive(
+        types::PrimitiveType::Integer(types::BitWidth::Width64, false),
+      ),
+    })
+  }
+
+  /// Find the named variant in the given union, and resolve the type of its
+  /// payload through the universe stack.
+  pub(crate) fn resolve_union_variant_type(
     &self,
-    key: &symbol_table::SubstitutionId,
-    universe_stack: &UniverseStack,
-  ) -> Result<Option<&types::Type>, &'static str> {
-    // REVIEW: More research and review is needed to ensure that this approach works for all cases. Perhaps implement more tests, but also need to intuitively understand how this works 100%.
-    // FIXME: Regarding the case where an infinite loop would occur, what happens then if the search is still done in reverse, but finds no entry at the beginning (goes until the end), then it would reach an infinite loop causing stack-overflow? If that's the case, we'd need an assertion and a minor tweak to prevent such a case. Of course, we'd only use an assertion if such case is considered a logic bug.
-    // NOTE: The iteration order here is crucial; by iterating in reverse,
-    // closer universes are prioritized. This is important because in the case
-    // that an artifact uses a polymorphic item multiple times, at the same time,
-    // (ie. `Id<Id<T>>`), the innermost artifact should be granted priority,
-    // otherwise, it may lead to a situation where the first artifact keeps
-    // on getting selected as the resolution universe, and may lead to an
-    // infinite loop (which may cause a stack overflow).
-    for universe_id in universe_stack.iter().rev() {
-      let universe = self
-        .universes
-        .get(universe_id)
-        .ok_or("universe id missing from type schemes")?;
-
-      if let Some(value) = universe.get(key) {
-        return Ok(Some(value));
-      }
-    }
+    union: &ast::Union,
+    variant_name: &str,
+    universe_stack: UniverseStack,
+  ) -> Result<types::Type, TypeResolutionError> {
+    let payload_type = Self::find_union_variant_payload_type(union, variant_name)?;
+
+    Ok(self.resolve(&payload_type, universe_stack)?.into_owned())
+  }
+
+  /// Determine whether the type, or any type on its immediate subtree, is an
+  /// open object type whose row was bound during unification.
+  fn has_bound_row(&self, ty: &types::Type) -> bool {
+    let Some(substitution_env) = self.substitution_env else {
+      return false;
+    };
 
-    Ok(None)
+    std::iter::once(ty)
+      .chain(ty.get_immediate_subtree_iter())
+      .any(|ty| {
+        matches!(
+          ty,
+          types::Type::Object(types::ObjectType {
+            kind: types::ObjectKind::Open(substitution_id),
+            ..
+          }) if substitution_env.contains_key(substitution_id)
+        )
+      })
   }
 
   /// Recursively instantiate stub type if applicable, then substitute it with
@@ -145,21 +292,35 @@ impl<'a> BaseResolutionHelper<'a> {
     ty: &'b types::Type,
     universe_stack: UniverseStack,
   ) -> Result<std::borrow::Cow<'b, types::Type>, TypeResolutionError> {
-    // Nothing to do if the type is already fully concrete.
-    if ty.is_immediate_subtree_concrete() {
+    // Nothing to do if the type is already fully concrete. Types with stubs
+    // anywhere in their immediate subtree can never be concrete, so they are
+    // ruled out before the more expensive concreteness check.
+    if !ty.has_stub_type() && ty.is_immediate_subtree_concrete() && !self.has_bound_row(ty) {
       return Ok(std::borrow::Cow::Borrowed(ty));
     }
 
-    let resolution = match ty {
-      types::Type::Stub(stub_type) => self.resolve_stub_type(stub_type, universe_stack)?,
-      types::Type::Generic(generic_type) => {
-        self.resolve_generic(&generic_type.substitution_id, universe_stack)?
-      }
-      // The type is not a stub, generic (at least at this layer), or a fully concrete type.
-      // In other words, the type contains a nested stub, or generic at some level on its
-      // subtree.
-      _ => self.resolve_within_subtree(ty, universe_stack)?,
-    };
+    let resolution =
+      match ty {
+        types::Type::Stub(stub_type) => self.resolve_stub_type(stub_type, universe_stack)?,
+        types::Type::Generic(generic_type) => {
+          self.resolve_generic(&generic_type.substitution_id, universe_stack)?
+        }
+        types::Type::Typeof(typeof_) => {
+          let type_env = self
+            .type_env
+            .ok_or(TypeResolutionError::TypeofWithoutTypeEnv)?;
+
+          let expr_type = type_env.get(&typeof_.type_id).ok_or(
+            TypeResolutionError::TypeofMissingTypeEnvEntry(typeof_.type_id),
+          )?;
+
+          self.resolve(expr_type, universe_stack)?
+        }
+        // The type is not a stub, generic (at least at this layer), or a fully concrete type.
+        // In other words, the type contains a nested stub, or generic at some level on its
+        // subtree.
+        _ => self.resolve_within_subtree(ty, universe_stack)?,
+      };
 
     assert!(
       resolution.is_immediate_subtree_concrete(),
@@ -169,6 +330,130 @@ impl<'a> BaseResolutionHelper<'a> {
     Ok(resolution)
   }
 
+  /// Same as `resolve`, but repeated resolutions of the same type under the
+  /// same universe stack are short-circuited by a memoization cache.
+  ///
+  /// ## Safety
+  ///
+  /// The cache is keyed by the type's address, which is only sound because
+  /// the type is required to outlive the helper (through the `'a` lifetime).
+  /// Otherwise, the type could be freed, and a different type could later be
+  /// allocated at the same address, which would then be mistaken for the
+  /// freed type. Temporary types (ie. those created during resolution) must
+  /// never be memoized for this reason.
+  pub(crate) fn resolve_memoized(
+    &self,
+    ty: &'a types::Type,
+    universe_stack: UniverseStack,
+  ) -> Result<std::borrow::Cow<'a, types::Type>, TypeResolutionError> {
+    // Nothing to do if the type is already fully concrete. Types with stubs
+    // anywhere in their immediate subtree can never be concrete, so they are
+    // ruled out before the more expensive concreteness check.
+    if !ty.has_stub_type() && ty.is_immediate_subtree_concrete() && !self.has_bound_row(ty) {
+      return Ok(std::borrow::Cow::Borrowed(ty));
+    }
+
+    let key = (ty as *const types::Type as usize, universe_stack);
+
+    if let Some(resolution) = self.resolution_cache.borrow().get(&key) {
+      return Ok(std::borrow::Cow::Owned(resolution.to_owned()));
+    }
+
+    let resolution = self.resolve(ty, key.1.clone())?.into_owned();
+
+    self
+      .resolution_cache
+      .borrow_mut()
+      .insert(key, resolution.clone());
+
+    Ok(std::borrow::Cow::Owned(resolution))
+  }
+
+  /// Compute the chain of types that the given stub type resolves through,
+  /// by following all of its monomorphic stub layers.
+  ///
+  /// The chain does not include the stub type itself, and its last element
+  /// is the root type that the stub type ultimately points to. Polymorphic
+  /// stub layers require instantiation, and thus end the chain (a polymorphic
+  /// stub type yields an empty chain).
+  ///
+  /// Since a monomorphic stub type's chain only depends on its link id, the
+  /// chain is cached, and subsequent calls for the same link id will not
+  /// traverse the symbol table again, unless the chain was evicted from the
+  /// cache in the meantime.
+  pub(crate) fn precompute_stub_chain(
+    &self,
+    stub_type: &types::StubType,
+  ) -> Result<Vec<types::Type>, TypeResolutionError> {
+    if !stub_type.generic_hints.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    {
+      let mut stub_chain_cache = self.stub_chain_cache.borrow_mut();
+
+      if let Some(index) = stub_chain_cache.get_index_of(&stub_type.path.link_id) {
+        let most_recent_index = stub_chain_cache.len() - 1;
+
+        stub_chain_cache.move_index(index, most_recent_index);
+
+        return Ok(stub_chain_cache[most_recent_index].to_owned());
+      }
+    }
+
+    let mut chain = Vec::new();
+    let mut current = stub_type.to_owned();
+    let mut seen_link_ids = std::collections::HashSet::new();
+
+    while current.generic_hints.is_empty() {
+      if !seen_link_ids.insert(current.path.link_id) {
+        return Err(TypeResolutionError::RecursiveStubType(
+          stub_type.path.to_owned(),
+        ));
+      }
+
+      let target_registry_item = self
+        .symbol_table
+        .follow_link(&current.path.link_id)
+        .ok_or_else(|| {
+          TypeResolutionError::StubTypeMissingSymbolTableEntry(current.path.to_owned())
+        })?;
+
+      let next = match target_registry_item {
+        symbol_table::RegistryItem::GenericType(generic_type) => {
+          types::Type::Generic(generic_type.to_owned())
+        }
+        // Polymorphic type defs. require instantiation, which is outside
+        // the scope of the chain.
+        symbol_table::RegistryItem::TypeDef(type_def)
+          if !type_def.generics.parameters.is_empty() =>
+        {
+          break;
+        }
+        symbol_table::RegistryItem::TypeDef(type_def) => type_def.body.to_owned(),
+        symbol_table::RegistryItem::Union(union) => types::Type::Union(std::rc::Rc::clone(union)),
+        _ => unreachable!("stub type should only point to a generic, type def., or union target"),
+      };
+
+      chain.push(next.clone());
+
+      match next {
+        types::Type::Stub(next_stub_type) => current = *next_stub_type,
+        _ => break,
+      }
+    }
+
+    let mut stub_chain_cache = self.stub_chain_cache.borrow_mut();
+
+    if stub_chain_cache.len() >= STUB_CHAIN_CACHE_CAPACITY {
+      stub_chain_cache.shift_remove_index(0);
+    }
+
+    stub_chain_cache.insert(stub_type.path.link_id, chain.clone());
+
+    Ok(chain)
+  }
+
   fn resolve_within_subtree<'b>(
     &self,
     ty: &types::Type,
@@ -181,9 +466,12 @@ impl<'a> BaseResolutionHelper<'a> {
       types::Type::Reference(pointee) => types::Type::Reference(Box::new(
         self.resolve(pointee, universe_stack)?.into_owned(),
       )),
+      types::Type::Array { element, length } => types::Type::Array {
+        element: Box::new(self.resolve(element, universe_stack)?.into_owned()),
+        length: *length,
+      },
       types::Type::Tuple(tuple) => types::Type::Tuple(types::TupleType(
         tuple
-          .0
           .iter()
           // FIXME: Properly handle result.
           // OPTIMIZE: Avoid cloning.
@@ -194,6 +482,27 @@ impl<'a> BaseResolutionHelper<'a> {
           .collect(),
       )),
       types::Type::Object(object_type) => {
+        // If the object's row was bound during unification, its substitution
+        // reflects its final field set and closed status.
+        let object_type = match (object_type.kind, self.substitution_env) {
+          (types::ObjectKind::Open(substitution_id), Some(substitution_env)) => {
+            match substitution_env.get(&substitution_id) {
+              // NOTE: Rows bound to a different object type (ie. a closed
+              // object, or another row) must be resolved in turn.
+              Some(substitution @ types::Type::Object(row))
+                if row.kind != types::ObjectKind::Open(substitution_id) =>
+              {
+                return Ok(std::borrow::Cow::Owned(
+                  self.resolve(substitution, universe_stack)?.into_owned(),
+                ));
+              }
+              Some(types::Type::Object(row)) => row,
+              _ => object_type,
+            }
+          }
+          _ => object_type,
+        };
+
         let fields = object_type.fields.iter().try_fold(
           std::collections::BTreeMap::new(),
           |mut accumulator, field| -> Result<_, TypeResolutionError> {
@@ -227,14 +536,23 @@ impl<'a> BaseResolutionHelper<'a> {
           .map(|cow| cow.into_owned())
           .collect();
 
-        types::Type::Signature(types::SignatureType {
+        types::Type::Signature(Box::new(types::SignatureType {
           arity_mode: signature.arity_mode,
           parameter_types,
           return_type: Box::new(return_type),
-        })
+        }))
       }
-      _ => unreachable!(
-        "type should have been a type constructor by this point, with a nested generic or stub type"
+      // These types have no resolvable children, so they are returned as-is.
+      types::Type::Range(..)
+      | types::Type::Opaque
+      | types::Type::Primitive(..)
+      | types::Type::Unit
+      | types::Type::Union(..) => ty.to_owned(),
+      types::Type::Stub(..)
+      | types::Type::Generic(..)
+      | types::Type::Variable(..)
+      | types::Type::Typeof(..) => unreachable!(
+        "meta types should have been handled before resolving within the type's subtree"
       ),
     }))
   }
@@ -244,23 +562,16 @@ impl<'a> BaseResolutionHelper<'a> {
     substitution_id: &'b symbol_table::SubstitutionId,
     universe_stack: UniverseStack,
   ) -> Result<std::borrow::Cow<'b, types::Type>, TypeResolutionError> {
-    // Generics cannot be resolved without any context artifact id and
-    // thus no corresponding substitution environment.
-    if universe_stack.is_empty() {
-      return Err(TypeResolutionError::EmptyUniverseStackWhenResolvingGeneric);
-    } else if self.universes.is_empty() {
-      return Err(TypeResolutionError::NoUniversesWhenResolvingGeneric);
-    }
-
     // BUG: When generics are nested, say inside pointer types, the inner generic will be called without any context artifact id, because it's not part of a polymorphic function. Instead, what should happen is that this function be called with the stub's artifact id, similar to as it is done on the case above.
 
     // NOTE: The substituted type might be a type stub, or a generic, which
     // is acceptable. The only job of this logic is to substitute one layer
     // of the type.
-    let substitution = self
-      .get_in_universe_stack(substitution_id, &universe_stack)
-      .map_err(|_| TypeResolutionError::MissingUniverse)?
-      .ok_or_else(|| TypeResolutionError::CouldNotFindSubstitutionInAnyUniverseInUniverseStack)?;
+    let substitution = instantiation::InstantiationHelper::find_substitution(
+      self.universes,
+      substitution_id,
+      &universe_stack,
+    )?;
 
     // TODO: Perform `!occurs_in` check to prevent stack overflow / infinite loop with the substitution and the original type. Use the original type and the substitution as the operands for the `occurs_in` assertion. Since this function is `Result`, return `Err` instead of having an assertion (is it acceptable for a type to resolve to itself?).
     // FIXME: This won't work for this case; recursion cannot be simply detected via stub type stripping, as instantiation is involved at each recursive step! Instead, some sort of state must be passed around resolution steps to detect recursion.
@@ -292,11 +603,11 @@ impl<'a> BaseResolutionHelper<'a> {
         "non-artifact stub types should have no entry on the universes"
       );
 
-      let stripped_target = stub_type
+      let stripped_target = self
+        .precompute_stub_chain(stub_type)?
+        .pop()
         // OPTIMIZE: Avoid cloning.
-        .clone()
-        .strip_all_monomorphic_stub_layers(self.symbol_table)
-        .or(Err(TypeResolutionError::StubTypeMissingSymbolTableEntry))?;
+        .unwrap_or_else(|| types::Type::Stub(Box::new(stub_type.to_owned())));
 
       let resolved_target = self.resolve(&stripped_target, universe_stack)?;
 
@@ -307,12 +618,15 @@ impl<'a> BaseResolutionHelper<'a> {
     let target_registry_item = self
       .symbol_table
       .follow_link(&stub_type.path.link_id)
-      .ok_or(TypeResolutionError::StubTypeMissingSymbolTableEntry)?;
+      .ok_or_else(|| {
+        TypeResolutionError::StubTypeMissingSymbolTableEntry(stub_type.path.to_owned())
+      })?;
 
-    assert!(
-      self.universes.contains_key(&stub_type.universe_id),
-      "a universe is not registered for the polymorphic stub type artifact"
-    );
+    // Universes are only registered once instantiation has taken place,
+    // thus polymorphic stub types cannot be resolved beforehand.
+    if !self.universes.contains_key(&stub_type.universe_id) {
+      return Err(TypeResolutionError::MissingUniverse);
+    }
 
     // By this point, it is known that the stub type is polymorphic, and
     // thus that it has generic hints. Thus, it is appropriate to add its
@@ -349,8 +663,9 @@ impl<'a> BaseResolutionHelper<'a> {
 
         self.resolve(&type_def.body, next_universe_stack)?
       }
-      // TODO: Handle unions.
-      symbol_table::RegistryItem::Union(..) => todo!("unions are not yet handled"),
+      symbol_table::RegistryItem::Union(union) => {
+        std::borrow::Cow::Owned(types::Type::Union(std::rc::Rc::clone(union)))
+      }
       _ => unreachable!("stub type should only point to a generic, type def., or union target"),
     };
 
@@ -363,3 +678,360 @@ impl<'a> BaseResolutionHelper<'a> {
     Ok(resolution)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolve_range_and_opaque() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let range_type_id = symbol_table::TypeId(0);
+    let opaque_type_id = symbol_table::TypeId(1);
+
+    let type_env = symbol_table::TypeEnvironment::from([
+      (range_type_id, types::Type::Range(1, 10)),
+      (opaque_type_id, types::Type::Opaque),
+    ]);
+
+    let resolution_helper = ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    assert!(matches!(
+      resolution_helper
+        .resolve_by_id(&range_type_id, UniverseStack::new())
+        .map(|ty| ty.into_owned()),
+      Ok(types::Type::Range(1, 10))
+    ));
+
+    assert!(matches!(
+      resolution_helper
+        .resolve_by_id(&opaque_type_id, UniverseStack::new())
+        .map(|ty| ty.into_owned()),
+      Ok(types::Type::Opaque)
+    ));
+
+    // Bypass the concreteness short-circuit.
+    assert!(matches!(
+      resolution_helper
+        .base
+        .resolve_within_subtree(&types::Type::Range(1, 10), UniverseStack::new())
+        .map(|ty| ty.into_owned()),
+      Ok(types::Type::Range(1, 10))
+    ));
+
+    assert!(matches!(
+      resolution_helper
+        .base
+        .resolve_within_subtree(&types::Type::Opaque, UniverseStack::new())
+        .map(|ty| ty.into_owned()),
+      Ok(types::Type::Opaque)
+    ));
+  }
+
+  #[test]
+  fn resolve_union_variant_type() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let resolution_helper = BaseResolutionHelper::new(&universes, &symbol_table);
+
+    let variant = |name: &str, kind| {
+      (
+        name.to_string(),
+        std::rc::Rc::new(ast::UnionVariant {
+          registry_id: symbol_table::RegistryId(1),
+          union_id: symbol_table::RegistryId(0),
+          name: name.to_string(),
+          kind,
+        }),
+      )
+    };
+
+    let union = ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("test"),
+      variants: std::collections::BTreeMap::from([
+        variant("a", ast::UnionVariantKind::Type(types::Type::Range(1, 10))),
+        variant("b", ast::UnionVariantKind::String(String::from("b"))),
+      ]),
+    };
+
+    assert!(matches!(
+      resolution_helper.resolve_union_variant_type(&union, "a", UniverseStack::new()),
+      Ok(types::Type::Range(1, 10))
+    ));
+
+    assert!(matches!(
+      resolution_helper.resolve_union_variant_type(&union, "b", UniverseStack::new()),
+      Ok(types::Type::Primitive(types::PrimitiveType::CString))
+    ));
+
+    assert!(matches!(
+      resolution_helper.resolve_union_variant_type(&union, "c", UniverseStack::new()),
+      Err(TypeResolutionError::UnionVariantNotFound)
+    ));
+  }
+
+  #[test]
+  fn resolution_error_context() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let type_env = symbol_table::TypeEnvironment::new();
+    let resolution_helper = ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    let stub_type = types::Type::Stub(Box::new(types::StubType {
+      universe_id: symbol_table::UniverseId(0, String::from("test")),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(0),
+        qualifier: None,
+        base_name: String::from("foo"),
+        sub_name: Some(String::from("Bar")),
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints: Vec::new(),
+    }));
+
+    let error = resolution_helper
+      .base
+      .resolve(&stub_type, UniverseStack::new())
+      .unwrap_err();
+
+    assert!(matches!(
+      &error,
+      TypeResolutionError::StubTypeMissingSymbolTableEntry(path) if path.base_name == "foo"
+    ));
+
+    assert!(error.to_string().contains("`foo::Bar`"));
+
+    let error = resolution_helper
+      .resolve_by_id(&symbol_table::TypeId(7), UniverseStack::new())
+      .unwrap_err();
+
+    assert!(matches!(
+      error,
+      TypeResolutionByIdError::MissingEntryForTypeId(symbol_table::TypeId(7))
+    ));
+  }
+
+  #[test]
+  fn resolve_memoized() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universe_id = symbol_table::UniverseId(0, String::from("test"));
+    let substitution_id = symbol_table::SubstitutionId(0);
+
+    let universes = instantiation::TypeSchemes::from([(
+      universe_id.clone(),
+      symbol_table::SubstitutionEnv::from([(substitution_id, types::Type::Unit)]),
+    )]);
+
+    // A subtree shared by multiple parents, which contains a generic type,
+    // and thus requires resolution.
+    let shared_type = types::Type::Tuple(types::TupleType(vec![
+      types::Type::Generic(types::GenericType {
+        name: String::from("T"),
+        registry_id: symbol_table::RegistryId(0),
+        substitution_id,
+      }),
+      types::Type::Opaque,
+    ]));
+
+    let type_env = symbol_table::TypeEnvironment::from([(symbol_table::TypeId(0), shared_type)]);
+    let resolution_helper = ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    for _ in 0..3 {
+      assert!(matches!(
+        resolution_helper
+          .resolve_by_id(&symbol_table::TypeId(0), vec![universe_id.clone()])
+          .map(|ty| ty.into_owned()),
+        Ok(types::Type::Tuple(types::TupleType(element_types)))
+          if matches!(element_types.first(), Some(types::Type::Unit))
+      ));
+    }
+
+    // All repeated resolutions were served by a single cache entry.
+    assert_eq!(1, resolution_helper.base.resolution_cache.borrow().len());
+  }
+
+  #[test]
+  fn precompute_stub_chain() {
+    let mut symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mock_stub_type = |id: usize, base_name: &str| types::StubType {
+      universe_id: symbol_table::UniverseId(id, base_name.to_string()),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(id),
+        qualifier: None,
+        base_name: base_name.to_string(),
+        sub_name: None,
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints: Vec::new(),
+    };
+
+    let union = std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(3),
+      name: String::from("U"),
+      variants: std::collections::BTreeMap::new(),
+    });
+
+    symbol_table.registry.insert(
+      union.registry_id,
+      symbol_table::RegistryItem::Union(std::rc::Rc::clone(&union)),
+    );
+
+    symbol_table
+      .links
+      .insert(symbol_table::LinkId(3), union.registry_id);
+
+    // type A = B
+    // type B = unit
+    // type C = U
+    for (id, name, body) in [
+      (0, "A", types::Type::Stub(Box::new(mock_stub_type(1, "B")))),
+      (1, "B", types::Type::Unit),
+      (2, "C", types::Type::Stub(Box::new(mock_stub_type(3, "U")))),
+    ] {
+      let registry_id = symbol_table::RegistryId(id);
+
+      symbol_table.registry.insert(
+        registry_id,
+        symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+          registry_id,
+          name: name.to_string(),
+          body,
+          generics: ast::Generics {
+            parameters: Vec::new(),
+          },
+        })),
+      );
+
+      symbol_table
+        .links
+        .insert(symbol_table::LinkId(id), registry_id);
+    }
+
+    let resolution_helper = BaseResolutionHelper::new(&universes, &symbol_table);
+
+    for _ in 0..2 {
+      assert!(matches!(
+        resolution_helper
+          .precompute_stub_chain(&mock_stub_type(0, "A"))
+          .as_deref(),
+        Ok([types::Type::Stub(..), types::Type::Unit])
+      ));
+    }
+
+    assert_eq!(1, resolution_helper.stub_chain_cache.borrow().len());
+
+    assert!(matches!(
+      resolution_helper
+        .precompute_stub_chain(&mock_stub_type(2, "C"))
+        .as_deref(),
+      Ok([types::Type::Stub(..), types::Type::Union(chain_union)])
+        if std::rc::Rc::ptr_eq(chain_union, &union)
+    ));
+  }
+
+  #[test]
+  fn precompute_stub_chain_evicts_least_recently_used() {
+    let mut symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mock_stub_type = |id: usize| types::StubType {
+      universe_id: symbol_table::UniverseId(id, format!("T{}", id)),
+      path: ast::Path {
+        link_id: symbol_table::LinkId(id),
+        qualifier: None,
+        base_name: format!("T{}", id),
+        sub_name: None,
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints: Vec::new(),
+    };
+
+    // type T<id> = unit
+    for id in 0..=STUB_CHAIN_CACHE_CAPACITY {
+      let registry_id = symbol_table::RegistryId(id);
+
+      symbol_table.registry.insert(
+        registry_id,
+        symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+          registry_id,
+          name: format!("T{}", id),
+          body: types::Type::Unit,
+          generics: ast::Generics {
+            parameters: Vec::new(),
+          },
+        })),
+      );
+
+      symbol_table
+        .links
+        .insert(symbol_table::LinkId(id), registry_id);
+    }
+
+    let resolution_helper = BaseResolutionHelper::new(&universes, &symbol_table);
+
+    for id in 0..STUB_CHAIN_CACHE_CAPACITY {
+      assert!(resolution_helper
+        .precompute_stub_chain(&mock_stub_type(id))
+        .is_ok());
+    }
+
+    // Using the first chain again makes the second chain the least recently
+    // used one, which is then evicted to make room for a new chain.
+    assert!(resolution_helper
+      .precompute_stub_chain(&mock_stub_type(0))
+      .is_ok());
+
+    assert!(resolution_helper
+      .precompute_stub_chain(&mock_stub_type(STUB_CHAIN_CACHE_CAPACITY))
+      .is_ok());
+
+    let stub_chain_cache = resolution_helper.stub_chain_cache.borrow();
+
+    assert_eq!(STUB_CHAIN_CACHE_CAPACITY, stub_chain_cache.len());
+    assert!(stub_chain_cache.contains_key(&symbol_table::LinkId(0)));
+    assert!(!stub_chain_cache.contains_key(&symbol_table::LinkId(1)));
+  }
+
+  #[test]
+  fn resolve_object_closed_during_unification() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let substitution_id = symbol_table::SubstitutionId(0);
+
+    let open_object = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(String::from("a"), types::Type::Unit)]),
+      kind: types::ObjectKind::Open(substitution_id),
+    });
+
+    // The open object's row was unified with a closed object with an
+    // additional field.
+    let substitution_env = symbol_table::SubstitutionEnv::from([(
+      substitution_id,
+      types::Type::Object(types::ObjectType {
+        fields: types::ObjectFieldMap::from([
+          (String::from("a"), types::Type::Unit),
+          (String::from("b"), types::Type::Opaque),
+        ]),
+        kind: types::ObjectKind::Closed,
+      }),
+    )]);
+
+    let mut resolution_helper = BaseResolutionHelper::new(&universes, &symbol_table);
+
+    resolution_helper.set_substitution_env(&substitution_env);
+
+    assert!(matches!(
+      resolution_helper
+        .resolve(&open_object, UniverseStack::new())
+        .map(|ty| ty.into_owned()),
+      Ok(types::Type::Object(types::ObjectType {
+        fields,
+        kind: types::ObjectKind::Closed,
+      })) if fields.len() == 2
+    ));
+  }
+}
@@ -1,6 +1,6 @@
 use crate::{
-  assert_extract, ast, auxiliary, diagnostic, instantiation, lowering, resolution, symbol_table,
-  types, visit,
+  ast, auxiliary, diagnostic, inference, instantiation, lowering, resolution, symbol_table, types,
+  visit,
 };
 
 pub struct SemanticCheckContext<'a> {
@@ -70,6 +70,94 @@ impl<'a> SemanticCheckContext<'a> {
     }
   }
 
+  /// Report the arguments passed to the variadic portion of a variadic
+  /// callee that will be converted when lowered.
+  ///
+  /// Narrow integers, booleans, characters and reals are promoted, following
+  /// C's default argument promotions (see `types::Type::promote_variadic`),
+  /// and typed pointers decay into opaque pointers, as expected by C (ie. for
+  /// `printf`'s `%p`).
+  pub(crate) fn check_variadic_arguments(
+    argument_types: &[types::Type],
+  ) -> Vec<diagnostic::Diagnostic> {
+    argument_types
+      .iter()
+      .filter_map(|argument_type| {
+        if let Some(promoted_type) = argument_type.promote_variadic() {
+          Some(diagnostic::Diagnostic::VariadicArgumentPromoted {
+            from: argument_type.to_owned(),
+            to: promoted_type,
+          })
+        } else if let types::Type::Pointer(..) = argument_type {
+          Some(diagnostic::Diagnostic::VariadicPointerDecayed(
+            argument_type.to_owned(),
+          ))
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  /// Verify that a chain of field accesses (ie. `a.b.c`) on the given root
+  /// type leads to an existing field.
+  ///
+  /// Only the last field of the chain is verified, since each access of
+  /// the chain is verified on its own. Chains through types other than
+  /// objects (such as unions) are not verified.
+  pub(crate) fn check_field_access_chain(
+    root_type: &types::Type,
+    field_path: &[String],
+  ) -> Option<diagnostic::Diagnostic> {
+    let (field_name, base_path) = field_path.split_last()?;
+
+    match root_type.resolve_field_path(base_path)? {
+      types::Type::Object(object_type) if object_type.field_type(field_name).is_none() => Some(
+        diagnostic::Diagnostic::ObjectFieldDoesNotExist(field_name.to_owned()),
+      ),
+      _ => None,
+    }
+  }
+
+  /// Report the array types within the given type whose length exceeds the
+  /// maximum length supported by LLVM, which represents array lengths as
+  /// 32-bit integers.
+  pub(crate) fn check_array_lengths(ty: &types::Type) -> Vec<diagnostic::Diagnostic> {
+    std::iter::once(ty)
+      .chain(ty.get_immediate_subtree_iter())
+      .filter_map(|ty| match ty {
+        types::Type::Array { length, .. } if u32::try_from(*length).is_err() => {
+          Some(diagnostic::Diagnostic::ArrayTooLarge(*length))
+        }
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// Report the variants of a union type which do not have integer
+  /// discriminants, which are required when branching on its values.
+  ///
+  /// String variants are represented by their string value rather than by
+  /// an integer, thus they cannot be used as discriminants. Types other than
+  /// unions are ignored.
+  pub(crate) fn check_union_discriminants(union_type: &types::Type) -> Vec<diagnostic::Diagnostic> {
+    let Ok(union) = union_type.try_as_union() else {
+      return Vec::new();
+    };
+
+    union
+      .variants
+      .values()
+      .filter(|variant| matches!(variant.kind, ast::UnionVariantKind::String(..)))
+      .map(
+        |variant| diagnostic::Diagnostic::NonIntegerUnionDiscriminant {
+          union_name: union.name.to_owned(),
+          variant_name: variant.name.to_owned(),
+        },
+      )
+      .collect()
+  }
+
   /// Determines if the given node requires an unsafe context to be executed.
   ///
   /// This function checks if a specific node, such as a unary operation with
@@ -198,8 +286,19 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
       .resolve_by_id(&function.type_id, self.universe_stack.clone())
       .expect(auxiliary::BUG_MISSING_TYPE);
 
+    self
+      .diagnostics
+      .extend(Self::check_array_lengths(&signature_type));
+
+    // NOTE: The return type is not part of the signature type's subtree.
+    if let types::Type::Signature(signature_type) = signature_type.as_ref() {
+      self
+        .diagnostics
+        .extend(Self::check_array_lengths(&signature_type.return_type));
+    }
+
     if function.name == lowering::ENTRY_POINT_NAME {
-      let main_function_signature = types::Type::Signature(types::SignatureType {
+      let main_function_signature = types::Type::Signature(Box::new(types::SignatureType {
         parameter_types: vec![
           types::Type::Primitive(types::PrimitiveType::Integer(
             types::BitWidth::Width32,
@@ -212,7 +311,7 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
           false,
         ))),
         arity_mode: types::ArityMode::Fixed,
-      });
+      }));
 
       if !instantiation::InstantiationHelper::compare_by_unification(
         // OPTIMIZE: Avoid cloning. This should be optimized on the `compare_by_unification` function, not here (as it is enforced by the function).
@@ -238,6 +337,17 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
   }
 
   fn visit_statement(&mut self, statement: &ast::Statement) {
+    if let ast::Statement::Binding(binding) = statement {
+      let binding_type = self
+        .resolution_helper
+        .resolve_by_id(&binding.type_id, self.universe_stack.clone())
+        .expect(auxiliary::BUG_MISSING_TYPE);
+
+      self
+        .diagnostics
+        .extend(Self::check_array_lengths(&binding_type));
+    }
+
     if let ast::Statement::InlineExpr(inner_expr) = statement {
       let type_id = inner_expr.find_type_id();
 
@@ -254,7 +364,7 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
         })
         .unwrap_or(types::Type::Unit);
 
-      if !ty.is_a_unit() {
+      if !ty.is_unit() {
         self
           .diagnostics
           .push(diagnostic::Diagnostic::UnusedValueMustBeUsedOrDiscarded);
@@ -291,7 +401,7 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
     let callee = call_site.strip_callee(self.symbol_table).unwrap();
 
     // REVIEW: Shouldn't this be handled implicitly by the type unification algorithm?
-    if let ast::Callable::Function(function) = callee {
+    if let ast::Callable::Function(function) = &callee {
       if !function.is_polymorphic() && !call_site.generic_hints.is_empty() {
         self
           .diagnostics
@@ -311,6 +421,31 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
           ));
       }
     }
+
+    let callee_type = self
+      .resolution_helper
+      .resolve_by_id(&call_site.callee_type_id, self.universe_stack.clone())
+      .expect(auxiliary::BUG_MISSING_TYPE);
+
+    // NOTE: The types of the arguments are generally unknown during
+    // inference, thus their conversions can only be determined here. The
+    // callee's type at a call site is built from the types of its arguments,
+    // thus its parameter types are those of the arguments.
+    if let ast::Callable::ForeignFunction(foreign_function) = &callee {
+      if foreign_function.signature.is_variadic {
+        let argument_types = callee_type
+          .as_parameter_types()
+          .expect("the callee's type at a call site should be a signature type");
+
+        let variadic_argument_types = argument_types
+          .get(foreign_function.signature.parameters.len()..)
+          .unwrap_or_default();
+
+        self
+          .diagnostics
+          .extend(Self::check_variadic_arguments(variadic_argument_types));
+      }
+    }
   }
 
   fn visit_closure(&mut self, closure: &ast::Closure) {
@@ -318,6 +453,64 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
     self.current_function_id = Some(closure.registry_id);
   }
 
+  fn visit_object_access(&mut self, object_access: &ast::ObjectAccess) {
+    let mut field_path = vec![object_access.field_name.to_owned()];
+    let mut root = object_access.object.flatten();
+
+    while let ast::Expr::ObjectAccess(inner_object_access) = root {
+      field_path.push(inner_object_access.field_name.to_owned());
+      root = inner_object_access.object.flatten();
+    }
+
+    field_path.reverse();
+
+    let Some(root_type_id) = root.find_type_id() else {
+      return;
+    };
+
+    let root_type = self
+      .resolution_helper
+      .resolve_by_id(root_type_id, self.universe_stack.clone())
+      .expect(auxiliary::BUG_MISSING_TYPE);
+
+    self
+      .diagnostics
+      .extend(Self::check_field_access_chain(&root_type, &field_path));
+  }
+
+  fn visit_pointer_indexing(&mut self, pointer_indexing: &ast::PointerIndexing) {
+    let indexed_type = self
+      .resolution_helper
+      .resolve_by_id(&pointer_indexing.type_id, self.universe_stack.clone())
+      .expect(auxiliary::BUG_MISSING_TYPE);
+
+    // NOTE: The indexed value's type may only be a type variable during
+    // inference, thus constant indices can only be checked against the
+    // array's length here, once types are resolved.
+    let types::Type::Array { length, .. } = indexed_type.as_ref() else {
+      return;
+    };
+
+    if let ast::Expr::Literal(ast::Literal {
+      kind: ast::LiteralKind::Number {
+        value,
+        is_real: false,
+        ..
+      },
+      ..
+    }) = pointer_indexing.index.flatten()
+    {
+      if *value >= *length as f64 {
+        self
+          .diagnostics
+          .push(diagnostic::Diagnostic::ArrayIndexOutOfBounds {
+            index: *value as u64,
+            array_length: *length,
+          });
+      }
+    }
+  }
+
   fn visit_cast(&mut self, cast: &ast::Cast) {
     let operand_type = self
       .resolution_helper
@@ -342,17 +535,38 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
         .push(diagnostic::Diagnostic::InvalidCastType);
     }
 
+    // Reinterpreting data as code is never sound in general, thus it is
+    // reported separately from other pointer casts.
+    let is_data_to_function_pointer_cast =
+      matches!(operand_type.as_ref(), types::Type::Pointer(..))
+        && !operand_type.is_function_pointer()
+        && cast_type.is_function_pointer();
+
     // Cast between pointer types must occur within an unsafe
     // scope.
-    if matches!(operand_type.as_ref(), types::Type::Pointer(..))
-      || matches!(cast_type.as_ref(), types::Type::Pointer(..)) && !self.in_unsafe_scope
-    {
+    let is_pointer_cast = matches!(operand_type.as_ref(), types::Type::Pointer(..))
+      || matches!(cast_type.as_ref(), types::Type::Pointer(..));
+
+    if is_data_to_function_pointer_cast && !self.in_unsafe_scope {
+      self
+        .diagnostics
+        .push(diagnostic::Diagnostic::FunctionPointerCastOutsideUnsafe);
+    } else if is_pointer_cast && !self.in_unsafe_scope {
       self
         .diagnostics
         .push(diagnostic::Diagnostic::CannotUseOutsideUnsafe);
     }
 
-    // TODO: Check if the cast and operand types are the same, thus making the operation redundant. Type equality comparison must be done through unification.
+    // NOTE: The operand's type may only be a type variable during inference,
+    // thus redundancy can only be determined here, once types are resolved.
+    if instantiation::InstantiationHelper::compare_by_unification(
+      // OPTIMIZE: Avoid cloning.
+      operand_type.into_owned(),
+      cast_type.into_owned(),
+      self.symbol_table,
+    ) {
+      self.diagnostics.push(diagnostic::Diagnostic::RedundantCast);
+    }
   }
 
   fn visit_match(&mut self, match_: &ast::Match) {
@@ -361,26 +575,325 @@ impl<'a> visit::Visitor for SemanticCheckContext<'a> {
         .diagnostics
         .push(diagnostic::Diagnostic::ConditionOrValueIsConstant);
     }
+
+    let subject_type = self
+      .resolution_helper
+      .resolve_by_id(&match_.subject_type_id, self.universe_stack.clone())
+      .expect(auxiliary::BUG_MISSING_TYPE);
+
+    self
+      .diagnostics
+      .extend(Self::check_union_discriminants(&subject_type));
+
+    let arm_cases = match_.arms.iter().map(|arm| &arm.case).collect::<Vec<_>>();
+
+    if let Err(diagnostics) = inference::InferenceContext::check_pattern_exhaustiveness(
+      &subject_type,
+      &arm_cases,
+      match_.default_case.as_ref(),
+    ) {
+      self.diagnostics.extend(diagnostics);
+    }
   }
 
-  fn visit_tuple_indexing(&mut self, tuple_indexing: &ast::TupleIndex) {
-    let tuple_general_type = self
+  fn visit_binary_op(&mut self, binary_op: &ast::BinaryOp) {
+    if !matches!(
+      binary_op.operator,
+      ast::BinaryOperator::Modulo | ast::BinaryOperator::Divide
+    ) {
+      return;
+    }
+
+    // NOTE: The operand types are generally unknown during inference, thus
+    // they can only be validated here, once they have been resolved.
+    let operand_type = self
       .resolution_helper
-      .resolve_by_id(
-        &tuple_indexing.indexed_tuple_type_id,
-        self.universe_stack.clone(),
-      )
+      .resolve_by_id(&binary_op.operand_type_id, self.universe_stack.clone())
       .expect(auxiliary::BUG_MISSING_TYPE);
 
-    let tuple_type = assert_extract!(tuple_general_type.as_ref(), types::Type::Tuple);
+    if binary_op.operator == ast::BinaryOperator::Divide {
+      let numeric_pattern = types::TypePattern::AnyOf(vec![
+        types::TypePattern::AnyReal,
+        types::TypePattern::AnySignedInteger,
+        types::TypePattern::AnyUnsignedInteger,
+      ]);
 
-    if tuple_indexing.index as usize >= tuple_type.0.len() {
+      if !operand_type.matches_pattern(&numeric_pattern) {
+        self
+          .diagnostics
+          .push(diagnostic::Diagnostic::DivisionOperandMustBeNumeric(
+            operand_type.into_owned(),
+          ));
+      }
+    } else if !operand_type.matches_pattern(&types::TypePattern::AnySignedInteger) {
       self
         .diagnostics
-        .push(diagnostic::Diagnostic::TupleAccessOutOfBounds {
-          index: tuple_indexing.index as usize,
-          tuple_length: tuple_type.0.len(),
-        });
+        .push(diagnostic::Diagnostic::ModuloOperandMustBeSignedInteger(
+          operand_type.into_owned(),
+        ));
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pointer_cast_unsafe_scope() {
+    use visit::Visitor;
+
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let int_type = types::Type::Primitive(types::PrimitiveType::Integer(
+      types::BitWidth::Width32,
+      true,
+    ));
+
+    let type_env = symbol_table::TypeEnvironment::from([
+      (
+        symbol_table::TypeId(0),
+        int_type.clone().into_pointer_type(),
+      ),
+      (symbol_table::TypeId(1), int_type.clone()),
+    ]);
+
+    let resolution_helper = resolution::ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    let cast = ast::Cast {
+      type_id: symbol_table::TypeId(1),
+      operand_type_id: symbol_table::TypeId(0),
+      operand: ast::Expr::Pass(std::rc::Rc::new(ast::Pass)),
+      cast_type: int_type,
+    };
+
+    let mut context = SemanticCheckContext::new(&symbol_table, &resolution_helper);
+
+    context.visit_cast(&cast);
+
+    assert!(matches!(
+      context.diagnostics.as_slice(),
+      [diagnostic::Diagnostic::CannotUseOutsideUnsafe]
+    ));
+
+    let mut context = SemanticCheckContext::new(&symbol_table, &resolution_helper);
+
+    context.in_unsafe_scope = true;
+    context.visit_cast(&cast);
+
+    assert!(context.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn array_index_out_of_bounds() {
+    use visit::Visitor;
+
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let type_env = symbol_table::TypeEnvironment::from([(
+      symbol_table::TypeId(0),
+      types::Type::Array {
+        element: Box::new(types::Type::Primitive(types::PrimitiveType::Char)),
+        length: 3,
+      },
+    )]);
+
+    let resolution_helper = resolution::ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    let array_indexing = |index: f64| ast::PointerIndexing {
+      type_id: symbol_table::TypeId(0),
+      pointer: ast::Expr::Pass(std::rc::Rc::new(ast::Pass)),
+      index: ast::Expr::Literal(ast::Literal {
+        type_id: symbol_table::TypeId(1),
+        kind: ast::LiteralKind::Number {
+          value: index,
+          is_real: false,
+          bit_width: types::BitWidth::Width32,
+          type_hint: None,
+        },
+      }),
+    };
+
+    let mut context = SemanticCheckContext::new(&symbol_table, &resolution_helper);
+
+    context.visit_pointer_indexing(&array_indexing(2.0));
+    assert!(context.diagnostics.is_empty());
+
+    context.visit_pointer_indexing(&array_indexing(3.0));
+
+    assert!(matches!(
+      context.diagnostics.as_slice(),
+      [diagnostic::Diagnostic::ArrayIndexOutOfBounds {
+        index: 3,
+        array_length: 3
+      }]
+    ));
+  }
+
+  #[test]
+  fn division_operand_must_be_numeric() {
+    use visit::Visitor;
+
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let type_env = symbol_table::TypeEnvironment::from([
+      (
+        symbol_table::TypeId(0),
+        types::Type::Primitive(types::PrimitiveType::Integer(
+          types::BitWidth::Width32,
+          false,
+        )),
+      ),
+      (
+        symbol_table::TypeId(1),
+        types::Type::Primitive(types::PrimitiveType::Bool),
+      ),
+    ]);
+
+    let resolution_helper = resolution::ResolutionHelper::new(&universes, &symbol_table, &type_env);
+
+    let division = |operand_type_id: symbol_table::TypeId| ast::BinaryOp {
+      type_id: operand_type_id,
+      operand_type_id,
+      operator: ast::BinaryOperator::Divide,
+      left_operand: ast::Expr::Pass(std::rc::Rc::new(ast::Pass)),
+      right_operand: ast::Expr::Pass(std::rc::Rc::new(ast::Pass)),
+    };
+
+    let mut context = SemanticCheckContext::new(&symbol_table, &resolution_helper);
+
+    context.visit_binary_op(&division(symbol_table::TypeId(0)));
+    assert!(context.diagnostics.is_empty());
+
+    context.visit_binary_op(&division(symbol_table::TypeId(1)));
+
+    assert!(matches!(
+      context.diagnostics.as_slice(),
+      [diagnostic::Diagnostic::DivisionOperandMustBeNumeric(
+        types::Type::Primitive(types::PrimitiveType::Bool)
+      )]
+    ));
+  }
+
+  #[test]
+  fn check_variadic_arguments() {
+    let argument_types = [
+      types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width8, true)),
+      types::Type::Primitive(types::PrimitiveType::Integer(
+        types::BitWidth::Width32,
+        true,
+      )),
+      types::Type::Primitive(types::PrimitiveType::Bool),
+      types::Type::Primitive(types::PrimitiveType::Char).into_pointer_type(),
+    ];
+
+    assert!(matches!(
+      SemanticCheckContext::check_variadic_arguments(&argument_types).as_slice(),
+      [
+        diagnostic::Diagnostic::VariadicArgumentPromoted {
+          to: types::Type::Primitive(types::PrimitiveType::Integer(
+            types::BitWidth::Width32,
+            true
+          )),
+          ..
+        },
+        diagnostic::Diagnostic::VariadicArgumentPromoted {
+          from: types::Type::Primitive(types::PrimitiveType::Bool),
+          ..
+        },
+        diagnostic::Diagnostic::VariadicPointerDecayed(types::Type::Pointer(..))
+      ]
+    ));
+  }
+
+  #[test]
+  fn check_field_access_chain() {
+    let inner_object_type = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(String::from("b"), types::Type::Unit)]),
+      kind: types::ObjectKind::Closed,
+    });
+
+    let root_type = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(String::from("a"), inner_object_type)]),
+      kind: types::ObjectKind::Closed,
+    });
+
+    assert!(SemanticCheckContext::check_field_access_chain(
+      &root_type,
+      &[String::from("a"), String::from("b")]
+    )
+    .is_none());
+
+    assert!(matches!(
+      SemanticCheckContext::check_field_access_chain(
+        &root_type,
+        &[String::from("a"), String::from("c")]
+      ),
+      Some(diagnostic::Diagnostic::ObjectFieldDoesNotExist(field_name)) if field_name == "c"
+    ));
+
+    // A missing intermediate field is reported by its own access.
+    assert!(SemanticCheckContext::check_field_access_chain(
+      &root_type,
+      &[String::from("c"), String::from("b")]
+    )
+    .is_none());
+  }
+
+  #[test]
+  fn check_array_lengths() {
+    let array_type = |length| types::Type::Array {
+      element: Box::new(types::Type::Primitive(types::PrimitiveType::Bool)),
+      length,
+    };
+
+    let oversized_length = u64::from(u32::MAX) + 1;
+
+    assert!(SemanticCheckContext::check_array_lengths(&array_type(u64::from(u32::MAX))).is_empty());
+
+    // Arrays nested within other types are also verified.
+    assert!(matches!(
+      SemanticCheckContext::check_array_lengths(&array_type(oversized_length).into_pointer_type())
+        .as_slice(),
+      [diagnostic::Diagnostic::ArrayTooLarge(length)] if *length == oversized_length
+    ));
+  }
+
+  #[test]
+  fn check_union_discriminants() {
+    let mock_variant = |name: &str, kind| {
+      (
+        name.to_string(),
+        std::rc::Rc::new(ast::UnionVariant {
+          registry_id: symbol_table::RegistryId(1),
+          union_id: symbol_table::RegistryId(0),
+          name: name.to_string(),
+          kind,
+        }),
+      )
+    };
+
+    let union_type = types::Type::Union(std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("a"),
+      variants: std::collections::BTreeMap::from([
+        mock_variant(
+          "b",
+          ast::UnionVariantKind::Singleton {
+            name: String::from("b"),
+            relative_index: 0,
+            explicit_value: None,
+          },
+        ),
+        mock_variant("c", ast::UnionVariantKind::String(String::from("c"))),
+      ]),
+    }));
+
+    assert!(matches!(
+      SemanticCheckContext::check_union_discriminants(&union_type).as_slice(),
+      [diagnostic::Diagnostic::NonIntegerUnionDiscriminant { variant_name, .. }] if variant_name == "c"
+    ));
+  }
+}
@@ -1,11 +1,14 @@
-//! A helper module to be used exclusively by the unification module to
-//! substitute type variables.
+//! A helper module to be used by the unification and instantiation modules to
+//! substitute type variables and generics.
 
 use crate::{assert_extract, symbol_table, types};
 
 #[derive(Debug)]
 pub(crate) enum SubstitutionError {
   TypeStripError(types::TypeStripError),
+  /// Stripping the given stub type led back to itself, such as with mutually
+  /// recursive type aliases.
+  RecursiveStubType(types::StubType),
   DirectRecursionCheckError(types::DirectRecursionCheckError),
 }
 
@@ -27,25 +30,6 @@ pub(crate) struct UnificationSubstitutionHelper<'a> {
 }
 
 impl<'a> UnificationSubstitutionHelper<'a> {
-  fn substitute_signature_type(
-    &self,
-    signature_type: &types::SignatureType,
-  ) -> Result<types::Type, SubstitutionError> {
-    let parameter_types = signature_type
-      .parameter_types
-      .iter()
-      .map(|parameter_type| self.substitute(parameter_type))
-      .collect::<Result<Vec<_>, _>>()?;
-
-    let return_type = self.substitute(&signature_type.return_type)?;
-
-    Ok(types::Type::Signature(types::SignatureType {
-      parameter_types,
-      return_type: Box::new(return_type),
-      arity_mode: signature_type.arity_mode,
-    }))
-  }
-
   fn substitute_generic_type(
     &self,
     ty: &types::Type,
@@ -94,9 +78,10 @@ impl<'a> UnificationSubstitutionHelper<'a> {
     }
   }
 
+  /// Substitute an object type, whose fields have already been substituted.
   fn substitute_object_type(
     &self,
-    object_type: &types::ObjectType,
+    object_type: types::ObjectType,
   ) -> Result<types::Type, SubstitutionError> {
     if let types::ObjectKind::Open(substitution_id) = object_type.kind {
       // SAFETY: What if it wasn't instantiated? Say, it was inside a generic function that was never called? In such a case, this shouldn't fail but the way the instantiation function is built mandates that all types have to be resolved/instantiated. Might need to change that (perhaps by returning an `Option`).
@@ -119,38 +104,24 @@ impl<'a> UnificationSubstitutionHelper<'a> {
       }
     }
 
-    let substituted_fields = object_type
-      .fields
-      // OPTIMIZE: Avoid cloning.
-      .to_owned()
-      .into_iter()
-      .map(|(name, field_type)| Ok((name, self.substitute(&field_type)?)))
-      .collect::<Result<types::ObjectFieldMap, SubstitutionError>>()?;
-
-    Ok(types::Type::Object(types::ObjectType {
-      fields: substituted_fields,
-      kind: object_type.kind,
-    }))
+    Ok(types::Type::Object(object_type))
   }
 
-  /// Substitute a type's entire subtree, substituting any type variable with its
-  /// concrete counterpart (if available).
-  ///
-  /// If the substitution is not defined, the same type is returned. This
-  /// function will recursively substitute type variables, until a non-variable
-  /// type is encountered.
-  ///
-  /// In the case that a type variable points to itself (ie. it has no corresponding
-  /// monomorphic type in the given substitution environment), the same, unresolved
-  /// type variable will be returned. Function callers should account for this.
-  pub(crate) fn substitute(&self, ty: &types::Type) -> Result<types::Type, SubstitutionError> {
-    // CONSIDER: (test:type_def_nested) On the case that the substitution process ends up on a (nested) polymorphic type stub artifact, it will simply stop its process and return it. This needs to be handle, as it is a hole! Consider improving the substitution function to provide more information about what it did (maybe return an enum alongside the type indicating what was the stopping condition?). Since the type is left with a nested polymorphic stub type, it proceeds to FAIL the concrete assertion!
-
-    // The type should be stripped of all simple, monomorphic stub type
-    // layers before processing.
-    let stripped_type = ty
-      .to_owned()
-      .try_strip_all_monomorphic_stub_layers(self.symbol_table)?;
+  /// Strip all of the monomorphic stub type layers of a stub type, and
+  /// substitute the resulting type.
+  fn substitute_stub_type(
+    &self,
+    stub_type: types::StubType,
+  ) -> Result<types::Type, SubstitutionError> {
+    let stripped_type = stub_type
+      .clone()
+      .strip_all_monomorphic_stub_layers(self.symbol_table)
+      .map_err(|type_strip_error| match type_strip_error {
+        types::TypeStripError::RecursionDetected => {
+          SubstitutionError::RecursiveStubType(stub_type.to_owned())
+        }
+        type_strip_error => SubstitutionError::TypeStripError(type_strip_error),
+      })?;
 
     // Recursive types are not yet supported.
     if stripped_type.contains_directly_recursive_types(self.symbol_table)? {
@@ -159,62 +130,170 @@ impl<'a> UnificationSubstitutionHelper<'a> {
       // return Err(SubstitutionError::RecursiveTypeDetected);
     }
 
-    match &stripped_type {
-      types::Type::Pointer(pointee) => Ok(self.substitute(pointee.as_ref())?.into_pointer_type()),
-      types::Type::Generic(generic) => self.substitute_generic_type(&stripped_type, generic),
-      types::Type::Object(object_type) => self.substitute_object_type(object_type),
-      types::Type::Reference(ty) => Ok(types::Type::Reference(Box::new(
-        self.substitute(ty.as_ref())?,
-      ))),
-      types::Type::Signature(signature_type) => self.substitute_signature_type(signature_type),
-      types::Type::Tuple(types::TupleType(element_types)) => {
-        Ok(types::Type::Tuple(types::TupleType(
-          element_types
-            .into_iter()
-            .map(|element_type| self.substitute(element_type))
-            .collect::<Result<Vec<_>, _>>()?,
-        )))
-      }
-      // In the case that a stub type is encountered after stripping,
-      // it must be a polymorphic stub type, which this function cannot handle.
-      types::Type::Stub(stub_type) => {
-        assert!(
-          !stub_type.generic_hints.is_empty(),
-          "all monomorphic stub type layers should have been stripped"
-        );
-
-        // Signal to the caller that a polymorphic stub type was encountered
-        // by returning a partial substitution result.
-        if !stub_type.generic_hints.is_empty() {
-          return Ok(stripped_type);
-        }
+    match stripped_type {
+      // In the case that a stub type remains after stripping, it must be
+      // a polymorphic stub type, which this function cannot handle. Signal
+      // to the caller that a polymorphic stub type was encountered by
+      // returning a partial substitution result.
+      types::Type::Stub(..) => Ok(stripped_type),
+      // The inner types of the stripped type have not been substituted yet.
+      _ => self.substitute(&stripped_type),
+    }
+  }
 
-        Ok(types::Type::Stub(stub_type.to_owned()))
-      }
+  /// Substitute a single layer of a type, whose inner types have already
+  /// been substituted.
+  fn substitute_layer(&self, layer: types::Type) -> Result<types::Type, SubstitutionError> {
+    match layer {
+      types::Type::Stub(stub_type) => self.substitute_stub_type(*stub_type),
+      types::Type::Generic(ref generic) => self.substitute_generic_type(&layer, generic),
+      types::Type::Object(object_type) => self.substitute_object_type(object_type),
       types::Type::Variable(types::TypeVariable {
         substitution_id, ..
       }) if self
         .substitution_env
-        .get(substitution_id)
+        .get(&substitution_id)
         // NOTE: The type doesn't need to be compared by id, since they're both unique
         // per-type, thus it would always be false, which would lead to a stack overflow.
         // Instead, by the point of instantiation it is assumed that both types have been
         // unified, and thus any errors would have been reported.
-        .map_or(true, |ty| !ty.is_same_type_variable_as(substitution_id)) =>
+        .map_or(true, |ty| !ty.is_same_type_variable_as(&substitution_id)) =>
       {
         self.substitute(
           self
             .substitution_env
-            .get(substitution_id)
+            .get(&substitution_id)
             // SAFETY: Undocumented/unchecked unwrap.
             .unwrap(),
         )
       }
-      // TODO: Implement. Handle unions.
-      types::Type::Union(..) => todo!(),
-      // The type is not a stub, generic (at least at this layer), or a fully
-      // concrete type. There is nothing to do.
-      _ => Ok(ty.to_owned()),
+      // There is nothing to substitute on this layer.
+      _ => Ok(layer),
     }
   }
+
+  pub(crate) fn substitute(&self, ty: &types::Type) -> Result<types::Type, SubstitutionError> {
+    // CONSIDER: (test:type_def_nested) On the case that the substitution process ends up on a (nested) polymorphic type stub artifact, it will simply stop its process and return it. This needs to be handle, as it is a hole! Consider improving the substitution function to provide more information about what it did (maybe return an enum alongside the type indicating what was the stopping condition?). Since the type is left with a nested polymorphic stub type, it proceeds to FAIL the concrete assertion!
+
+    // Concrete leaf types have no inner types, and thus nothing to
+    // substitute.
+    if let Some(
+      concrete_type @ (types::Type::Primitive(..)
+      | types::Type::Range(..)
+      | types::Type::Opaque
+      | types::Type::Unit),
+    ) = ty.as_concrete()
+    {
+      return Ok(concrete_type.to_owned());
+    }
+
+    // Since inner types are substituted before the types containing them,
+    // each layer only needs to handle itself.
+    ty.to_owned().fold(&|layer| self.substitute_layer(layer))
+  }
+
+  /// Substitute all types within the given type environment.
+  ///
+  /// The first substitution failure encountered is returned alongside the
+  /// id of the entry that caused it, and the remaining entries are left
+  /// unprocessed.
+  pub(crate) fn substitute_batch(
+    &self,
+    env: &symbol_table::TypeEnvironment,
+  ) -> Result<symbol_table::TypeEnvironment, (symbol_table::TypeId, SubstitutionError)> {
+    env
+      .iter()
+      .map(|(type_id, ty)| {
+        self
+          .substitute(ty)
+          .map(|substitution| (*type_id, substitution))
+          .map_err(|substitution_error| (*type_id, substitution_error))
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ast;
+
+  #[test]
+  fn substitute_cyclic_stub_alias() {
+    let mut symbol_table = symbol_table::SymbolTable::default();
+
+    let mock_stub_type = |link_id, base_name: &str| {
+      types::Type::Stub(Box::new(types::StubType {
+        universe_id: symbol_table::UniverseId(link_id, base_name.to_string()),
+        path: ast::Path {
+          link_id: symbol_table::LinkId(link_id),
+          qualifier: None,
+          base_name: base_name.to_string(),
+          sub_name: None,
+          symbol_kind: symbol_table::SymbolKind::Type,
+        },
+        generic_hints: Vec::new(),
+      }))
+    };
+
+    // type A = B
+    // type B = A
+    for (id, name, body) in [
+      (0, "A", mock_stub_type(1, "B")),
+      (1, "B", mock_stub_type(0, "A")),
+    ] {
+      let registry_id = symbol_table::RegistryId(id);
+
+      symbol_table.registry.insert(
+        registry_id,
+        symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+          registry_id,
+          name: name.to_string(),
+          body,
+          generics: ast::Generics {
+            parameters: Vec::new(),
+          },
+        })),
+      );
+
+      symbol_table
+        .links
+        .insert(symbol_table::LinkId(id), registry_id);
+    }
+
+    let substitution_id = symbol_table::SubstitutionId(0);
+
+    let substitution_env =
+      symbol_table::SubstitutionEnv::from([(substitution_id, mock_stub_type(0, "A"))]);
+
+    let substitution_helper = UnificationSubstitutionHelper {
+      symbol_table: &symbol_table,
+      substitution_env: &substitution_env,
+    };
+
+    // The cyclic alias is only reached once the type variable is substituted.
+    let pointer_type = types::Type::Variable(types::TypeVariable {
+      substitution_id,
+      debug_name: "test",
+    })
+    .into_pointer_type();
+
+    let result = substitution_helper.substitute(&pointer_type);
+
+    assert!(matches!(
+      result,
+      Err(SubstitutionError::RecursiveStubType(stub_type)) if stub_type.path.base_name == "A"
+    ));
+
+    let type_env = symbol_table::TypeEnvironment::from([(symbol_table::TypeId(0), pointer_type)]);
+
+    // Batch substitution reports the entry at which it failed.
+    assert!(matches!(
+      substitution_helper.substitute_batch(&type_env),
+      Err((
+        symbol_table::TypeId(0),
+        SubstitutionError::RecursiveStubType(..)
+      ))
+    ));
+  }
 }
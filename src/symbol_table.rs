@@ -22,6 +22,7 @@ use crate::{ast, instantiation, types};
 /// Any node that has a declaration id will be automatically stored into the
 /// symbol table during the declare step.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct RegistryId(pub usize);
 
 /// A unique identifier for a type.
@@ -31,6 +32,7 @@ pub struct RegistryId(pub usize);
 ///
 /// Furthermore, multiple type ids may point to the same type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct TypeId(pub usize);
 
 /// A unique id representing the key of an entry on a type substitution table,
@@ -39,6 +41,7 @@ pub struct TypeId(pub usize);
 /// Substituted types are meta types that represent other types, and which decay
 /// into concrete types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct SubstitutionId(pub usize);
 
 /// An intermediary unique id that can be used to serve as a middleman
@@ -46,6 +49,7 @@ pub struct SubstitutionId(pub usize);
 ///
 /// Used to associate paths/references with a corresponding node id.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct LinkId(pub usize);
 
 /// An instantiation artifact is an item that may reference a polymorphic
@@ -56,6 +60,7 @@ pub struct LinkId(pub usize);
 /// Artifact ids uniquely identify such artifacts, and are primarily used
 /// for the retrieval of the artifact's generic substitution environment.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct UniverseId(pub usize, pub String);
 
 /// A type environment that contains the instantiated types of various nodes.
@@ -64,14 +69,190 @@ pub struct UniverseId(pub usize, pub String);
 /// stored types are guaranteed to be resolved, and do not further any alias resolution.
 pub type TypeEnvironment = std::collections::HashMap<TypeId, types::Type>;
 
+/// The location of a node within its source file.
+///
+/// Both the start and end positions correspond to the source positions of the
+/// node's first and last tokens respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+/// A mapping from type ids to the span of the node that owns them.
+///
+/// This runs parallel to the type environment, and allows diagnostics that
+/// refer to type ids to be reported at their corresponding source locations.
+pub type SpanTable = std::collections::HashMap<TypeId, Span>;
+
 /// A mapping of type variables or generics to other type variables or monomorphic types.
 /// Also known as a universe of types.
 ///
 /// This is used during type unification as the substitution environment used
 /// to resolve constraints.
+///
+/// Since substitution ids are allocated monotonically, the ordered map
+/// iterates substitutions in their creation order, which keeps dumps of the
+/// environment deterministic.
 pub type SubstitutionEnv = std::collections::BTreeMap<SubstitutionId, types::Type>;
 
+/// A snapshot of a substitution environment, which is the length of its
+/// undo log at the time the snapshot was taken.
+pub(crate) struct SubstitutionEnvSnapshot(usize);
+
+/// A log of the bindings made to a substitution environment while any
+/// snapshot is open, along with the substitution that each binding replaced
+/// (if any), which allows the bindings to be undone.
+///
+/// Note that type variables are bound to themselves when they are created,
+/// thus most bindings replace an existing substitution rather than adding
+/// a new one.
+#[derive(Default)]
+pub(crate) struct SubstitutionUndoLog {
+  entries: Vec<(SubstitutionId, Option<types::Type>)>,
+  open_snapshots: usize,
+}
+
+impl SubstitutionUndoLog {
+  /// Start recording bindings, which can later be undone by rolling back to
+  /// the returned snapshot.
+  pub(crate) fn snapshot(&mut self) -> SubstitutionEnvSnapshot {
+    self.open_snapshots += 1;
+
+    SubstitutionEnvSnapshot(self.entries.len())
+  }
+
+  /// Record the substitution that was replaced by a binding, if any
+  /// snapshot is open.
+  pub(crate) fn record(&mut self, substitution_id: SubstitutionId, previous: Option<types::Type>) {
+    if self.open_snapshots > 0 {
+      self.entries.push((substitution_id, previous));
+    }
+  }
+
+  /// Undo all bindings made to the given substitution environment since the
+  /// snapshot was taken, in reverse order.
+  pub(crate) fn rollback(
+    &mut self,
+    substitution_env: &mut SubstitutionEnv,
+    snapshot: SubstitutionEnvSnapshot,
+  ) {
+    for (substitution_id, previous) in self.entries.drain(snapshot.0..).rev() {
+      match previous {
+        Some(previous) => substitution_env.insert(substitution_id, previous),
+        None => substitution_env.remove(&substitution_id),
+      };
+    }
+
+    self.close(snapshot);
+  }
+
+  /// Keep all bindings made since the snapshot was taken.
+  pub(crate) fn commit(&mut self, snapshot: SubstitutionEnvSnapshot) {
+    self.close(snapshot);
+  }
+
+  fn close(&mut self, snapshot: SubstitutionEnvSnapshot) {
+    assert!(
+      self.open_snapshots > 0 && snapshot.0 <= self.entries.len(),
+      "snapshot should be open"
+    );
+
+    self.open_snapshots -= 1;
+
+    // Bindings no longer need to be recorded once the outermost snapshot
+    // is closed.
+    if self.open_snapshots == 0 {
+      self.entries.clear();
+    }
+  }
+}
+
+pub(crate) trait SubstitutionEnvExt {
+  /// Follow the chain of type variable substitutions starting at the given
+  /// substitution id, returning every substitution id visited along the way.
+  ///
+  /// The last substitution id in the chain either has no entry, or its
+  /// entry is not a type variable.
+  fn lookup_chain(&self, substitution_id: SubstitutionId) -> Vec<SubstitutionId>;
+
+  /// Determine whether two type variables will ultimately be resolved to
+  /// the same type, based solely on the current substitutions.
+  fn transitively_equivalent(&self, a: SubstitutionId, b: SubstitutionId) -> bool;
+
+  /// Produce a textual dump of all substitutions, one per line, ordered by
+  /// substitution id.
+  ///
+  /// The output is deterministic, which makes it suitable for debugging and
+  /// snapshot comparisons.
+  fn dump_sorted(&self) -> String;
+
+  /// Bind the given substitution id, recording the substitution it replaces
+  /// (if any) in the undo log.
+  fn bind(
+    &mut self,
+    substitution_id: SubstitutionId,
+    ty: types::Type,
+    undo_log: &mut SubstitutionUndoLog,
+  );
+}
+
+impl SubstitutionEnvExt for SubstitutionEnv {
+  fn lookup_chain(&self, substitution_id: SubstitutionId) -> Vec<SubstitutionId> {
+    let mut chain = vec![substitution_id];
+
+    while let Some(types::Type::Variable(type_variable)) = self.get(chain.last().unwrap()) {
+      // Stop on cycles, otherwise this would loop forever.
+      if chain.contains(&type_variable.substitution_id) {
+        break;
+      }
+
+      chain.push(type_variable.substitution_id);
+    }
+
+    chain
+  }
+
+  fn transitively_equivalent(&self, a: SubstitutionId, b: SubstitutionId) -> bool {
+    let chain_a = self.lookup_chain(a);
+    let chain_b = self.lookup_chain(b);
+
+    if chain_a.contains(&b) || chain_b.contains(&a) {
+      return true;
+    }
+
+    let terminal_a = chain_a.last().and_then(|id| self.get(id));
+    let terminal_b = chain_b.last().and_then(|id| self.get(id));
+
+    match (terminal_a, terminal_b) {
+      // REVIEW: Types which aren't concrete are assumed not to be equivalent, since comparing them would require unification.
+      (Some(terminal_a), Some(terminal_b)) => terminal_a.concrete_eq(terminal_b) == Some(true),
+      _ => false,
+    }
+  }
+
+  fn dump_sorted(&self) -> String {
+    self
+      .iter()
+      .map(|(substitution_id, ty)| format!("${} = {:?}\n", substitution_id.0, ty))
+      .collect()
+  }
+
+  fn bind(
+    &mut self,
+    substitution_id: SubstitutionId,
+    ty: types::Type,
+    undo_log: &mut SubstitutionUndoLog,
+  ) {
+    let previous = self.insert(substitution_id, ty);
+
+    undo_log.record(substitution_id, previous);
+  }
+}
+
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum SymbolKind {
   /// A node declaration, such as a function, parameter or a binding.
   Declaration,
@@ -103,6 +284,7 @@ impl Symbol {
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct SymbolPath {
   pub base_name: String,
   pub sub_name: Option<String>,
@@ -124,6 +306,7 @@ pub type Scope = std::collections::HashMap<SymbolPath, ScopeEntry>;
 pub type ScopeEntry = (RegistryId, Symbol);
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct Qualifier {
   pub package_name: String,
   pub module_name: String,
@@ -271,5 +454,74 @@ pub mod tests {
     }
   }
 
+  fn mock_type_variable(substitution_id: SubstitutionId) -> types::Type {
+    types::Type::Variable(types::TypeVariable {
+      substitution_id,
+      debug_name: "test",
+    })
+  }
+
+  #[test]
+  fn transitively_equivalent() {
+    let bool_type = types::Type::Primitive(types::PrimitiveType::Bool);
+
+    let substitution_env = SubstitutionEnv::from([
+      (SubstitutionId(0), mock_type_variable(SubstitutionId(1))),
+      (SubstitutionId(1), bool_type.clone()),
+      (SubstitutionId(2), bool_type),
+      (SubstitutionId(3), types::Type::Unit),
+      (SubstitutionId(4), mock_type_variable(SubstitutionId(5))),
+    ]);
+
+    assert!(substitution_env.transitively_equivalent(SubstitutionId(0), SubstitutionId(2)));
+    assert!(substitution_env.transitively_equivalent(SubstitutionId(4), SubstitutionId(5)));
+    assert!(!substitution_env.transitively_equivalent(SubstitutionId(0), SubstitutionId(3)));
+  }
+
+  #[test]
+  fn snapshot_rollback() {
+    // Type variables start out bound to themselves.
+    let mut substitution_env = SubstitutionEnv::from([
+      (SubstitutionId(0), mock_type_variable(SubstitutionId(0))),
+      (SubstitutionId(1), mock_type_variable(SubstitutionId(1))),
+    ]);
+
+    let mut undo_log = SubstitutionUndoLog::default();
+
+    // Bindings made while no snapshot is open are not recorded.
+    substitution_env.bind(SubstitutionId(3), types::Type::Unit, &mut undo_log);
+
+    let snapshot = undo_log.snapshot();
+
+    substitution_env.bind(SubstitutionId(0), types::Type::Unit, &mut undo_log);
+    substitution_env.bind(SubstitutionId(2), types::Type::Unit, &mut undo_log);
+
+    let nested_snapshot = undo_log.snapshot();
+
+    substitution_env.bind(SubstitutionId(0), types::Type::Opaque, &mut undo_log);
+    undo_log.commit(nested_snapshot);
+    undo_log.rollback(&mut substitution_env, snapshot);
+
+    assert_eq!(3, substitution_env.len());
+
+    assert!(substitution_env
+      .values()
+      .all(|ty| !matches!(ty, types::Type::Opaque)));
+
+    assert!(matches!(
+      substitution_env.get(&SubstitutionId(0)),
+      Some(types::Type::Variable(types::TypeVariable {
+        substitution_id: SubstitutionId(0),
+        ..
+      }))
+    ));
+
+    assert!(!substitution_env.contains_key(&SubstitutionId(2)));
+    assert!(matches!(
+      substitution_env.get(&SubstitutionId(3)),
+      Some(types::Type::Unit)
+    ));
+  }
+
   // TODO: Add more tests for this module.
 }
@@ -3,7 +3,7 @@
 //! such as constraint gathering, unification or instantiation.
 
 use crate::{
-  ast,
+  ast, diagnostic,
   symbol_table::{self, SubstitutionEnv},
 };
 
@@ -13,7 +13,8 @@ use crate::{
 /// comparing codegen tests.
 pub type ObjectFieldMap = std::collections::BTreeMap<String, Type>;
 
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum ObjectKind {
   /// The object is open and can be extended.
   ///
@@ -27,6 +28,7 @@ pub enum ObjectKind {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct ObjectType {
   pub fields: ObjectFieldMap,
   /// Describes the kind of object type. Used to aid with type inference
@@ -34,7 +36,57 @@ pub struct ObjectType {
   pub kind: ObjectKind,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+impl ObjectType {
+  pub(crate) fn field_type(&self, name: &str) -> Option<&Type> {
+    self.fields.get(name)
+  }
+
+  /// Merge an open object type into a closed object type, producing the
+  /// resulting closed object type.
+  ///
+  /// All of the open object's fields must be present on the closed object,
+  /// and their types must not be in conflict. Only conflicts between concrete
+  /// types are detected; fields involving meta types are left for the
+  /// unification engine to verify.
+  pub(crate) fn merge(
+    open: &ObjectType,
+    closed: &ObjectType,
+  ) -> Result<ObjectType, ObjectMergeError> {
+    for (field_name, open_field_type) in &open.fields {
+      let Some(closed_field_type) = closed.field_type(field_name) else {
+        return Err(ObjectMergeError::UnknownField(field_name.to_owned()));
+      };
+
+      if open_field_type.concrete_eq(closed_field_type) == Some(false) {
+        return Err(ObjectMergeError::FieldTypeConflict {
+          field_name: field_name.to_owned(),
+          open_field_type: open_field_type.to_owned(),
+          closed_field_type: closed_field_type.to_owned(),
+        });
+      }
+    }
+
+    Ok(ObjectType {
+      fields: closed.fields.clone(),
+      kind: ObjectKind::Closed,
+    })
+  }
+}
+
+#[derive(Debug)]
+pub(crate) enum ObjectMergeError {
+  /// A field of the open object type is not present on the closed object type.
+  UnknownField(String),
+  /// A field is present on both object types, but with conflicting types.
+  FieldTypeConflict {
+    field_name: String,
+    open_field_type: Type,
+    closed_field_type: Type,
+  },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum ArityMode {
   Variadic {
     /// Used to allow variadic foreign functions to specify the minimum amount
@@ -62,12 +114,28 @@ impl ArityMode {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct SignatureType {
   pub return_type: Box<Type>,
   pub parameter_types: Vec<Type>,
   pub arity_mode: ArityMode,
 }
 
+impl SignatureType {
+  /// Whether any of the parameter types, or the return type is itself a
+  /// signature type.
+  ///
+  /// Only the immediate level is considered; meta types (ie. type variables)
+  /// are not resolved, and thus do not count as signature types.
+  pub(crate) fn is_higher_order(&self) -> bool {
+    self
+      .parameter_types
+      .iter()
+      .chain(std::iter::once(self.return_type.as_ref()))
+      .any(|ty| matches!(ty.as_concrete(), Some(Type::Signature(..))))
+  }
+}
+
 /// Represents a type that needs to be resolved.
 ///
 /// Type stubs are ultimately resolved to types that may be declared, for example
@@ -76,6 +144,7 @@ pub struct SignatureType {
 ///
 /// Type stubs can only point to: type definitions, generics, and unions.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct StubType {
   pub universe_id: symbol_table::UniverseId,
   pub path: ast::Path,
@@ -99,6 +168,20 @@ impl StubType {
     symbol_table: &symbol_table::SymbolTable,
   ) -> Result<Type, TypeStripError> {
     // TODO: Use this function as part of the `substitute` method. To be able to do this, make this function part of `Type`'s implementation.
+    self
+      .strip_all_monomorphic_stub_layers_traced(symbol_table)
+      .map(|(stripped, _)| stripped)
+  }
+
+  /// Same as `strip_all_monomorphic_stub_layers`, but also returns the registry
+  /// id of the declaration targeted by the last stub layer that was followed.
+  ///
+  /// This is useful for tooling, or for diagnostics that need to reference the
+  /// declaration site of a type.
+  pub(crate) fn strip_all_monomorphic_stub_layers_traced(
+    self,
+    symbol_table: &symbol_table::SymbolTable,
+  ) -> Result<(Type, symbol_table::RegistryId), TypeStripError> {
     // OPTIMIZE: Use reference to avoid taking ownership of `self`.
 
     let mut current = self;
@@ -111,15 +194,23 @@ impl StubType {
       if seen_stub_types.contains(&current.universe_id) {
         return Err(TypeStripError::RecursionDetected);
       }
+
+      let target_registry_id = symbol_table
+        .links
+        .get(&current.path.link_id)
+        .copied()
+        .ok_or(TypeStripError::SymbolTableMissingEntry)?;
+
       // Only strip away stub types that have no generic hints (monomorphic stub types).
-      else if !current.generic_hints.is_empty() {
-        return Ok(Type::Stub(current));
+      if !current.generic_hints.is_empty() {
+        return Ok((Type::Stub(Box::new(current)), target_registry_id));
       }
 
       seen_stub_types.insert(current.universe_id.to_owned());
 
       let target_registry_item = symbol_table
-        .follow_link(&current.path.link_id)
+        .registry
+        .get(&target_registry_id)
         .ok_or(TypeStripError::SymbolTableMissingEntry)?;
 
       let next = match target_registry_item {
@@ -140,7 +231,7 @@ impl StubType {
               "there should be an expected discrepancy between the current stub type's generic hint count and the target type def.'s generic parameter count"
             );
 
-            return Ok(Type::Stub(current));
+            return Ok((Type::Stub(Box::new(current)), target_registry_id));
           }
 
           type_def.body.to_owned()
@@ -149,26 +240,59 @@ impl StubType {
       };
 
       if let Type::Stub(next_stub_type) = next {
-        current = next_stub_type;
+        current = *next_stub_type;
       } else {
-        return Ok(next);
+        return Ok((next, target_registry_id));
       }
     }
   }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct TupleType(pub Vec<Type>);
 
+impl TupleType {
+  pub(crate) fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Retrieve the type of the element at the given index, if it is
+  /// within bounds.
+  pub(crate) fn element(&self, index: usize) -> Option<&Type> {
+    self.0.get(index)
+  }
+
+  pub(crate) fn iter(&self) -> std::slice::Iter<'_, Type> {
+    self.0.iter()
+  }
+
+  /// Whether this is the empty tuple `()`, which is treated as a synonym
+  /// for the unit type.
+  pub(crate) fn is_unit_singleton(&self) -> bool {
+    self.is_empty()
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct GenericType {
   pub name: String,
   pub registry_id: symbol_table::RegistryId,
   pub substitution_id: symbol_table::SubstitutionId,
 }
 
-#[derive(PartialEq, PartialOrd, Copy, Clone, Debug, Eq)]
+#[derive(PartialEq, PartialOrd, Copy, Clone, Debug, Eq, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum BitWidth {
+  /// A single, logical bit, such as that of a boolean or a bitfield.
+  ///
+  /// Its layout is always rounded up to a whole byte.
+  Width1 = 1,
   Width8 = 8,
   Width16 = 16,
   Width32 = 32,
@@ -177,7 +301,20 @@ pub enum BitWidth {
   Width128 = 128,
 }
 
-#[derive(PartialEq, Clone, Debug, Eq)]
+impl BitWidth {
+  pub(crate) fn bits(&self) -> u32 {
+    *self as u32
+  }
+
+  /// The amount of bytes this width occupies in memory, rounding sub-byte
+  /// widths up to a whole byte.
+  pub(crate) fn size_in_bytes(&self) -> u32 {
+    self.bits().div_ceil(u8::BITS)
+  }
+}
+
+#[derive(PartialEq, Clone, Debug, Eq, Hash)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum PrimitiveType {
   /// An integer literal with its bit size, and whether it is
   /// signed.
@@ -188,7 +325,33 @@ pub enum PrimitiveType {
   CString,
 }
 
+impl PrimitiveType {
+  /// The logical bit width of this primitive type, if it has a fixed one.
+  ///
+  /// Booleans are logically a single bit wide, although their layout is
+  /// rounded up to a whole byte.
+  pub(crate) fn bit_width(&self) -> Option<BitWidth> {
+    match self {
+      PrimitiveType::Integer(bit_width, _) | PrimitiveType::Real(bit_width) => Some(*bit_width),
+      PrimitiveType::Bool => Some(BitWidth::Width1),
+      PrimitiveType::Char => Some(BitWidth::Width8),
+      PrimitiveType::CString => None,
+    }
+  }
+
+  /// Whether this is a number type of a different kind than the other
+  /// (ie. an integer type and a real type).
+  pub(crate) fn is_different_number_kind_than(&self, other: &PrimitiveType) -> bool {
+    matches!(
+      (self, other),
+      (PrimitiveType::Integer(..), PrimitiveType::Real(..))
+        | (PrimitiveType::Real(..), PrimitiveType::Integer(..))
+    )
+  }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub struct TypeVariable {
   pub substitution_id: symbol_table::SubstitutionId,
   pub debug_name: &'static str,
@@ -245,6 +408,74 @@ impl<'a> Iterator for ImmediateSubtreeIterator<'a> {
   }
 }
 
+/// A visitor over the tree of a type, driven by `Type::accept`.
+///
+/// By default, every method visits the inner types of the type it is given
+/// (if any), thus implementors only need to override the methods for the types
+/// that they are interested in. Overriding methods may still recurse by calling
+/// `accept` on the inner types themselves. Stub types are not resolved.
+pub trait TypeVisitor: Sized {
+  fn visit_union(&mut self, union: &ast::Union) {
+    for variant in union.variants.values() {
+      if let ast::UnionVariantKind::Type(payload_type) = &variant.kind {
+        payload_type.accept(self);
+      }
+    }
+  }
+
+  fn visit_range(&mut self, _start: u64, _end: u64) {}
+
+  fn visit_primitive(&mut self, _primitive_type: &PrimitiveType) {}
+
+  fn visit_pointer(&mut self, pointee: &Type) {
+    pointee.accept(self);
+  }
+
+  fn visit_opaque(&mut self) {}
+
+  fn visit_reference(&mut self, pointee: &Type) {
+    pointee.accept(self);
+  }
+
+  fn visit_tuple(&mut self, tuple_type: &TupleType) {
+    for element_type in tuple_type.iter() {
+      element_type.accept(self);
+    }
+  }
+
+  fn visit_array(&mut self, element: &Type, _length: u64) {
+    element.accept(self);
+  }
+
+  fn visit_object(&mut self, object_type: &ObjectType) {
+    for field_type in object_type.fields.values() {
+      field_type.accept(self);
+    }
+  }
+
+  fn visit_stub(&mut self, stub_type: &StubType) {
+    for generic_hint in &stub_type.generic_hints {
+      generic_hint.accept(self);
+    }
+  }
+
+  fn visit_signature(&mut self, signature_type: &SignatureType) {
+    for parameter_type in &signature_type.parameter_types {
+      parameter_type.accept(self);
+    }
+
+    signature_type.return_type.accept(self);
+  }
+
+  fn visit_variable(&mut self, _type_variable: &TypeVariable) {}
+
+  fn visit_generic(&mut self, _generic_type: &GenericType) {}
+
+  fn visit_typeof(&mut self, _typeof: &ast::Typeof) {}
+
+  fn visit_unit(&mut self) {}
+}
+
 /// Traverse the indirect subtree of a given type, resolving type stubs as they
 /// are encountered.
 ///
@@ -315,8 +546,117 @@ pub(crate) enum DirectRecursionCheckError {
   SymbolTableMissingEntry,
 }
 
+#[derive(Debug)]
+pub(crate) enum UnificationError {
+  /// An open object type could not be merged into the closed object type it
+  /// was unified against.
+  Merge(ObjectMergeError),
+  /// Two closed object types share only some of their fields.
+  IncompleteIntersection(usize, usize),
+  FieldCountMismatch(usize, usize),
+}
+
+/// Unify the structure of two object types, and produce the resulting object
+/// type.
+///
+/// The types of the intersecting fields are not unified here, since doing so
+/// requires the unification engine. Instead, the engine is expected to unify
+/// them before invoking this function.
+///
+/// When an open object type is unified against a closed object type, the open
+/// object type's substitution is registered on the given substitution environment,
+/// as it is no longer open to extensibility. When both object types are open, the
+/// resulting object type is the union of both, and it is left up to the caller to
+/// register it for both object types.
+pub(crate) fn unify_object_types(
+  object_a: &ObjectType,
+  object_b: &ObjectType,
+  substitution_env: &mut SubstitutionEnv,
+) -> Result<ObjectType, UnificationError> {
+  match (object_a.kind, object_b.kind) {
+    (ObjectKind::Open(substitution_id), ObjectKind::Open(_)) => {
+      let union = object_a
+        .fields
+        .iter()
+        .chain(object_b.fields.iter())
+        .map(|field| (field.0.to_owned(), field.1.to_owned()))
+        .collect();
+
+      Ok(ObjectType {
+        fields: union,
+        kind: ObjectKind::Open(substitution_id),
+      })
+    }
+    (ObjectKind::Open(substitution_id), ObjectKind::Closed) => {
+      unify_open_closed_object_types(object_a, object_b, substitution_id, substitution_env)
+    }
+    (ObjectKind::Closed, ObjectKind::Open(substitution_id)) => {
+      unify_open_closed_object_types(object_b, object_a, substitution_id, substitution_env)
+    }
+    // If they're both closed, the intersection must be the same length as any
+    // of the field's lengths. In other words, `len(intersection) == len(a) == len(b)`
+    // must hold true.
+    (ObjectKind::Closed, ObjectKind::Closed) => {
+      let intersection_len = object_a
+        .fields
+        .keys()
+        .filter(|field_name| object_b.fields.contains_key(*field_name))
+        .count();
+
+      if intersection_len != object_a.fields.len() {
+        Err(UnificationError::IncompleteIntersection(
+          intersection_len,
+          object_a.fields.len(),
+        ))
+      } else if object_a.fields.len() != object_b.fields.len() {
+        Err(UnificationError::FieldCountMismatch(
+          object_a.fields.len(),
+          object_b.fields.len(),
+        ))
+      } else {
+        Ok(object_a.to_owned())
+      }
+    }
+  }
+}
+
+fn unify_open_closed_object_types(
+  open_object: &ObjectType,
+  closed_object: &ObjectType,
+  substitution_id: symbol_table::SubstitutionId,
+  substitution_env: &mut SubstitutionEnv,
+) -> Result<ObjectType, UnificationError> {
+  // When open objects are unified against closed objects, they are
+  // no longer open to extensibility.
+  let unified_object =
+    ObjectType::merge(open_object, closed_object).map_err(UnificationError::Merge)?;
+
+  substitution_env.insert(substitution_id, Type::Object(unified_object.clone()));
+
+  Ok(unified_object)
+}
+
+/// A shallow, first-order pattern which types may be matched against.
+///
+/// This is useful for compiler passes that only need to know the general
+/// shape of a type, such as whether it is some signed integer, regardless
+/// of its bit-width.
 #[derive(Clone, Debug)]
+pub(crate) enum TypePattern {
+  AnySignedInteger,
+  AnyUnsignedInteger,
+  AnyReal,
+  /// Matches if any of the given patterns match.
+  AnyOf(Vec<TypePattern>),
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "json-errors", derive(serde::Serialize))]
 pub enum Type {
+  #[cfg_attr(
+    feature = "json-errors",
+    serde(serialize_with = "serialize_union_by_name")
+  )]
   Union(std::rc::Rc<ast::Union>),
   Range(u64, u64),
   Primitive(PrimitiveType),
@@ -326,9 +666,17 @@ pub enum Type {
   Opaque,
   Reference(Box<Type>),
   Tuple(TupleType),
+  /// A fixed-length sequence of values of the same element type.
+  Array {
+    element: Box<Type>,
+    length: u64,
+  },
   Object(ObjectType),
-  Stub(StubType),
-  Signature(SignatureType),
+  // NOTE: Stub and signature types are boxed because they are much larger
+  // than the other variants, and would otherwise inflate the size of every
+  // type, which are cloned and moved around frequently.
+  Stub(Box<StubType>),
+  Signature(Box<SignatureType>),
   /// A meta type to be used during unification.
   ///
   /// Represents a type that has not yet been solved. A type variable will
@@ -340,10 +688,27 @@ pub enum Type {
   /// This type can be instantiated given an environment that maps this generic
   /// type to a concrete type.
   Generic(GenericType),
+  /// A meta type that evaluates to the type of an expression at compile time.
+  ///
+  /// This type is replaced by a type variable during type inference, which is
+  /// constrained against the type of the expression, and is thus resolved
+  /// along with all other type variables during substitution.
+  #[cfg_attr(feature = "json-errors", serde(skip_serializing))]
+  Typeof(Box<ast::Typeof>),
   /// A meta type that represents the lack of a value.
   Unit,
 }
 
+/// Union types are serialized by name only, since serializing their
+/// declarations would require serializing AST nodes.
+#[cfg(feature = "json-errors")]
+fn serialize_union_by_name<S: serde::Serializer>(
+  union: &std::rc::Rc<ast::Union>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  serializer.serialize_str(&union.name)
+}
+
 impl Type {
   pub(crate) fn contains_generic_types(
     &self,
@@ -405,6 +770,38 @@ impl Type {
     Ok(recursion_detected)
   }
 
+  /// Dispatch the given visitor to the method corresponding to this type.
+  pub fn accept<V: TypeVisitor>(&self, visitor: &mut V) {
+    match self {
+      Type::Union(union) => visitor.visit_union(union),
+      Type::Range(start, end) => visitor.visit_range(*start, *end),
+      Type::Primitive(primitive_type) => visitor.visit_primitive(primitive_type),
+      Type::Pointer(pointee) => visitor.visit_pointer(pointee),
+      Type::Opaque => visitor.visit_opaque(),
+      Type::Reference(pointee) => visitor.visit_reference(pointee),
+      Type::Tuple(tuple_type) => visitor.visit_tuple(tuple_type),
+      Type::Array { element, length } => visitor.visit_array(element, *length),
+      Type::Object(object_type) => visitor.visit_object(object_type),
+      Type::Stub(stub_type) => visitor.visit_stub(stub_type),
+      Type::Signature(signature_type) => visitor.visit_signature(signature_type),
+      Type::Variable(type_variable) => visitor.visit_variable(type_variable),
+      Type::Generic(generic_type) => visitor.visit_generic(generic_type),
+      Type::Typeof(typeof_) => visitor.visit_typeof(typeof_),
+      Type::Unit => visitor.visit_unit(),
+    }
+  }
+
+  /// Count the type variables within this type's tree, including the type
+  /// itself.
+  ///
+  /// Repeated occurrences of the same type variable are counted separately.
+  pub(crate) fn count_type_variables(&self) -> usize {
+    std::iter::once(self)
+      .chain(self.get_immediate_subtree_iter())
+      .filter(|ty| matches!(ty, Type::Variable(..)))
+      .count()
+  }
+
   pub(crate) fn get_immediate_subtree_iter(&self) -> ImmediateSubtreeIterator<'_> {
     ImmediateSubtreeIterator::new(self)
   }
@@ -458,6 +855,92 @@ impl Type {
     matches!(self, Type::Unit)
   }
 
+  /// Determine whether the type is the unit type, or any of its synonyms.
+  ///
+  /// Unlike `is_a_unit`, this also considers the empty tuple `()` to be
+  /// unit. This determination will not perform flattening.
+  pub(crate) fn is_unit(&self) -> bool {
+    match self {
+      Type::Unit => true,
+      Type::Tuple(tuple) => tuple.is_unit_singleton(),
+      _ => false,
+    }
+  }
+
+  /// Determine whether the type is a zero-sized type, which requires no
+  /// stack or heap space.
+  ///
+  /// Zero-sized types are the unit type, the empty tuple, and closed objects
+  /// without any fields. This determination will not perform flattening.
+  pub(crate) fn is_zst(&self) -> bool {
+    match self {
+      Type::Unit => true,
+      Type::Tuple(TupleType(element_types)) => element_types.is_empty(),
+      Type::Object(ObjectType { fields, kind }) => {
+        // NOTE: Open objects may still gain fields during unification.
+        fields.is_empty() && *kind == ObjectKind::Closed
+      }
+      _ => false,
+    }
+  }
+
+  /// Determine whether the type has a known size, and thus a layout.
+  ///
+  /// Ranges only constrain integer types, and have no layout of their own.
+  /// Stub types are not followed, thus they are assumed to be sized.
+  pub(crate) fn is_sized(&self) -> bool {
+    match self {
+      Type::Range(..) => false,
+      // Pointer-like types are always sized, regardless of their pointee.
+      Type::Pointer(..) | Type::Reference(..) | Type::Signature(..) => true,
+      _ => self
+        .get_inner_types()
+        .all(|inner_type| inner_type.is_sized()),
+    }
+  }
+
+  /// Determine whether the type matches the given shallow pattern.
+  ///
+  /// This determination will not perform flattening, thus stub types must
+  /// be stripped beforehand.
+  pub(crate) fn matches_pattern(&self, pattern: &TypePattern) -> bool {
+    match pattern {
+      TypePattern::AnySignedInteger => {
+        matches!(self, Type::Primitive(PrimitiveType::Integer(_, true)))
+      }
+      TypePattern::AnyUnsignedInteger => {
+        matches!(self, Type::Primitive(PrimitiveType::Integer(_, false)))
+      }
+      TypePattern::AnyReal => matches!(self, Type::Primitive(PrimitiveType::Real(..))),
+      TypePattern::AnyOf(patterns) => patterns.iter().any(|pattern| self.matches_pattern(pattern)),
+    }
+  }
+
+  /// Determine whether the type is a pointer to a signature type, as
+  /// opposed to a pointer to data.
+  ///
+  /// This determination will not perform flattening.
+  pub(crate) fn is_function_pointer(&self) -> bool {
+    matches!(self, Type::Pointer(pointee) if matches!(pointee.as_ref(), Type::Signature(..)))
+  }
+
+  /// Determine whether the type is a reference type.
+  ///
+  /// This determination will not perform flattening.
+  pub(crate) fn is_reference(&self) -> bool {
+    matches!(self, Type::Reference(..))
+  }
+
+  /// Strip a single reference layer, yielding the referent type.
+  ///
+  /// Returns `None` if the type is not a reference.
+  pub(crate) fn peel_reference(&self) -> Option<&Type> {
+    match self {
+      Type::Reference(referent) => Some(referent.as_ref()),
+      _ => None,
+    }
+  }
+
   /// Determine whether the type is a generic type.
   ///
   /// Generic types are types that are polymorphic. In other words,
@@ -467,13 +950,342 @@ impl Type {
     matches!(self, Type::Generic(..))
   }
 
+  /// Determine whether the type, or any type within its subtree, is a
+  /// stub type.
+  ///
+  /// Stub types are not followed, thus only the immediate subtree is
+  /// considered.
+  pub(crate) fn has_stub_type(&self) -> bool {
+    matches!(self, Type::Stub(..))
+      || self
+        .get_immediate_subtree_iter()
+        .any(|ty| matches!(ty, Type::Stub(..)))
+  }
+
   pub fn is_a_meta(&self) -> bool {
     matches!(
       self,
-      Type::Generic(..) | Type::Stub(..) | Type::Variable(..)
+      Type::Generic(..) | Type::Stub(..) | Type::Variable(..) | Type::Typeof(..)
     )
   }
 
+  /// Retrieve this type if it is concrete, or `None` if it is a meta type
+  /// (ie. a type variable or a stub type).
+  ///
+  /// Only the immediate level is considered.
+  pub(crate) fn as_concrete(&self) -> Option<&Type> {
+    if self.is_a_meta() {
+      None
+    } else {
+      Some(self)
+    }
+  }
+
+  /// Extract the union from a union type.
+  ///
+  /// Produces a diagnostic instead of panicking if the type is not a union,
+  /// since it may be the result of user input.
+  pub(crate) fn try_as_union(&self) -> Result<&std::rc::Rc<ast::Union>, diagnostic::Diagnostic> {
+    match self {
+      Type::Union(union) => Ok(union),
+      _ => Err(diagnostic::Diagnostic::ExpectedUnionType(self.to_owned())),
+    }
+  }
+
+  /// Retrieve the type of a named field of an object type, or the payload
+  /// type of a named variant of a union type.
+  pub(crate) fn project_field(&self, field_name: &str) -> Option<&Type> {
+    match self {
+      Type::Object(object_type) => object_type.field_type(field_name),
+      Type::Union(union) => match &union.variants.get(field_name)?.kind {
+        ast::UnionVariantKind::Type(payload_type) => Some(payload_type),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /// Follow a chain of field accesses on nested object types.
+  ///
+  /// This is intended for resolved (concrete) types, since stub types and
+  /// other meta types are not resolved along the way. Returns `None` if any
+  /// of the fields along the path do not exist, or if an intermediate type is
+  /// not an object.
+  pub(crate) fn resolve_field_path(&self, path: &[String]) -> Option<Type> {
+    let mut current = self;
+
+    for field_name in path {
+      current = match current {
+        Type::Object(object_type) => object_type.field_type(field_name)?,
+        _ => return None,
+      };
+    }
+
+    Some(current.to_owned())
+  }
+
+  /// Retrieve the return type of this type, if it is a signature type.
+  pub(crate) fn as_return_type(&self) -> Option<&Type> {
+    match self {
+      Type::Signature(signature) => Some(signature.return_type.as_ref()),
+      _ => None,
+    }
+  }
+
+  /// Retrieve the parameter types of this type, if it is a signature type.
+  pub(crate) fn as_parameter_types(&self) -> Option<&[Type]> {
+    match self {
+      Type::Signature(signature) => Some(signature.parameter_types.as_slice()),
+      _ => None,
+    }
+  }
+
+  /// Compare two types for equality, without requiring a symbol table.
+  ///
+  /// Returns `None` if either type contains meta types (such as stubs, generics
+  /// or type variables), in which case a comparison which is dependent on the
+  /// symbol table (ie. through unification) is required instead.
+  pub(crate) fn concrete_eq(&self, other: &Type) -> Option<bool> {
+    fn all_concrete_eq<'a>(pairs: impl Iterator<Item = (&'a Type, &'a Type)>) -> Option<bool> {
+      let mut result = Some(true);
+
+      for (type_a, type_b) in pairs {
+        match type_a.concrete_eq(type_b) {
+          // A single mismatch is conclusive, regardless of any meta types.
+          Some(false) => return Some(false),
+          None => result = None,
+          Some(true) => {}
+        }
+      }
+
+      result
+    }
+
+    match (self, other) {
+      _ if self.is_a_meta() || other.is_a_meta() => None,
+      (Type::Primitive(primitive_a), Type::Primitive(primitive_b)) => {
+        Some(primitive_a == primitive_b)
+      }
+      (Type::Range(start_a, end_a), Type::Range(start_b, end_b)) => {
+        Some(start_a == start_b && end_a == end_b)
+      }
+      (Type::Union(union_a), Type::Union(union_b)) => {
+        Some(union_a.registry_id == union_b.registry_id)
+      }
+      (Type::Pointer(pointee_a), Type::Pointer(pointee_b))
+      | (Type::Reference(pointee_a), Type::Reference(pointee_b)) => {
+        pointee_a.concrete_eq(pointee_b)
+      }
+      (Type::Tuple(tuple_a), Type::Tuple(tuple_b)) => {
+        if tuple_a.len() != tuple_b.len() {
+          return Some(false);
+        }
+
+        all_concrete_eq(tuple_a.iter().zip(tuple_b.iter()))
+      }
+      (
+        Type::Array {
+          element: element_a,
+          length: length_a,
+        },
+        Type::Array {
+          element: element_b,
+          length: length_b,
+        },
+      ) => {
+        if length_a != length_b {
+          return Some(false);
+        }
+
+        element_a.concrete_eq(element_b)
+      }
+      (Type::Signature(signature_a), Type::Signature(signature_b)) => {
+        if signature_a.arity_mode != signature_b.arity_mode
+          || signature_a.parameter_types.len() != signature_b.parameter_types.len()
+        {
+          return Some(false);
+        }
+
+        all_concrete_eq(
+          signature_a
+            .parameter_types
+            .iter()
+            .zip(signature_b.parameter_types.iter())
+            .chain(std::iter::once((
+              signature_a.return_type.as_ref(),
+              signature_b.return_type.as_ref(),
+            ))),
+        )
+      }
+      (Type::Object(object_a), Type::Object(object_b)) => {
+        // Open objects may still gain fields during unification.
+        if object_a.kind != ObjectKind::Closed || object_b.kind != ObjectKind::Closed {
+          return None;
+        } else if object_a.fields.len() != object_b.fields.len()
+          || object_a
+            .fields
+            .keys()
+            .any(|field_name| !object_b.fields.contains_key(field_name))
+        {
+          return Some(false);
+        }
+
+        all_concrete_eq(
+          object_a
+            .fields
+            .iter()
+            .map(|(field_name, field_type)| (field_type, &object_b.fields[field_name])),
+        )
+      }
+      (Type::Opaque, Type::Opaque) | (Type::Unit, Type::Unit) => Some(true),
+      _ => Some(false),
+    }
+  }
+
+  /// Determine whether two types are structurally identical, without
+  /// requiring a symbol table.
+  ///
+  /// Unlike `concrete_eq`, meta types are compared by identity (ie. a type
+  /// variable is only identical to itself). This is conservative; types that
+  /// would otherwise unify may still not be considered identical.
+  pub(crate) fn is_structurally_identical_to(&self, other: &Type) -> bool {
+    fn all_identical(types_a: &[Type], types_b: &[Type]) -> bool {
+      types_a.len() == types_b.len()
+        && types_a
+          .iter()
+          .zip(types_b)
+          .all(|(type_a, type_b)| type_a.is_structurally_identical_to(type_b))
+    }
+
+    match (self, other) {
+      (Type::Variable(type_variable_a), Type::Variable(type_variable_b)) => {
+        type_variable_a.substitution_id == type_variable_b.substitution_id
+      }
+      (Type::Generic(generic_a), Type::Generic(generic_b)) => {
+        generic_a.substitution_id == generic_b.substitution_id
+      }
+      (Type::Stub(stub_type_a), Type::Stub(stub_type_b)) => {
+        stub_type_a.universe_id == stub_type_b.universe_id
+          && stub_type_a.path.link_id == stub_type_b.path.link_id
+          && all_identical(&stub_type_a.generic_hints, &stub_type_b.generic_hints)
+      }
+      (Type::Typeof(typeof_a), Type::Typeof(typeof_b)) => typeof_a.type_id == typeof_b.type_id,
+      (Type::Primitive(primitive_a), Type::Primitive(primitive_b)) => primitive_a == primitive_b,
+      (Type::Range(start_a, end_a), Type::Range(start_b, end_b)) => {
+        start_a == start_b && end_a == end_b
+      }
+      (Type::Union(union_a), Type::Union(union_b)) => union_a.registry_id == union_b.registry_id,
+      (Type::Pointer(pointee_a), Type::Pointer(pointee_b))
+      | (Type::Reference(pointee_a), Type::Reference(pointee_b)) => {
+        pointee_a.is_structurally_identical_to(pointee_b)
+      }
+      (Type::Tuple(tuple_a), Type::Tuple(tuple_b)) => all_identical(&tuple_a.0, &tuple_b.0),
+      (
+        Type::Array {
+          element: element_a,
+          length: length_a,
+        },
+        Type::Array {
+          element: element_b,
+          length: length_b,
+        },
+      ) => length_a == length_b && element_a.is_structurally_identical_to(element_b),
+      (Type::Signature(signature_a), Type::Signature(signature_b)) => {
+        signature_a.arity_mode == signature_b.arity_mode
+          && all_identical(&signature_a.parameter_types, &signature_b.parameter_types)
+          && signature_a
+            .return_type
+            .is_structurally_identical_to(&signature_b.return_type)
+      }
+      (Type::Object(object_a), Type::Object(object_b)) => {
+        object_a.kind == object_b.kind
+          && object_a.fields.len() == object_b.fields.len()
+          && object_a.fields.iter().zip(&object_b.fields).all(
+            |((field_name_a, field_type_a), (field_name_b, field_type_b))| {
+              field_name_a == field_name_b
+                && field_type_a.is_structurally_identical_to(field_type_b)
+            },
+          )
+      }
+      (Type::Opaque, Type::Opaque) | (Type::Unit, Type::Unit) => true,
+      _ => false,
+    }
+  }
+
+  /// Hash this type by its structure, consistently with
+  /// [`Type::is_structurally_identical_to`].
+  ///
+  /// Types that are structurally identical produce the same hash. Like
+  /// structural identity, meta types are hashed by their ids, and are not
+  /// resolved.
+  pub(crate) fn structural_hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    use std::hash::Hash;
+
+    fn hash_all<H: std::hash::Hasher>(types: &[Type], state: &mut H) {
+      types.len().hash(state);
+
+      for ty in types {
+        ty.structural_hash(state);
+      }
+    }
+
+    std::mem::discriminant(self).hash(state);
+
+    match self {
+      Type::Variable(type_variable) => type_variable.substitution_id.hash(state),
+      Type::Generic(generic) => generic.substitution_id.hash(state),
+      Type::Stub(stub_type) => {
+        stub_type.universe_id.hash(state);
+        stub_type.path.link_id.hash(state);
+        hash_all(&stub_type.generic_hints, state);
+      }
+      Type::Typeof(typeof_) => typeof_.type_id.hash(state),
+      Type::Primitive(primitive) => primitive.hash(state),
+      Type::Range(start, end) => {
+        start.hash(state);
+        end.hash(state);
+      }
+      Type::Union(union) => union.registry_id.hash(state),
+      Type::Pointer(pointee) | Type::Reference(pointee) => pointee.structural_hash(state),
+      Type::Tuple(tuple) => hash_all(&tuple.0, state),
+      Type::Array { element, length } => {
+        length.hash(state);
+        element.structural_hash(state);
+      }
+      Type::Signature(signature) => {
+        signature.arity_mode.hash(state);
+        hash_all(&signature.parameter_types, state);
+        signature.return_type.structural_hash(state);
+      }
+      Type::Object(object) => {
+        object.kind.hash(state);
+        object.fields.len().hash(state);
+
+        // NOTE: Fields are stored in a sorted map, thus they are always
+        // hashed in the order of their names.
+        for (field_name, field_type) in &object.fields {
+          field_name.hash(state);
+          field_type.structural_hash(state);
+        }
+      }
+      Type::Opaque | Type::Unit => {}
+    }
+  }
+
+  /// Compare only the outermost layer of two types, without considering
+  /// their inner types.
+  ///
+  /// Meta types are considered equal to any other type, since they can only
+  /// be properly compared after unification. This is useful for detecting
+  /// obvious mismatches early, during inference.
+  pub(crate) fn shallow_equal(&self, other: &Type) -> bool {
+    match (self, other) {
+      _ if self.is_a_meta() || other.is_a_meta() => true,
+      (Type::Primitive(primitive_a), Type::Primitive(primitive_b)) => primitive_a == primitive_b,
+      _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+    }
+  }
+
   /// A concrete type is any type that is not a meta type (ex. generic,
   /// stub, type variable, etc.) and whose entire inner type subtree is
   /// also concrete.
@@ -484,27 +1296,947 @@ impl Type {
     // This is because that same stub type could resolve to a non-concrete type, such
     // as a generic. Instead, this function's purpose focuses to ensure that a given
     // type is FULLY concrete and simplified.
-    !self.is_a_meta() && self.get_immediate_subtree_iter().all(|ty| !ty.is_a_meta())
+    self.as_concrete().is_some()
+      && self
+        .get_immediate_subtree_iter()
+        .all(|ty| ty.as_concrete().is_some())
+  }
+
+  /// Transform the type by applying the given function bottom-up on every
+  /// node of its tree, including the type itself.
+  ///
+  /// Inner types are folded before the type containing them, thus the
+  /// function always receives a type whose inner types have already been
+  /// transformed. Stub types are not followed, but their generic hints are
+  /// folded.
+  pub(crate) fn fold<E, F: Fn(Type) -> Result<Type, E>>(self, f: &F) -> Result<Type, E> {
+    let fold_all = |types: Vec<Type>| {
+      types
+        .into_iter()
+        .map(|ty| ty.fold(f))
+        .collect::<Result<Vec<_>, E>>()
+    };
+
+    let folded = match self {
+      Type::Pointer(pointee) => Type::Pointer(Box::new(pointee.fold(f)?)),
+      Type::Reference(pointee) => Type::Reference(Box::new(pointee.fold(f)?)),
+      Type::Array { element, length } => Type::Array {
+        element: Box::new(element.fold(f)?),
+        length,
+      },
+      Type::Tuple(TupleType(element_types)) => Type::Tuple(TupleType(fold_all(element_types)?)),
+      Type::Object(ObjectType { fields, kind }) => Type::Object(ObjectType {
+        fields: fields
+          .into_iter()
+          .map(|(name, field_type)| Ok((name, field_type.fold(f)?)))
+          .collect::<Result<_, E>>()?,
+        kind,
+      }),
+      Type::Signature(signature_type) => {
+        let SignatureType {
+          return_type,
+          parameter_types,
+          arity_mode,
+        } = *signature_type;
+
+        Type::Signature(Box::new(SignatureType {
+          return_type: Box::new(return_type.fold(f)?),
+          parameter_types: fold_all(parameter_types)?,
+          arity_mode,
+        }))
+      }
+      Type::Stub(stub_type) => {
+        let StubType {
+          universe_id,
+          path,
+          generic_hints,
+        } = *stub_type;
+
+        Type::Stub(Box::new(StubType {
+          universe_id,
+          path,
+          generic_hints: fold_all(generic_hints)?,
+        }))
+      }
+      // NOTE: Unions are nominal, thus the types of their variants are not
+      // part of their structure.
+      leaf => leaf,
+    };
+
+    f(folded)
   }
 
   pub(crate) fn get_inner_types(&self) -> Box<dyn Iterator<Item = &Type> + '_> {
     match self {
       Type::Pointer(pointee) => Box::new(std::iter::once(pointee.as_ref())),
       Type::Object(object) => Box::new(object.fields.iter().map(|field| field.1)),
-      Type::Tuple(TupleType(element_types)) => Box::new(element_types.iter()),
+      Type::Tuple(tuple_type) => Box::new(tuple_type.iter()),
+      Type::Array { element, .. } => Box::new(std::iter::once(element.as_ref())),
       Type::Reference(pointee) => Box::new(std::iter::once(pointee.as_ref())),
       Type::Signature(signature) => Box::new(signature.parameter_types.iter()),
-      // TODO: Handle unions case.
-      Type::Union(union_) => todo!(),
+      // NOTE: Unions are nominal; they are identified by their declaration,
+      // thus the types of their variants are not part of their structure.
+      Type::Union(..) => Box::new(std::iter::empty()),
       _ => Box::new(std::iter::empty()),
     }
   }
 
+  /// Attempt to re-type a numeric literal into the expected type.
+  ///
+  /// This will only succeed if the literal's value can be represented by
+  /// the expected type without any loss, otherwise `None` is returned. The
+  /// literal type is the type that the literal would have by default, had
+  /// there been no context for it.
+  pub(crate) fn coerce_literal(
+    literal_type: &Type,
+    literal_value: f64,
+    expected: &Type,
+  ) -> Option<Type> {
+    let (literal_primitive, expected_primitive) = match (literal_type, expected) {
+      (Type::Primitive(literal_primitive), Type::Primitive(expected_primitive)) => {
+        (literal_primitive, expected_primitive)
+      }
+      _ => return None,
+    };
+
+    // The literal's default type is always valid.
+    if literal_primitive == expected_primitive {
+      return Some(expected.to_owned());
+    }
+
+    // NOTE: Literal values are always positive, since negation is applied
+    // as a separate unary operation.
+    let fits = match (literal_primitive, expected_primitive) {
+      (PrimitiveType::Integer(..), PrimitiveType::Integer(bit_width, is_signed)) => {
+        let value_bits = if *is_signed {
+          *bit_width as i32 - 1
+        } else {
+          *bit_width as i32
+        };
+
+        literal_value < 2_f64.powi(value_bits)
+      }
+      (PrimitiveType::Real(literal_bit_width), PrimitiveType::Real(expected_bit_width)) => {
+        expected_bit_width >= literal_bit_width
+      }
+      // Integer literals are promoted into real numbers, as long as the
+      // real type's significand can represent the value exactly.
+      (PrimitiveType::Integer(..), PrimitiveType::Real(bit_width)) => {
+        let significand_bits = match bit_width {
+          BitWidth::Width16 => 11,
+          BitWidth::Width32 => 24,
+          BitWidth::Width64 => 53,
+          _ => return None,
+        };
+
+        literal_value <= 2_f64.powi(significand_bits)
+      }
+      // NOTE: Real literals are never demoted into integers, since that
+      // would discard their fractional component.
+      _ => false,
+    };
+
+    if fits {
+      Some(expected.to_owned())
+    } else {
+      None
+    }
+  }
+
+  /// Determine the type that both given integer types promote to when used
+  /// together in an arithmetic operation.
+  ///
+  /// If both types share the same signedness, the widest of the two is used.
+  /// Otherwise, the result is signed, and if the unsigned type is at least as
+  /// wide as the signed type, it is widened to the next bit width so that all
+  /// of its values can be represented.
+  ///
+  /// Returns `None` if either type is not an integer, or if no wide enough
+  /// integer type is available.
+  pub(crate) fn widen_integer(a: &PrimitiveType, b: &PrimitiveType) -> Option<PrimitiveType> {
+    let (
+      PrimitiveType::Integer(bit_width_a, is_signed_a),
+      PrimitiveType::Integer(bit_width_b, is_signed_b),
+    ) = (a, b)
+    else {
+      return None;
+    };
+
+    if is_signed_a == is_signed_b {
+      let widest_bit_width = if bit_width_a >= bit_width_b {
+        bit_width_a
+      } else {
+        bit_width_b
+      };
+
+      return Some(PrimitiveType::Integer(*widest_bit_width, *is_signed_a));
+    }
+
+    let (signed_bit_width, unsigned_bit_width) = if *is_signed_a {
+      (*bit_width_a, *bit_width_b)
+    } else {
+      (*bit_width_b, *bit_width_a)
+    };
+
+    if signed_bit_width > unsigned_bit_width {
+      return Some(PrimitiveType::Integer(signed_bit_width, true));
+    }
+
+    // NOTE: 128-bit integers are not yet supported, thus 64-bit unsigned
+    // integers cannot be promoted.
+    let next_bit_width = match unsigned_bit_width {
+      BitWidth::Width1 => BitWidth::Width8,
+      BitWidth::Width8 => BitWidth::Width16,
+      BitWidth::Width16 => BitWidth::Width32,
+      BitWidth::Width32 => BitWidth::Width64,
+      BitWidth::Width64 | BitWidth::Width128 => return None,
+    };
+
+    Some(PrimitiveType::Integer(next_bit_width, true))
+  }
+
+  /// Determine whether a value of the source type may be implicitly coerced
+  /// into the target type, without any loss.
+  ///
+  /// Applicable coercions are integer widening (see `widen_integer`), real
+  /// widening, and integer to real conversion.
+  pub(crate) fn is_coercible_to(source: &Type, target: &Type) -> bool {
+    match (source, target) {
+      // An integer may be coerced into the target integer type if that is
+      // what both of them promote to.
+      (
+        Type::Primitive(source_primitive @ PrimitiveType::Integer(..)),
+        Type::Primitive(target_primitive @ PrimitiveType::Integer(..)),
+      ) => {
+        Type::widen_integer(source_primitive, target_primitive).as_ref() == Some(target_primitive)
+      }
+      (
+        Type::Primitive(PrimitiveType::Real(source_bit_width)),
+        Type::Primitive(PrimitiveType::Real(target_bit_width)),
+      ) => target_bit_width >= source_bit_width,
+      (Type::Primitive(PrimitiveType::Integer(..)), Type::Primitive(PrimitiveType::Real(..))) => {
+        true
+      }
+      _ => false,
+    }
+  }
+
+  /// Determine the type that a value of this type is promoted to when passed
+  /// as a variadic argument, following C's default argument promotions.
+  ///
+  /// Integers narrower than 32 bits are promoted to 32-bit integers of the
+  /// same signedness, booleans and characters are promoted to signed 32-bit
+  /// integers (C's `int`), and 32-bit (or narrower) reals are promoted to
+  /// 64-bit reals. Returns `None` if no promotion takes place.
+  pub(crate) fn promote_variadic(&self) -> Option<Type> {
+    match self {
+      Type::Primitive(PrimitiveType::Integer(bit_width, is_signed))
+        if *bit_width < BitWidth::Width32 =>
+      {
+        Some(Type::Primitive(PrimitiveType::Integer(
+          BitWidth::Width32,
+          *is_signed,
+        )))
+      }
+      Type::Primitive(PrimitiveType::Bool | PrimitiveType::Char) => Some(Type::Primitive(
+        PrimitiveType::Integer(BitWidth::Width32, true),
+      )),
+      Type::Primitive(PrimitiveType::Real(bit_width)) if *bit_width < BitWidth::Width64 => {
+        Some(Type::Primitive(PrimitiveType::Real(BitWidth::Width64)))
+      }
+      _ => None,
+    }
+  }
+
   // CONSIDER: Add a `find_substitution_id` helper function (or trait) that will perform abstract operations on substitute-able types, such as type variables and `typeof` types. For example, it would re-perform the unification operation with its substitution if it is bound, and also perform occurs checks. This would standardize the process of substitution.
 }
 
+impl std::hash::Hash for Type {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    self.structural_hash(state);
+  }
+}
+
+/// Types are compared structurally, consistent with their hash. Whether
+/// two types are semantically equal should instead be determined through
+/// `Type::concrete_eq` or unification.
+impl PartialEq for Type {
+  fn eq(&self, other: &Self) -> bool {
+    self.is_structurally_identical_to(other)
+  }
+}
+
+impl Eq for Type {}
+
 impl From<SignatureType> for Type {
   fn from(signature_type: SignatureType) -> Self {
-    Type::Signature(signature_type)
+    Type::Signature(Box::new(signature_type))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  const DEFAULT_INTEGER_TYPE: Type =
+    Type::Primitive(PrimitiveType::Integer(BitWidth::Width32, true));
+
+  #[test]
+  fn coerce_literal_to_narrower_integer() {
+    let expected = Type::Primitive(PrimitiveType::Integer(BitWidth::Width8, true));
+
+    assert!(matches!(
+      Type::coerce_literal(&DEFAULT_INTEGER_TYPE, 5_f64, &expected),
+      Some(Type::Primitive(PrimitiveType::Integer(
+        BitWidth::Width8,
+        true
+      )))
+    ));
+
+    assert!(Type::coerce_literal(&DEFAULT_INTEGER_TYPE, 128_f64, &expected).is_none());
+  }
+
+  #[test]
+  fn coerce_literal_to_real() {
+    let real64_type = Type::Primitive(PrimitiveType::Real(BitWidth::Width64));
+    let real32_type = Type::Primitive(PrimitiveType::Real(BitWidth::Width32));
+
+    assert!(matches!(
+      Type::coerce_literal(&DEFAULT_INTEGER_TYPE, 5_f64, &real64_type),
+      Some(Type::Primitive(PrimitiveType::Real(BitWidth::Width64)))
+    ));
+
+    assert!(Type::coerce_literal(&DEFAULT_INTEGER_TYPE, 2_f64.powi(25), &real32_type).is_none());
+    assert!(Type::coerce_literal(&real64_type, 5_f64, &real32_type).is_none());
+    assert!(Type::coerce_literal(&real64_type, 5_f64, &DEFAULT_INTEGER_TYPE).is_none());
+  }
+
+  #[test]
+  fn concrete_eq() {
+    let int_type = DEFAULT_INTEGER_TYPE;
+    let bool_type = Type::Primitive(PrimitiveType::Bool);
+
+    assert_eq!(Some(true), int_type.concrete_eq(&DEFAULT_INTEGER_TYPE));
+    assert_eq!(Some(false), int_type.concrete_eq(&bool_type));
+
+    assert_eq!(
+      Some(false),
+      int_type
+        .clone()
+        .into_pointer_type()
+        .concrete_eq(&bool_type.into_pointer_type())
+    );
+  }
+
+  fn mock_stub_type(link_id: symbol_table::LinkId, name: &str) -> StubType {
+    StubType {
+      universe_id: symbol_table::UniverseId(link_id.0, name.to_string()),
+      path: ast::Path {
+        link_id,
+        qualifier: None,
+        base_name: name.to_string(),
+        sub_name: None,
+        symbol_kind: symbol_table::SymbolKind::Type,
+      },
+      generic_hints: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn concrete_eq_stub_fallback() {
+    let stub_type = Type::Stub(Box::new(mock_stub_type(symbol_table::LinkId(0), "Test")));
+
+    assert_eq!(None, DEFAULT_INTEGER_TYPE.concrete_eq(&stub_type));
+
+    assert_eq!(
+      None,
+      DEFAULT_INTEGER_TYPE
+        .into_pointer_type()
+        .concrete_eq(&stub_type.into_pointer_type())
+    );
+  }
+
+  #[test]
+  fn strip_all_monomorphic_stub_layers_traced() {
+    let mut symbol_table = symbol_table::SymbolTable::default();
+    let type_def_a_id = symbol_table::RegistryId(0);
+    let type_def_b_id = symbol_table::RegistryId(1);
+    let link_a_id = symbol_table::LinkId(0);
+    let link_b_id = symbol_table::LinkId(1);
+
+    // type A = int
+    // type B = A
+    for (registry_id, name, body) in [
+      (type_def_a_id, "A", DEFAULT_INTEGER_TYPE),
+      (
+        type_def_b_id,
+        "B",
+        Type::Stub(Box::new(mock_stub_type(link_a_id, "A"))),
+      ),
+    ] {
+      symbol_table.registry.insert(
+        registry_id,
+        symbol_table::RegistryItem::TypeDef(std::rc::Rc::new(ast::TypeDef {
+          registry_id,
+          name: name.to_string(),
+          body,
+          generics: ast::Generics {
+            parameters: Vec::new(),
+          },
+        })),
+      );
+    }
+
+    symbol_table.links.insert(link_a_id, type_def_a_id);
+    symbol_table.links.insert(link_b_id, type_def_b_id);
+
+    let (stripped, registry_id) = mock_stub_type(link_b_id, "B")
+      .strip_all_monomorphic_stub_layers_traced(&symbol_table)
+      .unwrap();
+
+    assert!(matches!(
+      stripped,
+      Type::Primitive(PrimitiveType::Integer(..))
+    ));
+
+    assert_eq!(type_def_a_id, registry_id);
+  }
+
+  #[test]
+  fn resolve_field_path() {
+    let inner_object_type = Type::Object(ObjectType {
+      fields: ObjectFieldMap::from([(String::from("b"), DEFAULT_INTEGER_TYPE)]),
+      kind: ObjectKind::Closed,
+    });
+
+    let outer_object_type = Type::Object(ObjectType {
+      fields: ObjectFieldMap::from([(String::from("a"), inner_object_type)]),
+      kind: ObjectKind::Closed,
+    });
+
+    assert!(matches!(
+      outer_object_type.resolve_field_path(&[String::from("a"), String::from("b")]),
+      Some(Type::Primitive(PrimitiveType::Integer(..)))
+    ));
+
+    assert!(outer_object_type
+      .resolve_field_path(&[String::from("c"), String::from("b")])
+      .is_none());
+  }
+
+  #[test]
+  fn project_field() {
+    let object_type = Type::Object(ObjectType {
+      fields: ObjectFieldMap::from([(String::from("a"), Type::Unit)]),
+      kind: ObjectKind::Closed,
+    });
+
+    assert!(matches!(object_type.project_field("a"), Some(Type::Unit)));
+    assert!(object_type.project_field("b").is_none());
+    assert!(DEFAULT_INTEGER_TYPE.project_field("a").is_none());
+  }
+
+  #[test]
+  fn count_type_variables() {
+    let type_variable = Type::Variable(TypeVariable {
+      substitution_id: symbol_table::SubstitutionId(0),
+      debug_name: "test",
+    });
+
+    let tuple_type = Type::Tuple(TupleType(vec![
+      type_variable.clone(),
+      DEFAULT_INTEGER_TYPE,
+      Type::Pointer(Box::new(type_variable.clone())),
+    ]));
+
+    assert_eq!(0, DEFAULT_INTEGER_TYPE.count_type_variables());
+    assert_eq!(1, type_variable.count_type_variables());
+    assert_eq!(2, tuple_type.count_type_variables());
+  }
+
+  #[test]
+  fn object_type_merge() {
+    let bool_type = Type::Primitive(PrimitiveType::Bool);
+
+    let open_object_type = ObjectType {
+      fields: ObjectFieldMap::from([(String::from("a"), DEFAULT_INTEGER_TYPE)]),
+      kind: ObjectKind::Open(symbol_table::SubstitutionId(0)),
+    };
+
+    let closed_object_type = ObjectType {
+      fields: ObjectFieldMap::from([
+        (String::from("a"), DEFAULT_INTEGER_TYPE),
+        (String::from("b"), bool_type.clone()),
+      ]),
+      kind: ObjectKind::Closed,
+    };
+
+    let merged_object_type = ObjectType::merge(&open_object_type, &closed_object_type).unwrap();
+
+    assert_eq!(ObjectKind::Closed, merged_object_type.kind);
+    assert_eq!(2, merged_object_type.fields.len());
+
+    let conflicting_object_type = ObjectType {
+      fields: ObjectFieldMap::from([(String::from("a"), bool_type)]),
+      kind: ObjectKind::Closed,
+    };
+
+    assert!(matches!(
+      ObjectType::merge(&open_object_type, &conflicting_object_type),
+      Err(ObjectMergeError::FieldTypeConflict { field_name, .. }) if field_name == "a"
+    ));
+
+    assert!(matches!(
+      ObjectType::merge(&closed_object_type, &open_object_type),
+      Err(ObjectMergeError::UnknownField(field_name)) if field_name == "b"
+    ));
+  }
+
+  #[test]
+  fn type_visitor() {
+    struct PointerIndirectionCounter(usize);
+
+    impl TypeVisitor for PointerIndirectionCounter {
+      fn visit_pointer(&mut self, pointee: &Type) {
+        self.0 += 1;
+        pointee.accept(self);
+      }
+    }
+
+    let tuple_type = Type::Tuple(TupleType(vec![
+      DEFAULT_INTEGER_TYPE.into_pointer_type().into_pointer_type(),
+      Type::Signature(Box::new(SignatureType {
+        return_type: Box::new(Type::Unit.into_pointer_type()),
+        parameter_types: vec![DEFAULT_INTEGER_TYPE],
+        arity_mode: ArityMode::Fixed,
+      })),
+    ]));
+
+    let mut counter = PointerIndirectionCounter(0);
+
+    tuple_type.accept(&mut counter);
+    assert_eq!(3, counter.0);
+  }
+
+  #[test]
+  fn try_as_union() {
+    let union_type = Type::Union(std::rc::Rc::new(ast::Union {
+      registry_id: symbol_table::RegistryId(0),
+      name: String::from("a"),
+      variants: std::collections::BTreeMap::new(),
+    }));
+
+    assert_eq!("a", union_type.try_as_union().unwrap().name);
+
+    assert!(matches!(
+      DEFAULT_INTEGER_TYPE.try_as_union(),
+      Err(diagnostic::Diagnostic::ExpectedUnionType(Type::Primitive(
+        ..
+      )))
+    ));
+  }
+
+  #[test]
+  fn as_return_and_parameter_types() {
+    let signature_type = Type::Signature(Box::new(SignatureType {
+      return_type: Box::new(Type::Unit),
+      parameter_types: vec![DEFAULT_INTEGER_TYPE],
+      arity_mode: ArityMode::Fixed,
+    }));
+
+    assert!(matches!(signature_type.as_return_type(), Some(Type::Unit)));
+
+    assert!(matches!(
+      signature_type.as_parameter_types(),
+      Some([Type::Primitive(PrimitiveType::Integer(..))])
+    ));
+
+    assert!(DEFAULT_INTEGER_TYPE.as_return_type().is_none());
+    assert!(DEFAULT_INTEGER_TYPE.as_parameter_types().is_none());
+  }
+
+  #[test]
+  fn tuple_type_element() {
+    let tuple_type = TupleType(vec![Type::Unit, Type::Opaque]);
+
+    assert_eq!(2, tuple_type.len());
+    assert!(!tuple_type.is_empty());
+    assert!(matches!(tuple_type.element(1), Some(Type::Opaque)));
+    assert!(tuple_type.element(2).is_none());
+    assert!(TupleType(Vec::new()).element(0).is_none());
+  }
+
+  #[test]
+  fn has_stub_type() {
+    let stub_type = Type::Stub(Box::new(mock_stub_type(symbol_table::LinkId(0), "A")));
+
+    assert!(stub_type.has_stub_type());
+    assert!(
+      Type::Tuple(TupleType(vec![Type::Unit, stub_type.into_pointer_type()])).has_stub_type()
+    );
+    assert!(!Type::Tuple(TupleType(vec![Type::Unit, Type::Opaque])).has_stub_type());
+    assert!(!Type::Primitive(PrimitiveType::Bool).has_stub_type());
+  }
+
+  #[test]
+  fn is_unit() {
+    assert!(Type::Unit.is_unit());
+    assert!(Type::Tuple(TupleType(Vec::new())).is_unit());
+    assert!(!Type::Tuple(TupleType(vec![Type::Unit])).is_unit());
+    assert!(!Type::Tuple(TupleType(Vec::new())).is_a_unit());
+  }
+
+  #[test]
+  fn fold() {
+    let signature_type = Type::Signature(Box::new(SignatureType {
+      return_type: Box::new(Type::Unit),
+      parameter_types: vec![Type::Tuple(TupleType(vec![Type::Unit, Type::Opaque]))],
+      arity_mode: ArityMode::Fixed,
+    }));
+
+    let bool_type = Type::Primitive(PrimitiveType::Bool);
+
+    let folded = signature_type
+      .clone()
+      .fold(&|ty| -> Result<Type, ()> {
+        Ok(if ty.is_a_unit() {
+          bool_type.clone()
+        } else {
+          ty
+        })
+      })
+      .unwrap();
+
+    assert!(matches!(
+      folded,
+      Type::Signature(folded_signature_type)
+        if matches!(folded_signature_type.return_type.as_ref(), Type::Primitive(PrimitiveType::Bool))
+          && matches!(
+            folded_signature_type.parameter_types.as_slice(),
+            [Type::Tuple(TupleType(elements))]
+              if matches!(elements.as_slice(), [Type::Primitive(PrimitiveType::Bool), Type::Opaque])
+          )
+    ));
+
+    // Errors are propagated, and short-circuit the fold.
+    assert!(matches!(
+      signature_type.fold(&|ty| match ty {
+        Type::Opaque => Err("opaque"),
+        _ => Ok(ty),
+      }),
+      Err("opaque")
+    ));
+  }
+
+  #[test]
+  fn is_zst() {
+    assert!(Type::Unit.is_zst());
+    assert!(Type::Tuple(TupleType(Vec::new())).is_zst());
+    assert!(Type::Object(mock_object_type(&[], ObjectKind::Closed)).is_zst());
+
+    assert!(!Type::Object(mock_object_type(
+      &[],
+      ObjectKind::Open(symbol_table::SubstitutionId(0))
+    ))
+    .is_zst());
+
+    assert!(!Type::Object(mock_object_type(&["a"], ObjectKind::Closed)).is_zst());
+    assert!(!Type::Opaque.is_zst());
+  }
+
+  #[test]
+  fn matches_pattern() {
+    let nat8_type = Type::Primitive(PrimitiveType::Integer(BitWidth::Width8, false));
+    let numeric_pattern =
+      TypePattern::AnyOf(vec![TypePattern::AnyReal, TypePattern::AnySignedInteger]);
+
+    assert!(DEFAULT_INTEGER_TYPE.matches_pattern(&TypePattern::AnySignedInteger));
+    assert!(!nat8_type.matches_pattern(&TypePattern::AnySignedInteger));
+    assert!(nat8_type.matches_pattern(&TypePattern::AnyUnsignedInteger));
+    assert!(DEFAULT_INTEGER_TYPE.matches_pattern(&numeric_pattern));
+    assert!(!nat8_type.matches_pattern(&numeric_pattern));
+    assert!(!nat8_type
+      .into_pointer_type()
+      .matches_pattern(&TypePattern::AnyUnsignedInteger));
+  }
+
+  #[test]
+  fn is_function_pointer() {
+    let signature_type = Type::Signature(Box::new(SignatureType {
+      parameter_types: Vec::new(),
+      return_type: Box::new(Type::Unit),
+      arity_mode: ArityMode::Fixed,
+    }));
+
+    assert!(signature_type
+      .clone()
+      .into_pointer_type()
+      .is_function_pointer());
+    assert!(!signature_type.is_function_pointer());
+    assert!(!Type::Unit.into_pointer_type().is_function_pointer());
+  }
+
+  #[test]
+  fn peel_references() {
+    let nested_reference_type = Type::Reference(Box::new(Type::Reference(Box::new(
+      Type::Unit.into_pointer_type(),
+    ))));
+
+    assert!(nested_reference_type.is_reference());
+    assert!(!Type::Unit.is_reference());
+    assert!(Type::Unit.peel_reference().is_none());
+
+    assert!(matches!(
+      nested_reference_type.peel_reference(),
+      Some(Type::Reference(..))
+    ));
+
+    // Pointers are not references, and thus are not peeled.
+    assert!(Type::Unit.into_pointer_type().peel_reference().is_none());
+  }
+
+  #[test]
+  fn bit_width() {
+    assert!(BitWidth::Width1 < BitWidth::Width8);
+    assert!(BitWidth::Width8 < BitWidth::Width128);
+    assert_eq!(1, BitWidth::Width1.bits());
+    assert_eq!(8, BitWidth::Width8 as u32);
+    assert_eq!(1, BitWidth::Width1.size_in_bytes());
+    assert_eq!(1, BitWidth::Width8.size_in_bytes());
+    assert_eq!(16, BitWidth::Width128.size_in_bytes());
+
+    assert_eq!(Some(BitWidth::Width1), PrimitiveType::Bool.bit_width());
+
+    assert_eq!(None, PrimitiveType::CString.bit_width());
+  }
+
+  #[test]
+  fn is_higher_order() {
+    let first_order_signature_type = SignatureType {
+      return_type: Box::new(Type::Unit),
+      parameter_types: vec![DEFAULT_INTEGER_TYPE],
+      arity_mode: ArityMode::Fixed,
+    };
+
+    let higher_order_signature_type = SignatureType {
+      return_type: Box::new(Type::Unit),
+      parameter_types: vec![Type::Signature(Box::new(
+        first_order_signature_type.clone(),
+      ))],
+      arity_mode: ArityMode::Fixed,
+    };
+
+    assert!(!first_order_signature_type.is_higher_order());
+    assert!(higher_order_signature_type.is_higher_order());
+  }
+
+  #[test]
+  fn is_coercible_to() {
+    let int8_type = Type::Primitive(PrimitiveType::Integer(BitWidth::Width8, true));
+    let nat64_type = Type::Primitive(PrimitiveType::Integer(BitWidth::Width64, false));
+    let real16_type = Type::Primitive(PrimitiveType::Real(BitWidth::Width16));
+    let real64_type = Type::Primitive(PrimitiveType::Real(BitWidth::Width64));
+
+    assert!(Type::is_coercible_to(&int8_type, &DEFAULT_INTEGER_TYPE));
+    assert!(!Type::is_coercible_to(&DEFAULT_INTEGER_TYPE, &int8_type));
+    assert!(!Type::is_coercible_to(&int8_type, &nat64_type));
+    assert!(Type::is_coercible_to(&real16_type, &real64_type));
+    assert!(!Type::is_coercible_to(&real64_type, &real16_type));
+    assert!(Type::is_coercible_to(&DEFAULT_INTEGER_TYPE, &real16_type));
+    assert!(!Type::is_coercible_to(&real16_type, &DEFAULT_INTEGER_TYPE));
+
+    // Unsigned integers fit within wider signed integers.
+    assert!(Type::is_coercible_to(
+      &Type::Primitive(PrimitiveType::Integer(BitWidth::Width8, false)),
+      &Type::Primitive(PrimitiveType::Integer(BitWidth::Width16, true))
+    ));
+  }
+
+  #[test]
+  fn widen_integer() {
+    let int8 = PrimitiveType::Integer(BitWidth::Width8, true);
+    let int16 = PrimitiveType::Integer(BitWidth::Width16, true);
+    let nat8 = PrimitiveType::Integer(BitWidth::Width8, false);
+    let nat64 = PrimitiveType::Integer(BitWidth::Width64, false);
+
+    assert_eq!(Some(int16.clone()), Type::widen_integer(&int8, &int16));
+    assert_eq!(Some(int16.clone()), Type::widen_integer(&nat8, &int8));
+    assert_eq!(Some(int16.clone()), Type::widen_integer(&int16, &nat8));
+    assert_eq!(None, Type::widen_integer(&nat64, &int8));
+    assert_eq!(None, Type::widen_integer(&int8, &PrimitiveType::Bool));
+  }
+
+  #[test]
+  fn coerce_literal_to_bool() {
+    assert!(Type::coerce_literal(
+      &DEFAULT_INTEGER_TYPE,
+      5_f64,
+      &Type::Primitive(PrimitiveType::Bool)
+    )
+    .is_none());
+  }
+
+  fn mock_object_type(field_names: &[&str], kind: ObjectKind) -> ObjectType {
+    ObjectType {
+      fields: field_names
+        .iter()
+        .map(|field_name| (field_name.to_string(), Type::Unit))
+        .collect(),
+      kind,
+    }
+  }
+
+  fn field_names_of(object_type: &ObjectType) -> Vec<&str> {
+    object_type.fields.keys().map(|key| key.as_str()).collect()
+  }
+
+  #[test]
+  fn unify_object_types_closed_closed() {
+    let mut substitution_env = SubstitutionEnv::new();
+    let object_a = mock_object_type(&["a", "b"], ObjectKind::Closed);
+    let object_b = mock_object_type(&["a", "b"], ObjectKind::Closed);
+    let result = unify_object_types(&object_a, &object_b, &mut substitution_env).unwrap();
+
+    assert_eq!(vec!["a", "b"], field_names_of(&result));
+    assert_eq!(ObjectKind::Closed, result.kind);
+    assert!(substitution_env.is_empty());
+  }
+
+  #[test]
+  fn unify_object_types_open_closed() {
+    let mut substitution_env = SubstitutionEnv::new();
+    let substitution_id = symbol_table::SubstitutionId(0);
+    let open_object = mock_object_type(&["a"], ObjectKind::Open(substitution_id));
+    let closed_object = mock_object_type(&["a", "b"], ObjectKind::Closed);
+
+    let result = unify_object_types(&closed_object, &open_object, &mut substitution_env).unwrap();
+
+    assert_eq!(vec!["a", "b"], field_names_of(&result));
+    assert_eq!(ObjectKind::Closed, result.kind);
+
+    assert!(matches!(
+      substitution_env.get(&substitution_id),
+      Some(Type::Object(ObjectType {
+        kind: ObjectKind::Closed,
+        ..
+      }))
+    ));
+  }
+
+  #[test]
+  fn unify_object_types_open_open() {
+    let mut substitution_env = SubstitutionEnv::new();
+    let substitution_id_a = symbol_table::SubstitutionId(0);
+    let substitution_id_b = symbol_table::SubstitutionId(1);
+    let object_a = mock_object_type(&["a"], ObjectKind::Open(substitution_id_a));
+    let object_b = mock_object_type(&["b"], ObjectKind::Open(substitution_id_b));
+    let result = unify_object_types(&object_a, &object_b, &mut substitution_env).unwrap();
+
+    assert_eq!(vec!["a", "b"], field_names_of(&result));
+    assert_eq!(ObjectKind::Open(substitution_id_a), result.kind);
+  }
+
+  #[test]
+  fn unify_object_types_missing_field() {
+    let mut substitution_env = SubstitutionEnv::new();
+    let open_object = mock_object_type(&["c"], ObjectKind::Open(symbol_table::SubstitutionId(0)));
+    let closed_object = mock_object_type(&["a", "b"], ObjectKind::Closed);
+
+    assert!(matches!(
+      unify_object_types(&open_object, &closed_object, &mut substitution_env),
+      Err(UnificationError::Merge(ObjectMergeError::UnknownField(field_name))) if field_name == "c"
+    ));
+
+    assert!(substitution_env.is_empty());
+  }
+
+  #[test]
+  fn as_concrete() {
+    let type_variable = Type::Variable(TypeVariable {
+      substitution_id: symbol_table::SubstitutionId(0),
+      debug_name: "test",
+    });
+
+    assert!(type_variable.as_concrete().is_none());
+    assert!(Type::Pointer(Box::new(type_variable))
+      .as_concrete()
+      .is_some());
+    assert!(matches!(Type::Unit.as_concrete(), Some(Type::Unit)));
+  }
+
+  #[test]
+  fn type_size() {
+    // Stub types hold a path, which alone would otherwise make every type
+    // well over a hundred bytes in size.
+    const _: () = assert!(std::mem::size_of::<Type>() <= 48);
+  }
+
+  #[test]
+  fn structural_hash() {
+    use std::hash::{Hash, Hasher};
+
+    let hash = |ty: &Type| {
+      let mut hasher = std::collections::hash_map::DefaultHasher::new();
+
+      ty.hash(&mut hasher);
+
+      hasher.finish()
+    };
+
+    let object_type = |field_type: Type| {
+      Type::Object(ObjectType {
+        fields: ObjectFieldMap::from([(String::from("a"), field_type)]),
+        kind: ObjectKind::Closed,
+      })
+    };
+
+    assert_eq!(
+      hash(&object_type(Type::Unit.into_pointer_type())),
+      hash(&object_type(Type::Unit.into_pointer_type()))
+    );
+
+    assert_ne!(
+      hash(&object_type(Type::Unit.into_pointer_type())),
+      hash(&object_type(Type::Unit))
+    );
+  }
+
+  #[test]
+  fn promote_variadic() {
+    let nat16_type = Type::Primitive(PrimitiveType::Integer(BitWidth::Width16, false));
+    let real32_type = Type::Primitive(PrimitiveType::Real(BitWidth::Width32));
+
+    assert!(matches!(
+      nat16_type.promote_variadic(),
+      Some(Type::Primitive(PrimitiveType::Integer(
+        BitWidth::Width32,
+        false
+      )))
+    ));
+
+    assert!(matches!(
+      real32_type.promote_variadic(),
+      Some(Type::Primitive(PrimitiveType::Real(BitWidth::Width64)))
+    ));
+
+    assert!(matches!(
+      Type::Primitive(PrimitiveType::Bool).promote_variadic(),
+      Some(Type::Primitive(PrimitiveType::Integer(
+        BitWidth::Width32,
+        true
+      )))
+    ));
+
+    assert!(matches!(
+      Type::Primitive(PrimitiveType::Char).promote_variadic(),
+      Some(Type::Primitive(PrimitiveType::Integer(
+        BitWidth::Width32,
+        true
+      )))
+    ));
+
+    assert!(DEFAULT_INTEGER_TYPE.promote_variadic().is_none());
+    assert!(Type::Unit.promote_variadic().is_none());
   }
 }
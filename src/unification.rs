@@ -12,12 +12,146 @@ use crate::{
   types,
 };
 
+impl From<types::UnificationError> for diagnostic::Diagnostic {
+  fn from(error: types::UnificationError) -> Self {
+    match error {
+      types::UnificationError::Merge(merge_error) => diagnostic::Diagnostic::from(merge_error),
+      types::UnificationError::IncompleteIntersection(intersection_len, fields_len) => {
+        diagnostic::Diagnostic::IntersectionOfClosedObjectsIsIncomplete(
+          intersection_len,
+          fields_len,
+        )
+      }
+      types::UnificationError::FieldCountMismatch(count_a, count_b) => {
+        diagnostic::Diagnostic::ObjectFieldCountMismatch(count_a, count_b)
+      }
+    }
+  }
+}
+
+impl From<types::ObjectMergeError> for diagnostic::Diagnostic {
+  fn from(error: types::ObjectMergeError) -> Self {
+    match error {
+      types::ObjectMergeError::UnknownField(field_name) => {
+        diagnostic::Diagnostic::ObjectFieldDoesNotExist(field_name)
+      }
+      types::ObjectMergeError::FieldTypeConflict {
+        field_name,
+        open_field_type,
+        closed_field_type,
+      } => diagnostic::Diagnostic::ObjectFieldTypeMismatch {
+        field_name,
+        expected: open_field_type,
+        actual: closed_field_type,
+      },
+    }
+  }
+}
+
+/// Determines whether open object types (ie. those produced by field accesses)
+/// may remain open once all constraints have been solved.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum RowPolymorphism {
+  /// Open object types are permitted to remain open, and are simply closed
+  /// over the fields that were accessed (structural typing).
+  #[default]
+  Structural,
+  /// Open object types must have been unified against a closed object type,
+  /// such as a declared type, otherwise they are reported.
+  Nominal,
+}
+
 pub struct TypeUnificationContext<'a> {
   pub(crate) symbol_table: &'a symbol_table::SymbolTable,
   /// Substitution map for type variables and generics.
   substitutions: symbol_table::SubstitutionEnv,
   object_substitutions: symbol_table::SubstitutionEnv,
+  /// The bindings made to the substitutions while a snapshot is open, which
+  /// allows failed unification attempts to be undone.
+  undo_log: symbol_table::SubstitutionUndoLog,
   resolution_helper: resolution::BaseResolutionHelper<'a>,
+  literal_origins: inference::LiteralOrigins,
+  /// The type environment produced by inference, if any, which is used to
+  /// unify `typeof` types as the type of their expression.
+  partial_type_env: Option<&'a symbol_table::TypeEnvironment>,
+  /// The maximum amount of type variable occurrences allowed across all
+  /// constraints to be solved, if any.
+  type_variable_limit: Option<usize>,
+  row_polymorphism: RowPolymorphism,
+  /// The maximum amount of unification steps that solving the constraints
+  /// may take.
+  step_limit: usize,
+  /// The amount of unification steps taken so far while solving the
+  /// constraints.
+  steps: usize,
+}
+
+/// The default maximum amount of unification steps, which is generous
+/// enough to never be reached by reasonable programs.
+pub const DEFAULT_STEP_LIMIT: usize = 1_000_000;
+
+/// Convert any open object types remaining within a solved type into closed
+/// object types, since their fields can no longer be extended once all
+/// constraints have been solved.
+///
+/// Open object types whose fields contain unsolved type variables cannot be
+/// finalized, as their layout is genuinely unknown. Under nominal row
+/// polymorphism, no open object types may remain at all.
+fn finalize_open_objects(
+  ty: &types::Type,
+  row_polymorphism: RowPolymorphism,
+) -> Result<types::Type, diagnostic::Diagnostic> {
+  let finalize = |ty: &types::Type| finalize_open_objects(ty, row_polymorphism);
+
+  let finalize_all =
+    |types: &[types::Type]| types.iter().map(finalize).collect::<Result<Vec<_>, _>>();
+
+  Ok(match ty {
+    types::Type::Object(object_type) => {
+      let is_open = matches!(object_type.kind, types::ObjectKind::Open(..));
+
+      if is_open && row_polymorphism == RowPolymorphism::Nominal {
+        return Err(diagnostic::Diagnostic::OpenObjectTypeNotClosed(
+          ty.to_owned(),
+        ));
+      }
+
+      if is_open
+        && object_type
+          .fields
+          .values()
+          .any(|field_type| field_type.count_type_variables() > 0)
+      {
+        return Err(diagnostic::Diagnostic::AmbiguousObjectType(ty.to_owned()));
+      }
+
+      types::Type::Object(types::ObjectType {
+        fields: object_type
+          .fields
+          .iter()
+          .map(|(name, field_type)| Ok((name.to_owned(), finalize(field_type)?)))
+          .collect::<Result<_, diagnostic::Diagnostic>>()?,
+        kind: types::ObjectKind::Closed,
+      })
+    }
+    types::Type::Pointer(pointee) => finalize(pointee)?.into_pointer_type(),
+    types::Type::Reference(pointee) => types::Type::Reference(Box::new(finalize(pointee)?)),
+    types::Type::Array { element, length } => types::Type::Array {
+      element: Box::new(finalize(element)?),
+      length: *length,
+    },
+    types::Type::Tuple(types::TupleType(element_types)) => {
+      types::Type::Tuple(types::TupleType(finalize_all(element_types)?))
+    }
+    types::Type::Signature(signature_type) => {
+      types::Type::Signature(Box::new(types::SignatureType {
+        parameter_types: finalize_all(&signature_type.parameter_types)?,
+        return_type: Box::new(finalize(&signature_type.return_type)?),
+        arity_mode: signature_type.arity_mode,
+      }))
+    }
+    _ => ty.to_owned(),
+  })
 }
 
 impl<'a> TypeUnificationContext<'a> {
@@ -30,7 +164,74 @@ impl<'a> TypeUnificationContext<'a> {
       symbol_table,
       substitutions: type_var_substitutions,
       object_substitutions: symbol_table::SubstitutionEnv::new(),
+      undo_log: symbol_table::SubstitutionUndoLog::default(),
       resolution_helper: resolution::BaseResolutionHelper::new(universes, symbol_table),
+      literal_origins: inference::LiteralOrigins::new(),
+      partial_type_env: None,
+      type_variable_limit: None,
+      row_polymorphism: RowPolymorphism::default(),
+      step_limit: DEFAULT_STEP_LIMIT,
+      steps: 0,
+    }
+  }
+
+  /// Provide the type environment produced by inference, so that `typeof`
+  /// types which were not instantiated during inference (ie. those within
+  /// generic hints or type definitions) can be unified.
+  pub(crate) fn set_partial_type_env(
+    &mut self,
+    partial_type_env: &'a symbol_table::TypeEnvironment,
+  ) {
+    self.partial_type_env = Some(partial_type_env);
+  }
+
+  pub(crate) fn set_row_polymorphism(&mut self, row_polymorphism: RowPolymorphism) {
+    self.row_polymorphism = row_polymorphism;
+  }
+
+  /// Limit the amount of type variable occurrences across the constraints
+  /// to be solved, which serves as an estimate of their complexity.
+  pub(crate) fn set_type_variable_limit(&mut self, type_variable_limit: Option<usize>) {
+    self.type_variable_limit = type_variable_limit;
+  }
+
+  /// Limit the amount of unification steps that solving the constraints may
+  /// take, which bounds the time spent on pathological constraint sets.
+  pub(crate) fn set_step_limit(&mut self, step_limit: usize) {
+    self.step_limit = step_limit;
+  }
+
+  /// The substitutions produced so far, which may be used to warm start
+  /// another unification context.
+  ///
+  /// Object substitutions are not included, thus warm starting from these
+  /// is only possible for constraints that do not involve open objects.
+  pub(crate) fn get_substitutions(&self) -> &symbol_table::SubstitutionEnv {
+    &self.substitutions
+  }
+
+  /// Register the type variables that originate from numeric literals,
+  /// allowing them to be coerced into the types they are unified against.
+  pub(crate) fn register_literal_origins(&mut self, literal_origins: inference::LiteralOrigins) {
+    self.literal_origins.extend(literal_origins);
+  }
+
+  /// Substitute any literal-origin type variables which remain unsolved with
+  /// their literal's default type.
+  fn default_unsolved_literals(&mut self) {
+    for (substitution_id, literal_origin) in &self.literal_origins {
+      let is_unsolved = self
+        .substitutions
+        .get(substitution_id)
+        .map_or(true, |substitution| {
+          substitution.is_same_type_variable_as(substitution_id)
+        });
+
+      if is_unsolved {
+        self
+          .substitutions
+          .insert(*substitution_id, literal_origin.default_type.to_owned());
+      }
     }
   }
 
@@ -117,22 +318,59 @@ impl<'a> TypeUnificationContext<'a> {
   /// is to solve all type variables, and some generics. Note that not all generics
   /// will be resolved, and that is expected because polymorphic functions must remain
   /// with their generic types.
+  ///
+  /// ## Warm start
+  ///
+  /// Solving starts from the context's current substitution environment,
+  /// which may already be populated, either by previous calls on the same
+  /// context, or by a substitution environment given upon creation of the
+  /// context (see `get_substitutions`). Such substitutions are assumed to be
+  /// the result of solving a set of constraints which are consistent with the
+  /// given ones, thus only newly added constraints need to be given. Solving
+  /// an already-solved constraint again is a no-op, because type variables
+  /// are unified against their existing substitutions instead of being
+  /// overwritten.
   pub(crate) fn solve_constraints(
     &mut self,
     partial_type_env: &symbol_table::TypeEnvironment,
     constraints: &inference::ConstraintSet,
   ) -> diagnostic::Maybe<symbol_table::TypeEnvironment> {
+    use symbol_table::SubstitutionEnvExt;
+
     // SAFETY: What if we have conflicting constraints? Say, we have different calls with different types to the same function? Or if the parameters are constrained to be something, yet the arguments are constrained to be different?
-    let constraints = constraints
+    // NOTE: Tuple element constraints are solved after all compatibility
+    // constraints, since the tuple types they refer to are only known once
+    // their type variables have been substituted.
+    let (constraints, tuple_element_constraints): (Vec<_>, Vec<_>) = constraints
       .iter()
       // OPTIMIZE: Avoid cloning.
       .cloned()
-      .filter(|constraint| matches!(constraint.1, inference::Constraint::Equality(..)))
-      .collect::<Vec<_>>();
+      .partition(|constraint| matches!(constraint.1, inference::Constraint::Compatibility { .. }));
 
     let mut diagnostics_helper = diagnostic::DiagnosticsHelper::default();
 
-    // Solve all equality constraints.
+    self.steps = 0;
+
+    if let Some(limit) = self.type_variable_limit {
+      let count = constraints
+        .iter()
+        .map(|(_, constraint)| match constraint {
+          inference::Constraint::Compatibility { a, b, .. } => {
+            a.count_type_variables() + b.count_type_variables()
+          }
+          inference::Constraint::TupleElementOf { .. } => 0,
+        })
+        .sum::<usize>();
+
+      if count > limit {
+        return Err(vec![diagnostic::Diagnostic::TooManyTypeVariables {
+          count,
+          limit,
+        }]);
+      }
+    }
+
+    // Solve all compatibility constraints.
     for (universe_stack, constraint) in constraints.clone() {
       assert!(
         universe_stack.len() <= self.resolution_helper.get_universes().len(),
@@ -142,7 +380,13 @@ impl<'a> TypeUnificationContext<'a> {
       diagnostics_helper.extend(self.dispatch_constraint(&universe_stack, constraint))?;
     }
 
-    let mut solutions = symbol_table::TypeEnvironment::new();
+    for (universe_stack, constraint) in tuple_element_constraints {
+      diagnostics_helper.extend(self.dispatch_constraint(&universe_stack, constraint))?;
+    }
+
+    self.default_unsolved_literals();
+
+    let mut solutions = symbol_table::TypeEnvironment::with_capacity(partial_type_env.len());
 
     let substitution_helper = substitution::UnificationSubstitutionHelper {
       symbol_table: self.symbol_table,
@@ -154,21 +398,31 @@ impl<'a> TypeUnificationContext<'a> {
     // Substitute all types in the substitution map, and store the results on the
     // solutions map to be returned. In the case that any solving fails, issue a
     // corresponding diagnostic.
-    for (id, ty) in partial_type_env {
-      let substitution = match substitution_helper.substitute(ty) {
-        Ok(substitution) => substitution,
+    let substitutions = substitution_helper
+      .substitute_batch(partial_type_env)
+      .map_err(|(id, substitution_error)| match substitution_error {
         // REVISE: Don't just return this error; add it to the diagnostics helper, and return the diagnostics helper. This way, multiple diagnostics are aggregated.
-        Err(substitution::SubstitutionError::TypeStripError(types::TypeStripError::RecursionDetected)) => return Err(vec![diagnostic::Diagnostic::RecursiveType(ty.to_owned())]),
+        // Report the stub type at which recursion was detected, so that its path is
+        // shown, rather than the type that contains it.
+        substitution::SubstitutionError::RecursiveStubType(stub_type) => vec![diagnostic::Diagnostic::RecursiveType(types::Type::Stub(Box::new(stub_type)))],
+        substitution::SubstitutionError::TypeStripError(types::TypeStripError::RecursionDetected) => vec![diagnostic::Diagnostic::RecursiveType(partial_type_env[&id].to_owned())],
         // This would constitute a logic bug in where the name resolution pass
         // did not properly fill in all entries.
-        Err(substitution::SubstitutionError::TypeStripError(types::TypeStripError::SymbolTableMissingEntry)) | Err(substitution::SubstitutionError::DirectRecursionCheckError(types::DirectRecursionCheckError::SymbolTableMissingEntry)) => unreachable!("name resolution should have previously registered all links and nodes in the symbol table")
-      };
+        substitution::SubstitutionError::TypeStripError(types::TypeStripError::SymbolTableMissingEntry) | substitution::SubstitutionError::DirectRecursionCheckError(types::DirectRecursionCheckError::SymbolTableMissingEntry) => unreachable!("name resolution should have previously registered all links and nodes in the symbol table"),
+      })?;
 
+    for (id, substitution) in substitutions {
       // REVISE: Perform stub type stripping on each unification call step instead of everywhere else. This way, there shouldn't need to be a need to strip stub types on subsequent phases after unification has occurred (including here).
       let stripped_substitution = substitution
         .try_strip_all_monomorphic_stub_layers(self.symbol_table)
         // FIXME: Properly handle result.
-        .unwrap();
+        .unwrap_or_else(|error| {
+          panic!(
+            "solved type should be strippable ({:?}), with substitutions:\n{}",
+            error,
+            self.substitutions.dump_sorted()
+          )
+        });
 
       // There should not be any type variables left after substitution,
       // otherwise it would mean that not all constraints could be fully
@@ -181,6 +435,8 @@ impl<'a> TypeUnificationContext<'a> {
         // Include the substituted type as well, to ensure that it isn't
         // a type variable itself.
         .chain(std::iter::once(&stripped_substitution))
+        // Concrete types cannot be unsolved.
+        .filter(|ty| ty.as_concrete().is_none())
       {
         if let types::Type::Variable(type_variable) = inner_type {
           diagnostics_helper.add_one(diagnostic::Diagnostic::UnsolvedTypeVariable(
@@ -192,7 +448,17 @@ impl<'a> TypeUnificationContext<'a> {
 
       // SAFETY: Check that there aren't any type variables on the INDIRECT subtree left?
 
-      solutions.insert(*id, stripped_substitution);
+      let finalized_substitution =
+        match finalize_open_objects(&stripped_substitution, self.row_polymorphism) {
+          Ok(finalized_substitution) => finalized_substitution,
+          Err(diagnostic) => {
+            diagnostics_helper.add_one(diagnostic);
+
+            stripped_substitution
+          }
+        };
+
+      solutions.insert(id, finalized_substitution);
     }
 
     diagnostics_helper.try_return_value(solutions)
@@ -203,9 +469,30 @@ impl<'a> TypeUnificationContext<'a> {
     tuple_type: &types::Type,
     element_type: &types::Type,
     index: u32,
+    universe_stack: &resolution::UniverseStack,
   ) -> diagnostic::Maybe {
-    // TODO: Implement. Might need to occur after equality constraints, so that it doesn't have to deal with type variables, generics, and stub types?
-    todo!();
+    let resolved_tuple_type = match tuple_type {
+      types::Type::Variable(type_variable) => type_variable
+        .try_substitute_self(&self.substitutions)
+        .cloned()
+        .unwrap_or_else(|| tuple_type.to_owned()),
+      _ => tuple_type.to_owned(),
+    };
+
+    // TODO: Handle stub types and generics that resolve to tuple types.
+    // NOTE: If the tuple type is not yet known, nothing can be determined
+    // about the element; it will later be reported as unsolved.
+    let types::Type::Tuple(tuple) = resolved_tuple_type else {
+      return Ok(());
+    };
+
+    match tuple.element(index as usize) {
+      Some(tuple_element_type) => self.unify(element_type, tuple_element_type, universe_stack),
+      None => Err(vec![diagnostic::Diagnostic::TupleAccessOutOfBounds {
+        index: index as usize,
+        tuple_length: tuple.len(),
+      }]),
+    }
   }
 
   fn dispatch_constraint(
@@ -214,18 +501,92 @@ impl<'a> TypeUnificationContext<'a> {
     constraint: inference::Constraint,
   ) -> diagnostic::Maybe {
     match &constraint {
-      // Equality between two types.
-      inference::Constraint::Equality(type_a, type_b) => self.unify(type_a, type_b, universe_stack),
+      // Compatibility between two types.
+      inference::Constraint::Compatibility {
+        a,
+        b,
+        variance,
+        origin,
+        type_id,
+      } => self
+        .unify_with_variance(a, b, *variance, universe_stack)
+        .map_err(|diagnostics| match origin {
+          Some(origin) => diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic::Diagnostic::ConstraintOriginated {
+              origin: origin.to_owned(),
+              type_id: type_id.to_owned(),
+              diagnostic: Box::new(diagnostic),
+            })
+            .collect(),
+          None => diagnostics,
+        }),
       inference::Constraint::TupleElementOf {
         tuple_type,
         element_type,
         index,
-      } => self.unify_tuple_element_of(tuple_type, element_type, *index),
+      } => self.unify_tuple_element_of(tuple_type, element_type, *index, universe_stack),
     }
   }
 }
 
 impl TypeUnificationContext<'_> {
+  /// Unifies two types for compatibility under the given variance.
+  ///
+  /// Invariant compatibility is treated identically to equality.
+  pub(crate) fn unify_with_variance(
+    &mut self,
+    type_a: &types::Type,
+    type_b: &types::Type,
+    variance: inference::Variance,
+    universe_stack: &resolution::UniverseStack,
+  ) -> diagnostic::Maybe {
+    match variance {
+      inference::Variance::Invariant => self.unify(type_a, type_b, universe_stack),
+      inference::Variance::Covariant => self.unify_subtype(type_a, type_b, universe_stack),
+      inference::Variance::Contravariant => self.unify_subtype(type_b, type_a, universe_stack),
+    }
+  }
+
+  /// Unifies two types such that the first type is a subtype of the second.
+  fn unify_subtype(
+    &mut self,
+    subtype: &types::Type,
+    supertype: &types::Type,
+    universe_stack: &resolution::UniverseStack,
+  ) -> diagnostic::Maybe {
+    // TODO: The only subtyping relation so far is that of lossless numeric coercions. Other relations (ie. open objects being supertypes of closed objects with more fields, or generic types with declared variance) should be handled here before falling back to equality.
+    let resolve = |ty: &types::Type| match ty {
+      types::Type::Variable(type_variable) => type_variable
+        .try_substitute_self(&self.substitutions)
+        .cloned()
+        .unwrap_or_else(|| ty.to_owned()),
+      _ => ty.to_owned(),
+    };
+
+    let (subtype, supertype) = (resolve(subtype), resolve(supertype));
+
+    // NOTE: The lowering phase only emits conversion instructions for the
+    // coercion of function bodies into their return types, thus non-invariant
+    // constraints should not be produced elsewhere until it does.
+    if types::Type::is_coercible_to(&subtype, &supertype) {
+      return Ok(());
+    }
+
+    // Falling back to equality may bind some type variables before failing
+    // (ie. on a later tuple element), thus any such bindings are undone.
+    let snapshot = self.undo_log.snapshot();
+    let result = self.unify(&subtype, &supertype, universe_stack);
+
+    if result.is_err() {
+      self.undo_log.rollback(&mut self.substitutions, snapshot);
+    } else {
+      self.undo_log.commit(snapshot);
+    }
+
+    result
+  }
+
   /// Unifies two types for equality. Solves the constraints by performing a
   /// unification algorithm. The types are compared for equivalence, similar
   /// to a system of equations, type variables are *substituted* to aid the
@@ -250,10 +611,31 @@ impl TypeUnificationContext<'_> {
   ) -> diagnostic::Maybe {
     // CONSIDER: Since various types have substitution ids, consider creating a `find_substitution_id` for types and resolving it automatically here on top, then removing the resolution logic from the match cases (this simplifies and standardizes the substitution procedure). Then, on the actual match cases, if they're reached it means that substitution couldn't be performed, thus we just have that logic for when they couldn't be substituted there (if any). This will also make it much easier to implement new types that may require substitution. The logic for when the substitution is itself will also need to added, to avoid infinite loops. The same abstraction can be used for the occurs check.
 
+    // Every unification (including nested ones) counts as a single step.
+    self.steps += 1;
+
+    if self.steps > self.step_limit {
+      return Err(vec![diagnostic::Diagnostic::SolveBudgetExceeded {
+        limit: self.step_limit,
+      }]);
+    }
+
     // TODO: Add an example of a case to demonstrate why this is the case (order matters for match cases), and explain clearly in which path what should occur and why.
     // NOTE: The order of match cases is important and can affect the unification
     // algorithm.
     match (type_a, type_b) {
+      // A `typeof` type is simply the type of its expression. It is expanded
+      // before type variables are unified, so that it never ends up as the
+      // substitution of a type variable.
+      (types::Type::Typeof(typeof_), other) | (other, types::Type::Typeof(typeof_)) => {
+        let expr_type = self
+          .partial_type_env
+          .and_then(|partial_type_env| partial_type_env.get(&typeof_.type_id))
+          .expect("the expression of a typeof type should have been inferred")
+          .to_owned();
+
+        self.unify(&expr_type, other, universe_stack)
+      }
       // NOTE: Type variables should ALWAYS be unified before the
       // other cases, otherwise it may miss opportunities to update
       // substitutions for type variables.
@@ -272,6 +654,39 @@ impl TypeUnificationContext<'_> {
       (types::Type::Tuple(tuple_a), types::Type::Tuple(tuple_b)) => {
         self.unify_tuples(tuple_a, tuple_b, universe_stack)
       }
+      (
+        types::Type::Array {
+          element: element_a,
+          length: length_a,
+        },
+        types::Type::Array {
+          element: element_b,
+          length: length_b,
+        },
+      ) => {
+        if length_a != length_b {
+          return Err(vec![diagnostic::Diagnostic::TypeMismatch(
+            type_a.to_owned(),
+            type_b.to_owned(),
+          )]);
+        }
+
+        self.unify(element_a, element_b, universe_stack)
+      }
+      // The empty tuple is a synonym for the unit type.
+      (types::Type::Tuple(_), types::Type::Unit) | (types::Type::Unit, types::Type::Tuple(_))
+        if type_a.is_unit() && type_b.is_unit() =>
+      {
+        Ok(())
+      }
+      // Unit values are produced liberally (ie. by statements and discards),
+      // thus using one where a value is expected is a common mistake, which
+      // warrants a more specific diagnostic. This only applies when the unit
+      // type is that of the value (the second type); an expected unit type
+      // is reported as a regular type mismatch.
+      (expected, types::Type::Unit) => Err(vec![diagnostic::Diagnostic::UnexpectedUnit {
+        expected: expected.to_owned(),
+      }]),
       (types::Type::Pointer(pointee_a), types::Type::Pointer(pointee_b)) => {
         self.unify(pointee_a.as_ref(), pointee_b.as_ref(), &universe_stack)
       }
@@ -283,10 +698,24 @@ impl TypeUnificationContext<'_> {
       }
       // Opaque pointers are incompatible with typed pointers. They must be casted
       // before any pointer operation is performed on them.
+      // NOTE: Because of this, an opaque pointer is never implicitly narrowed
+      // into a typed pointer during unification; any such narrowing is always
+      // explicit (through a cast), so no warning needs to be emitted here.
       (types::Type::Opaque, types::Type::Pointer(_))
       | (types::Type::Pointer(_), types::Type::Opaque) => {
         Err(vec![diagnostic::Diagnostic::OpaquePointerMustBeCasted])
       }
+      // REVIEW: References and pointers are not yet reconciled, so report the distinction clearly instead of a bare type mismatch.
+      (types::Type::Pointer(_), types::Type::Reference(_)) => {
+        Err(vec![diagnostic::Diagnostic::ReferencePointerMismatch {
+          found_reference: true,
+        }])
+      }
+      (types::Type::Reference(_), types::Type::Pointer(_)) => {
+        Err(vec![diagnostic::Diagnostic::ReferencePointerMismatch {
+          found_reference: false,
+        }])
+      }
       (types::Type::Object(object_a), types::Type::Object(object_b)) => {
         self.unify_objects(object_a, object_b, universe_stack)
       }
@@ -299,8 +728,46 @@ impl TypeUnificationContext<'_> {
           Ok(())
         }
       }
+      // A range is compatible with any integer type that is wide enough
+      // to hold its bounds. Since ranges are always non-negative, and their
+      // start is never greater than their end, only the end needs to be checked.
+      (
+        types::Type::Range(_, end),
+        types::Type::Primitive(types::PrimitiveType::Integer(bit_width, is_signed)),
+      )
+      | (
+        types::Type::Primitive(types::PrimitiveType::Integer(bit_width, is_signed)),
+        types::Type::Range(_, end),
+      ) => {
+        let value_bits = if *is_signed {
+          bit_width.bits() - 1
+        } else {
+          bit_width.bits()
+        };
+
+        // NOTE: The maximum value is computed as a 128-bit integer so that
+        // it may hold that of the widest integer types. A signed, single-bit
+        // integer has no bits left for its magnitude, which would otherwise
+        // overflow the shift.
+        let max_value = u128::MAX.checked_shr(u128::BITS - value_bits).unwrap_or(0);
+
+        if u128::from(*end) > max_value {
+          Err(vec![diagnostic::Diagnostic::TypeMismatch(
+            type_a.to_owned(),
+            type_b.to_owned(),
+          )])
+        } else {
+          Ok(())
+        }
+      }
       (types::Type::Primitive(primitive_a), types::Type::Primitive(primitive_b)) => {
-        if primitive_a != primitive_b {
+        // NOTE: Lossless numeric coercions are not considered here, since equality is symmetric but coercions are not. Those are instead handled by `unify_subtype`, for constraints with a non-invariant variance.
+        if primitive_a.is_different_number_kind_than(primitive_b) {
+          Err(vec![diagnostic::Diagnostic::NumberKindMismatch(
+            type_a.to_owned(),
+            type_b.to_owned(),
+          )])
+        } else if primitive_a != primitive_b {
           Err(vec![diagnostic::Diagnostic::TypeMismatch(
             type_a.to_owned(),
             type_b.to_owned(),
@@ -346,66 +813,47 @@ impl TypeUnificationContext<'_> {
       diagnostics_helper.extend(self.unify(field_a, field_b, &universe_stack))?;
     }
 
-    // TODO: Add passing tests representing each and every single case and edge case outlined here.
-    let result = match (object_a.kind, object_b.kind) {
-      // If they're both open object types, replace their types in the environment
-      // to be the a new open object type, representing the union of both.
-      (types::ObjectKind::Open(substitution_id_a), types::ObjectKind::Open(substitution_id_b)) => {
-        let union: types::ObjectFieldMap = object_a
-          .fields
-          .iter()
-          .chain(object_b.fields.iter())
-          .map(|field| (field.0.to_owned(), field.1.to_owned()))
-          .collect();
-
-        self.object_substitutions.insert(
-          substitution_id_a,
-          types::Type::Object(types::ObjectType {
-            fields: union.clone(),
-            kind: types::ObjectKind::Open(substitution_id_a),
-          }),
+    // Open object rows may be bound by unifying the objects, thus their
+    // current substitutions are recorded so that they can be undone.
+    for object_kind in [object_a.kind, object_b.kind] {
+      if let types::ObjectKind::Open(substitution_id) = object_kind {
+        self.undo_log.record(
+          substitution_id,
+          self.substitutions.get(&substitution_id).cloned(),
         );
+      }
+    }
 
-        self.object_substitutions.insert(
-          substitution_id_b,
-          types::Type::Object(types::ObjectType {
-            fields: union,
-            kind: types::ObjectKind::Open(substitution_id_b),
-          }),
-        );
+    // TODO: Add passing tests representing each and every single case and edge case outlined here.
+    let result = match types::unify_object_types(&object_a, &object_b, &mut self.substitutions) {
+      Ok(unified_object) => {
+        // If they're both open object types, replace their types in the environment
+        // to be the a new open object type, representing the union of both.
+        if let (
+          types::ObjectKind::Open(substitution_id_a),
+          types::ObjectKind::Open(substitution_id_b),
+        ) = (object_a.kind, object_b.kind)
+        {
+          self.object_substitutions.insert(
+            substitution_id_a,
+            types::Type::Object(types::ObjectType {
+              fields: unified_object.fields.clone(),
+              kind: types::ObjectKind::Open(substitution_id_a),
+            }),
+          );
+
+          self.object_substitutions.insert(
+            substitution_id_b,
+            types::Type::Object(types::ObjectType {
+              fields: unified_object.fields,
+              kind: types::ObjectKind::Open(substitution_id_b),
+            }),
+          );
+        }
 
         Ok(())
       }
-      // If one is open and the other is closed, ensure that all open fields are
-      // also present in the closed object's fields. This is because closed object
-      // types must contain all fields of the open object.
-      (types::ObjectKind::Open(substitution_id), types::ObjectKind::Closed) => {
-        self.check_open_closed_objects(&object_a, &object_b, substitution_id)
-      }
-      (types::ObjectKind::Closed, types::ObjectKind::Open(substitution_id)) => {
-        self.check_open_closed_objects(&object_b, &object_a, substitution_id)
-      }
-      // Otherwise, if they're both closed, simply ensure that the field count matches.
-      // Also, the intersection must be the same length as any of the field's lengths
-      // (since they would be checked to ensure they are the same). In other words,
-      // `len(intersection) == len(a) == len(b)` must hold true.
-      (types::ObjectKind::Closed, types::ObjectKind::Closed) => {
-        if intersection.len() != object_a.fields.len() {
-          Err(vec![
-            diagnostic::Diagnostic::IntersectionOfClosedObjectsIsIncomplete(
-              intersection.len(),
-              object_a.fields.len(),
-            ),
-          ])
-        } else if object_a.fields.len() != object_b.fields.len() {
-          Err(vec![diagnostic::Diagnostic::ObjectFieldCountMismatch(
-            object_a.fields.len(),
-            object_b.fields.len(),
-          )])
-        } else {
-          Ok(())
-        }
-      }
+      Err(unification_error) => Err(vec![diagnostic::Diagnostic::from(unification_error)]),
     };
 
     diagnostics_helper.extend(result)
@@ -426,7 +874,7 @@ impl TypeUnificationContext<'_> {
       Ok(stripped_target) => stripped_target,
       Err(types::TypeStripError::RecursionDetected) => {
         return Err(vec![diagnostic::Diagnostic::RecursiveType(
-          types::Type::Stub(stub_type.to_owned()),
+          types::Type::Stub(Box::new(stub_type.to_owned())),
         )]);
       }
       Err(types::TypeStripError::SymbolTableMissingEntry) => {
@@ -523,9 +971,18 @@ impl TypeUnificationContext<'_> {
     let signature_a_len = signature_a.parameter_types.len();
     let signature_b_len = signature_b.parameter_types.len();
 
-    let parameter_count_mismatch_error = Err(vec![
-      diagnostic::Diagnostic::SignaturesDifferInParameterCount(signature_a_len, signature_b_len),
-    ]);
+    // Arity mistakes involving function arguments (ie. a callback of the
+    // wrong arity) are reported separately, since they are harder to spot.
+    let parameter_count_mismatch_error = Err(vec![if signature_a.is_higher_order()
+      || signature_b.is_higher_order()
+    {
+      diagnostic::Diagnostic::HigherOrderSignaturesDifferInParameterCount(
+        signature_a_len,
+        signature_b_len,
+      )
+    } else {
+      diagnostic::Diagnostic::SignaturesDifferInParameterCount(signature_a_len, signature_b_len)
+    }]);
 
     // If neither function is variadic, their parameter count must match exactly.
     if !is_any_variadic && signature_a_len != signature_b_len {
@@ -540,22 +997,27 @@ impl TypeUnificationContext<'_> {
       return parameter_count_mismatch_error;
     }
 
-    let mut diagnostics_helper = diagnostic::DiagnosticsHelper::default();
-
     // NOTE: The zip will ignore variadic parameters without pairs.
-    for (parameter_a, parameter_b) in signature_a
+    let parameters_result = signature_a
       .parameter_types
       .iter()
       .zip(signature_b.parameter_types.iter())
-    {
-      diagnostics_helper.extend(self.unify(parameter_a, parameter_b, universe_stack))?;
-    }
+      .try_for_each(|(parameter_a, parameter_b)| {
+        self.unify(parameter_a, parameter_b, universe_stack)
+      });
 
-    diagnostics_helper.extend(self.unify(
+    // The return types are unified even if the parameters failed to unify,
+    // so that a mismatch on both is reported at once.
+    let return_type_result = self.unify(
       signature_a.return_type.as_ref(),
       signature_b.return_type.as_ref(),
       universe_stack,
-    ))
+    );
+
+    let ((), diagnostics) =
+      inference::try_combine_results(parameters_result, return_type_result, |_, _| ());
+
+    diagnostic::DiagnosticsHelper::from(diagnostics).try_return_value(())
   }
 
   pub(crate) fn unify_tuples(
@@ -565,14 +1027,16 @@ impl TypeUnificationContext<'_> {
     universe_stack: &resolution::UniverseStack,
   ) -> diagnostic::Maybe {
     let mut diagnostics_helper = diagnostic::DiagnosticsHelper::default();
-    let types_a = &tuple_a.0;
-    let types_b = &tuple_b.0;
-
-    if types_a.len() != types_b.len() {
-      diagnostics_helper.try_add_one(diagnostic::Diagnostic::TuplesDifferInLength)?;
+    // Check arity before unifying element-wise, otherwise the mismatch
+    // would surface as a more confusing element-level diagnostic.
+    if tuple_a.len() != tuple_b.len() {
+      diagnostics_helper.try_add_one(diagnostic::Diagnostic::TupleArityMismatch {
+        expected: tuple_a.len(),
+        actual: tuple_b.len(),
+      })?;
     }
 
-    for (type_a, type_b) in types_a.iter().zip(types_b.iter()) {
+    for (type_a, type_b) in tuple_a.iter().zip(tuple_b.iter()) {
       diagnostics_helper.extend(self.unify(type_a, type_b, universe_stack))?;
     }
 
@@ -585,17 +1049,30 @@ impl TypeUnificationContext<'_> {
     other_type: &types::Type,
     universe_stack: &resolution::UniverseStack,
   ) -> diagnostic::Maybe {
+    use symbol_table::SubstitutionEnvExt;
+
     // If both types are the same type variable do nothing as
     // they are equivalent; there is no need to verify anything further.
     if other_type.is_same_type_variable_as(&type_variable.substitution_id) {
       return Ok(());
     }
+
+    // Likewise, if both type variables are linked through their chains of
+    // substitutions, or their chains end up at the same concrete type, they
+    // are already equivalent.
+    if let types::Type::Variable(other_type_variable) = other_type {
+      if self.substitutions.transitively_equivalent(
+        type_variable.substitution_id,
+        other_type_variable.substitution_id,
+      ) {
+        return Ok(());
+      }
+    }
+
     // If there is an existing substitution for the type
     // variable, unify with that instead. This way we get
     // rid of type variables before actual unification.
-    else if let Some(existing_substitution) =
-      type_variable.try_substitute_self(&self.substitutions)
-    {
+    if let Some(existing_substitution) = type_variable.try_substitute_self(&self.substitutions) {
       // OPTIMIZE: Avoid cloning.
       return self.unify(
         &existing_substitution.to_owned(),
@@ -618,6 +1095,129 @@ impl TypeUnificationContext<'_> {
       }
     }
 
+    // Literal-origin type variables adopt the type they are unified against,
+    // provided that the literal's value fits within it.
+    if let Some(literal_origin) = self.literal_origins.get(&type_variable.substitution_id) {
+      match other_type {
+        types::Type::Variable(other_type_variable) => {
+          let other_substitution_id = other_type_variable.substitution_id;
+
+          // If both type variables originate from literals, the literal whose
+          // values fit within the other literal's default type is bound to it.
+          // The bound literal's values are then carried over, so that they are
+          // still considered if the other literal is later coerced.
+          if let Some(other_literal_origin) = self.literal_origins.get(&other_substitution_id) {
+            // NOTE: Integer literals are only promoted into real numbers when
+            // the context explicitly requires so, thus two literals of different
+            // number kinds never unify.
+            if let (types::Type::Primitive(primitive_a), types::Type::Primitive(primitive_b)) = (
+              &literal_origin.default_type,
+              &other_literal_origin.default_type,
+            ) {
+              if primitive_a.is_different_number_kind_than(primitive_b) {
+                return Err(vec![diagnostic::Diagnostic::NumberKindMismatch(
+                  literal_origin.default_type.to_owned(),
+                  other_literal_origin.default_type.to_owned(),
+                )]);
+              }
+            }
+
+            let (bound_id, target_type_variable) = if literal_origin
+              .coerce(&other_literal_origin.default_type)
+              .is_some()
+            {
+              (type_variable.substitution_id, other_type_variable)
+            } else if other_literal_origin
+              .coerce(&literal_origin.default_type)
+              .is_some()
+            {
+              (other_substitution_id, type_variable)
+            } else {
+              return Err(vec![diagnostic::Diagnostic::TypeMismatch(
+                literal_origin.default_type.to_owned(),
+                other_literal_origin.default_type.to_owned(),
+              )]);
+            };
+
+            let bound_values = self.literal_origins[&bound_id].values.clone();
+
+            self
+              .literal_origins
+              .get_mut(&target_type_variable.substitution_id)
+              .expect("target type variable should originate from a literal")
+              .values
+              .extend(bound_values);
+
+            self.substitutions.bind(
+              bound_id,
+              types::Type::Variable(target_type_variable.to_owned()),
+              &mut self.undo_log,
+            );
+
+            return Ok(());
+          }
+
+          // Otherwise, bind the other type variable to the literal-origin type
+          // variable instead, so that the literal's value is still considered once
+          // it is solved.
+          self.substitutions.bind(
+            other_substitution_id,
+            types::Type::Variable(type_variable.to_owned()),
+            &mut self.undo_log,
+          );
+
+          return Ok(());
+        }
+        // Stub and generic types must first be resolved, in order for the literal
+        // to be coerced against their resolutions.
+        types::Type::Stub(stub_type) => {
+          return self.unify_stub(
+            stub_type,
+            &types::Type::Variable(type_variable.to_owned()),
+            universe_stack,
+          );
+        }
+        types::Type::Generic(generic_type) => {
+          return self.unify_generic(
+            generic_type,
+            &types::Type::Variable(type_variable.to_owned()),
+            universe_stack,
+          );
+        }
+        _ => {
+          let coercion = literal_origin.coerce(other_type).ok_or_else(|| {
+            let is_real_literal = matches!(
+              literal_origin.default_type,
+              types::Type::Primitive(types::PrimitiveType::Real(..))
+            );
+
+            let is_integer_type = matches!(
+              other_type,
+              types::Type::Primitive(types::PrimitiveType::Integer(..))
+            );
+
+            if is_real_literal && is_integer_type {
+              vec![diagnostic::Diagnostic::NumberKindMismatch(
+                other_type.to_owned(),
+                literal_origin.default_type.to_owned(),
+              )]
+            } else {
+              vec![diagnostic::Diagnostic::TypeMismatch(
+                literal_origin.default_type.to_owned(),
+                other_type.to_owned(),
+              )]
+            }
+          })?;
+
+          self
+            .substitutions
+            .bind(type_variable.substitution_id, coercion, &mut self.undo_log);
+
+          return Ok(());
+        }
+      }
+    }
+
     // Otherwise, the other type is an unbound type variable; update the
     // substitution of the unbound type variable.
 
@@ -629,9 +1229,11 @@ impl TypeUnificationContext<'_> {
       return Err(vec![diagnostic::Diagnostic::ConstructionOfInfiniteType]);
     }
 
-    self
-      .substitutions
-      .insert(type_variable.substitution_id, other_type.to_owned());
+    self.substitutions.bind(
+      type_variable.substitution_id,
+      other_type.to_owned(),
+      &mut self.undo_log,
+    );
 
     Ok(())
   }
@@ -666,34 +1268,6 @@ impl TypeUnificationContext<'_> {
 
     self.unify(&resolution, other_type, universe_stack)
   }
-
-  pub(crate) fn check_open_closed_objects(
-    &mut self,
-    open_object: &types::ObjectType,
-    closed_object: &types::ObjectType,
-    substitution_id: symbol_table::SubstitutionId,
-  ) -> diagnostic::Maybe {
-    // Closed object must contain all fields of the open object.
-    for open_field in &open_object.fields {
-      if !closed_object.fields.contains_key(open_field.0) {
-        return Err(vec![diagnostic::Diagnostic::ObjectFieldDoesNotExist(
-          open_field.0.to_owned(),
-        )]);
-      }
-    }
-
-    self.substitutions.insert(
-      substitution_id,
-      types::Type::Object(types::ObjectType {
-        fields: closed_object.fields.clone(),
-        // When open objects are unified against closed objects, they are
-        // no longer open to extensibility.
-        kind: types::ObjectKind::Closed,
-      }),
-    );
-
-    Ok(())
-  }
 }
 
 #[cfg(test)]
@@ -749,7 +1323,7 @@ mod tests {
   }
 
   #[test]
-  fn solve_constraints() {
+  fn unify_signatures_reports_all_mismatches() {
     let symbol_table = symbol_table::SymbolTable::default();
     let universes = instantiation::TypeSchemes::new();
 
@@ -759,13 +1333,1044 @@ mod tests {
       &universes,
     );
 
-    // TODO: Add actual constraints to complete this test.
+    let signature_of = |ty: types::Type| types::SignatureType {
+      return_type: Box::new(ty.clone()),
+      parameter_types: vec![ty],
+      arity_mode: types::ArityMode::Fixed,
+    };
+
+    let bool_signature = signature_of(types::Type::Primitive(types::PrimitiveType::Bool));
+    let char_signature = signature_of(types::Type::Primitive(types::PrimitiveType::Char));
+
+    assert!(matches!(
+      unification_ctx
+        .unify_signatures(
+          &bool_signature,
+          &char_signature,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [
+        diagnostic::Diagnostic::TypeMismatch(..),
+        diagnostic::Diagnostic::TypeMismatch(..)
+      ]
+    ));
+  }
+
+  #[test]
+  fn unify_higher_order_signatures_parameter_count_mismatch() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let signature_of = |parameter_types: Vec<types::Type>| types::SignatureType {
+      return_type: Box::new(types::Type::Unit),
+      parameter_types,
+      arity_mode: types::ArityMode::Fixed,
+    };
+
+    let callback_type = types::Type::Signature(Box::new(signature_of(Vec::new())));
+    let unary_signature = signature_of(vec![types::Type::Unit]);
+
+    assert!(matches!(
+      unification_ctx
+        .unify_signatures(
+          &unary_signature,
+          &signature_of(Vec::new()),
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::SignaturesDifferInParameterCount(
+        1, 0
+      )]
+    ));
+
+    assert!(matches!(
+      unification_ctx
+        .unify_signatures(
+          &unary_signature,
+          &signature_of(vec![callback_type.clone(), callback_type]),
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::HigherOrderSignaturesDifferInParameterCount(1, 2)]
+    ));
+  }
+
+  #[test]
+  fn unify_tuples() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let pair = types::TupleType(vec![types::Type::Unit, types::Type::Unit]);
+    let triple = types::TupleType(vec![types::Type::Unit; 3]);
 
     assert!(unification_ctx
-      .solve_constraints(
-        &symbol_table::TypeEnvironment::new(),
-        &inference::ConstraintSet::new()
+      .unify_tuples(&pair, &pair, &resolution::UniverseStack::new())
+      .is_ok());
+
+    assert!(matches!(
+      unification_ctx
+        .unify_tuples(&pair, &triple, &resolution::UniverseStack::new())
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TupleArityMismatch {
+        expected: 2,
+        actual: 3
+      }]
+    ));
+
+    assert!(matches!(
+      unification_ctx
+        .unify_tuple_element_of(
+          &types::Type::Tuple(pair.clone()),
+          &types::Type::Unit,
+          2,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TupleAccessOutOfBounds {
+        index: 2,
+        tuple_length: 2
+      }]
+    ));
+
+    let empty_tuple = types::Type::Tuple(types::TupleType(Vec::new()));
+
+    assert!(unification_ctx
+      .unify(
+        &empty_tuple,
+        &types::Type::Unit,
+        &resolution::UniverseStack::new()
+      )
+      .is_ok());
+
+    assert!(unification_ctx
+      .unify(
+        &types::Type::Unit,
+        &types::Type::Tuple(pair),
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+  }
+
+  #[test]
+  fn unify_unexpected_unit() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let char_type = types::Type::Primitive(types::PrimitiveType::Char);
+
+    assert!(matches!(
+      unification_ctx
+        .unify(
+          &char_type,
+          &types::Type::Unit,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::UnexpectedUnit {
+        expected: types::Type::Primitive(types::PrimitiveType::Char)
+      }]
+    ));
+
+    // A value used where unit is expected is not a misused unit value.
+    assert!(matches!(
+      unification_ctx
+        .unify(
+          &types::Type::Unit,
+          &char_type,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TypeMismatch(..)]
+    ));
+  }
+
+  #[test]
+  fn unify_with_variance_numeric_subtype() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let int8_type =
+      types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width8, true));
+
+    let int64_type = types::Type::Primitive(types::PrimitiveType::Integer(
+      types::BitWidth::Width64,
+      true,
+    ));
+
+    assert!(unification_ctx
+      .unify_with_variance(
+        &int64_type,
+        &int8_type,
+        inference::Variance::Contravariant,
+        &resolution::UniverseStack::new()
       )
       .is_ok());
+
+    assert!(unification_ctx
+      .unify_with_variance(
+        &int64_type,
+        &int8_type,
+        inference::Variance::Covariant,
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+
+    assert!(unification_ctx
+      .unify_with_variance(
+        &int64_type,
+        &int8_type,
+        inference::Variance::Invariant,
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+  }
+
+  #[test]
+  fn unify_subtype_rollback() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let type_variable = types::Type::Variable(types::TypeVariable {
+      substitution_id: symbol_table::SubstitutionId(0),
+      debug_name: "test",
+    });
+
+    // Type variables are bound to themselves upon creation during inference.
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::from([(
+        symbol_table::SubstitutionId(0),
+        type_variable.clone(),
+      )]),
+      &universes,
+    );
+
+    // The type variable is bound against the first element, before the
+    // second element fails to unify.
+    let result = unification_ctx.unify_with_variance(
+      &types::Type::Tuple(types::TupleType(vec![
+        type_variable,
+        types::Type::Primitive(types::PrimitiveType::Bool),
+      ])),
+      &types::Type::Tuple(types::TupleType(vec![
+        types::Type::Unit,
+        types::Type::Primitive(types::PrimitiveType::Char),
+      ])),
+      inference::Variance::Covariant,
+      &resolution::UniverseStack::new(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(1, unification_ctx.substitutions.len());
+
+    assert!(matches!(
+      unification_ctx
+        .substitutions
+        .get(&symbol_table::SubstitutionId(0)),
+      Some(types::Type::Variable(types::TypeVariable {
+        substitution_id: symbol_table::SubstitutionId(0),
+        ..
+      }))
+    ));
+  }
+
+  #[test]
+  fn unify_opaque_with_pointer() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let int_pointer_type = types::Type::Pointer(Box::new(types::Type::Primitive(
+      types::PrimitiveType::Integer(types::BitWidth::Width32, true),
+    )));
+
+    assert!(matches!(
+      unification_ctx
+        .unify(
+          &types::Type::Opaque,
+          &int_pointer_type,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::OpaquePointerMustBeCasted]
+    ));
+  }
+
+  #[test]
+  fn unify_reference_with_pointer() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let pointer_type = types::Type::Pointer(Box::new(types::Type::Unit));
+    let reference_type = types::Type::Reference(Box::new(types::Type::Unit));
+
+    assert!(matches!(
+      unification_ctx
+        .unify(
+          &pointer_type,
+          &reference_type,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::ReferencePointerMismatch {
+        found_reference: true
+      }]
+    ));
+  }
+
+  #[test]
+  fn unify_range_with_integer() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let int_type = types::Type::Primitive(types::PrimitiveType::Integer(
+      types::BitWidth::Width32,
+      true,
+    ));
+
+    let int8_type =
+      types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width8, true));
+
+    assert!(unification_ctx
+      .unify(
+        &types::Type::Range(0, 10),
+        &int_type,
+        &resolution::UniverseStack::new()
+      )
+      .is_ok());
+
+    assert!(unification_ctx
+      .unify(
+        &int8_type,
+        &types::Type::Range(0, 1000),
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+
+    assert!(unification_ctx
+      .unify(
+        &types::Type::Range(0, u64::MAX),
+        &types::Type::Primitive(types::PrimitiveType::Integer(
+          types::BitWidth::Width128,
+          true
+        )),
+        &resolution::UniverseStack::new()
+      )
+      .is_ok());
+  }
+
+  #[test]
+  fn unify_real_widths() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let real32_type = types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width32));
+    let real64_type = types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width64));
+
+    let int_type = types::Type::Primitive(types::PrimitiveType::Integer(
+      types::BitWidth::Width32,
+      true,
+    ));
+
+    assert!(matches!(
+      unification_ctx
+        .unify(
+          &real32_type,
+          &real64_type,
+          &resolution::UniverseStack::new()
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TypeMismatch(..)]
+    ));
+
+    // Real widening is permitted for constraints with a non-invariant variance.
+    assert!(unification_ctx
+      .unify_with_variance(
+        &real32_type,
+        &real64_type,
+        inference::Variance::Covariant,
+        &resolution::UniverseStack::new()
+      )
+      .is_ok());
+
+    assert!(unification_ctx
+      .unify_with_variance(
+        &real64_type,
+        &real32_type,
+        inference::Variance::Covariant,
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+
+    assert!(matches!(
+      unification_ctx
+        .unify(&int_type, &real64_type, &resolution::UniverseStack::new())
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::NumberKindMismatch(..)]
+    ));
+  }
+
+  #[test]
+  fn unify_merged_literals() {
+    let mut id_generator = auxiliary::IdGenerator::default();
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let mut literal = |value| {
+      let substitution_id = id_generator.next_substitution_id();
+
+      unification_ctx.register_literal_origins(inference::LiteralOrigins::from([(
+        substitution_id,
+        inference::LiteralOrigin {
+          values: vec![value],
+          default_type: types::Type::Primitive(types::PrimitiveType::Integer(
+            types::BitWidth::Width32,
+            true,
+          )),
+        },
+      )]));
+
+      types::Type::Variable(types::TypeVariable {
+        substitution_id,
+        debug_name: "literal",
+      })
+    };
+
+    let large_literal = literal(1000_f64);
+    let small_literal = literal(5_f64);
+    let universe_stack = resolution::UniverseStack::new();
+
+    assert!(unification_ctx
+      .unify(&large_literal, &small_literal, &universe_stack)
+      .is_ok());
+
+    let int8_type =
+      types::Type::Primitive(types::PrimitiveType::Integer(types::BitWidth::Width8, true));
+
+    // Although the remaining literal's own value fits, the value of the
+    // literal that was bound to it does not.
+    assert!(matches!(
+      unification_ctx
+        .unify(&small_literal, &int8_type, &universe_stack)
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TypeMismatch(..)]
+    ));
+  }
+
+  #[test]
+  fn unify_integer_literal_with_real() {
+    let mut id_generator = auxiliary::IdGenerator::default();
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let mut literal = |value| {
+      let substitution_id = id_generator.next_substitution_id();
+
+      unification_ctx.register_literal_origins(inference::LiteralOrigins::from([(
+        substitution_id,
+        inference::LiteralOrigin {
+          values: vec![value],
+          default_type: types::Type::Primitive(types::PrimitiveType::Integer(
+            types::BitWidth::Width32,
+            true,
+          )),
+        },
+      )]));
+
+      types::TypeVariable {
+        substitution_id,
+        debug_name: "literal",
+      }
+    };
+
+    let promoted_literal = literal(5_f64);
+    let inexact_literal = literal(2_f64.powi(30));
+
+    let real32_type = types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width32));
+    let real64_type = types::Type::Primitive(types::PrimitiveType::Real(types::BitWidth::Width64));
+
+    assert!(unification_ctx
+      .unify(
+        &types::Type::Variable(promoted_literal.clone()),
+        &real64_type,
+        &resolution::UniverseStack::new()
+      )
+      .is_ok());
+
+    assert!(matches!(
+      promoted_literal.try_substitute_self(&unification_ctx.substitutions),
+      Some(types::Type::Primitive(types::PrimitiveType::Real(
+        types::BitWidth::Width64
+      )))
+    ));
+
+    // The literal's value cannot be represented exactly by a 32-bit real.
+    assert!(unification_ctx
+      .unify(
+        &types::Type::Variable(inexact_literal),
+        &real32_type,
+        &resolution::UniverseStack::new()
+      )
+      .is_err());
+  }
+
+  #[test]
+  fn solve_constraints() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    // TODO: Add actual constraints to complete this test.
+
+    assert!(unification_ctx
+      .solve_constraints(
+        &symbol_table::TypeEnvironment::new(),
+        &inference::ConstraintSet::new()
+      )
+      .is_ok());
+  }
+
+  #[test]
+  fn object_field_type_conflict_names_field() {
+    let diagnostic = diagnostic::Diagnostic::from(types::ObjectMergeError::FieldTypeConflict {
+      field_name: String::from("a"),
+      open_field_type: types::Type::Primitive(types::PrimitiveType::Bool),
+      closed_field_type: types::Type::Unit,
+    });
+
+    assert!(matches!(
+      &diagnostic,
+      diagnostic::Diagnostic::ObjectFieldTypeMismatch { field_name, .. } if field_name == "a"
+    ));
+  }
+
+  #[test]
+  fn unify_transitively_equivalent_type_variables() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let bool_type = types::Type::Primitive(types::PrimitiveType::Bool);
+
+    let mock_type_variable = |substitution_id| types::TypeVariable {
+      substitution_id,
+      debug_name: "test",
+    };
+
+    let substitutions = symbol_table::SubstitutionEnv::from([
+      (
+        symbol_table::SubstitutionId(0),
+        types::Type::Variable(mock_type_variable(symbol_table::SubstitutionId(1))),
+      ),
+      (symbol_table::SubstitutionId(1), bool_type.clone()),
+      (symbol_table::SubstitutionId(2), bool_type),
+    ]);
+
+    let mut unification_ctx = TypeUnificationContext::new(&symbol_table, substitutions, &universes);
+
+    assert!(unification_ctx
+      .unify_type_variable(
+        &mock_type_variable(symbol_table::SubstitutionId(0)),
+        &types::Type::Variable(mock_type_variable(symbol_table::SubstitutionId(2))),
+        &resolution::UniverseStack::new(),
+      )
+      .is_ok());
+
+    // No new substitutions are necessary to unify equivalent type variables.
+    assert_eq!(3, unification_ctx.get_substitutions().len());
+  }
+
+  #[test]
+  fn dump_solved_substitutions_deterministically() {
+    use symbol_table::SubstitutionEnvExt;
+
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let dump_solved_substitutions = || {
+      let mut id_generator = auxiliary::IdGenerator::default();
+      let mut type_var_substitutions = symbol_table::SubstitutionEnv::new();
+
+      let type_variables = (0..8)
+        .map(|_| {
+          let type_variable = types::Type::Variable(types::TypeVariable {
+            substitution_id: id_generator.next_substitution_id(),
+            debug_name: "test",
+          });
+
+          if let types::Type::Variable(types::TypeVariable {
+            substitution_id, ..
+          }) = &type_variable
+          {
+            type_var_substitutions.insert(*substitution_id, type_variable.clone());
+          }
+
+          type_variable
+        })
+        .collect::<Vec<_>>();
+
+      // Chain all type variables together in reverse, ending on a concrete type.
+      let mut constraints = type_variables
+        .windows(2)
+        .rev()
+        .map(|pair| {
+          (
+            resolution::UniverseStack::new(),
+            inference::Constraint::Compatibility {
+              a: pair[1].clone(),
+              b: pair[0].clone(),
+              variance: inference::Variance::Invariant,
+              origin: None,
+              type_id: None,
+            },
+          )
+        })
+        .collect::<inference::ConstraintSet>();
+
+      constraints.push((
+        resolution::UniverseStack::new(),
+        inference::Constraint::Compatibility {
+          a: type_variables[0].clone(),
+          b: types::Type::Primitive(types::PrimitiveType::Bool),
+          variance: inference::Variance::Invariant,
+          origin: None,
+          type_id: None,
+        },
+      ));
+
+      let mut unification_ctx =
+        TypeUnificationContext::new(&symbol_table, type_var_substitutions, &universes);
+
+      unification_ctx
+        .solve_constraints(&symbol_table::TypeEnvironment::new(), &constraints)
+        .unwrap();
+
+      unification_ctx.get_substitutions().dump_sorted()
+    };
+
+    let dump = dump_solved_substitutions();
+
+    assert_eq!(dump, dump_solved_substitutions());
+    assert!(dump.starts_with("$0 = "));
+    assert_eq!(8, dump.lines().count());
+  }
+
+  #[test]
+  fn solve_constraints_finalize_open_objects() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let mut id_generator = auxiliary::IdGenerator::default();
+
+    let field_substitution_id = id_generator.next_substitution_id();
+
+    let field_type_variable = types::Type::Variable(types::TypeVariable {
+      substitution_id: field_substitution_id,
+      debug_name: "test",
+    });
+
+    // An object which was only accessed, and whose field type was later
+    // fully determined.
+    let open_object_type = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(String::from("a"), field_type_variable.clone())]),
+      kind: types::ObjectKind::Open(id_generator.next_substitution_id()),
+    });
+
+    let type_env = symbol_table::TypeEnvironment::from([
+      (symbol_table::TypeId(0), open_object_type.clone()),
+      (
+        symbol_table::TypeId(1),
+        types::Type::Array {
+          element: Box::new(open_object_type.clone()),
+          length: 2,
+        },
+      ),
+    ]);
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    let constraints = inference::ConstraintSet::from([(
+      resolution::UniverseStack::new(),
+      inference::Constraint::Compatibility {
+        a: field_type_variable.clone(),
+        b: types::Type::Unit,
+        variance: inference::Variance::Invariant,
+        origin: None,
+        type_id: None,
+      },
+    )]);
+
+    let solutions = unification_ctx
+      .solve_constraints(&type_env, &constraints)
+      .unwrap();
+
+    assert!(matches!(
+      &solutions[&symbol_table::TypeId(0)],
+      types::Type::Object(types::ObjectType {
+        kind: types::ObjectKind::Closed,
+        ..
+      })
+    ));
+
+    // Open objects nested within other types are finalized as well.
+    assert!(matches!(
+      &solutions[&symbol_table::TypeId(1)],
+      types::Type::Array { element, .. } if matches!(
+        element.as_ref(),
+        types::Type::Object(types::ObjectType {
+          kind: types::ObjectKind::Closed,
+          ..
+        })
+      )
+    ));
+
+    // Without the field's type being determined, it remains ambiguous. The
+    // unsolved type variable is mapped to itself, as substitution expects all
+    // type variables to be present in the substitution environment.
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::from([(field_substitution_id, field_type_variable.clone())]),
+      &universes,
+    );
+
+    assert!(unification_ctx
+      .solve_constraints(&type_env, &inference::ConstraintSet::new())
+      .unwrap_err()
+      .iter()
+      .any(|diagnostic| matches!(diagnostic, diagnostic::Diagnostic::AmbiguousObjectType(..))));
+  }
+
+  #[test]
+  fn solve_constraints_row_polymorphism() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let mut id_generator = auxiliary::IdGenerator::default();
+
+    // A value which was only ever accessed through its field `a`.
+    let open_object_type = types::Type::Object(types::ObjectType {
+      fields: types::ObjectFieldMap::from([(String::from("a"), types::Type::Unit)]),
+      kind: types::ObjectKind::Open(id_generator.next_substitution_id()),
+    });
+
+    let type_env =
+      symbol_table::TypeEnvironment::from([(symbol_table::TypeId(0), open_object_type)]);
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    assert!(unification_ctx
+      .solve_constraints(&type_env, &inference::ConstraintSet::new())
+      .is_ok());
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    unification_ctx.set_row_polymorphism(RowPolymorphism::Nominal);
+
+    assert!(matches!(
+      unification_ctx
+        .solve_constraints(&type_env, &inference::ConstraintSet::new())
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::OpenObjectTypeNotClosed(..)]
+    ));
+  }
+
+  #[test]
+  fn solve_constraints_warm_start() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let mut id_generator = auxiliary::IdGenerator::default();
+
+    let mut create_type_variable = || {
+      types::Type::Variable(types::TypeVariable {
+        substitution_id: id_generator.next_substitution_id(),
+        debug_name: "test",
+      })
+    };
+
+    let (type_variable_a, type_variable_b) = (create_type_variable(), create_type_variable());
+    let int_type = types::Type::Primitive(types::PrimitiveType::Integer(
+      types::BitWidth::Width32,
+      true,
+    ));
+
+    let constraint = |a: &types::Type, b: &types::Type| {
+      (
+        resolution::UniverseStack::new(),
+        inference::Constraint::Compatibility {
+          a: a.to_owned(),
+          b: b.to_owned(),
+          variance: inference::Variance::Invariant,
+          origin: None,
+          type_id: None,
+        },
+      )
+    };
+
+    let type_env = symbol_table::TypeEnvironment::from([
+      (symbol_table::TypeId(0), type_variable_a.clone()),
+      (symbol_table::TypeId(1), type_variable_b.clone()),
+    ]);
+
+    let initial_constraints =
+      inference::ConstraintSet::from([constraint(&type_variable_a, &int_type)]);
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    assert!(unification_ctx
+      .solve_constraints(&symbol_table::TypeEnvironment::new(), &initial_constraints)
+      .is_ok());
+
+    let substitutions = unification_ctx.get_substitutions().to_owned();
+
+    // Re-solving an already-solved set should be a no-op.
+    assert!(unification_ctx
+      .solve_constraints(&symbol_table::TypeEnvironment::new(), &initial_constraints)
+      .is_ok());
+
+    assert_eq!(
+      substitutions.len(),
+      unification_ctx.get_substitutions().len()
+    );
+
+    // Warm start from the previous substitutions, only providing the newly
+    // added constraint.
+    let mut warm_unification_ctx =
+      TypeUnificationContext::new(&symbol_table, substitutions, &universes);
+
+    let solutions = warm_unification_ctx
+      .solve_constraints(
+        &type_env,
+        &inference::ConstraintSet::from([constraint(&type_variable_b, &type_variable_a)]),
+      )
+      .unwrap();
+
+    for type_id in [symbol_table::TypeId(0), symbol_table::TypeId(1)] {
+      assert_eq!(Some(true), solutions[&type_id].concrete_eq(&int_type));
+    }
+
+    // A limit that is too low should prevent solving altogether.
+    let mut limited_unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::new(),
+      &universes,
+    );
+
+    limited_unification_ctx.set_type_variable_limit(Some(1));
+
+    assert!(matches!(
+      limited_unification_ctx
+        .solve_constraints(
+          &symbol_table::TypeEnvironment::new(),
+          &inference::ConstraintSet::from([constraint(&type_variable_b, &type_variable_a)])
+        )
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::TooManyTypeVariables { count: 2, limit: 1 }]
+    ));
+  }
+
+  #[test]
+  fn unify_unions() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+    let substitution_id = symbol_table::SubstitutionId(0);
+
+    let mut unification_ctx = TypeUnificationContext::new(
+      &symbol_table,
+      symbol_table::SubstitutionEnv::from([(
+        substitution_id,
+        types::Type::Variable(types::TypeVariable {
+          substitution_id,
+          debug_name: "test",
+        }),
+      )]),
+      &universes,
+    );
+
+    let mock_union_type = |registry_id: usize, name: &str| {
+      types::Type::Union(std::rc::Rc::new(crate::ast::Union {
+        registry_id: symbol_table::RegistryId(registry_id),
+        name: name.to_string(),
+        variants: std::collections::BTreeMap::new(),
+      }))
+    };
+
+    let union_a = mock_union_type(0, "A");
+    let universe_stack = resolution::UniverseStack::new();
+
+    assert!(unification_ctx
+      .unify(&union_a, &mock_union_type(0, "A"), &universe_stack)
+      .is_ok());
+
+    // Unions are nominal; structurally identical unions still differ.
+    assert!(matches!(
+      unification_ctx
+        .unify(&union_a, &mock_union_type(1, "B"), &universe_stack)
+        .unwrap_err()
+        .as_slice(),
+      [diagnostic::Diagnostic::UnionTypesDiffer]
+    ));
+
+    let type_variable = types::Type::Variable(types::TypeVariable {
+      substitution_id,
+      debug_name: "test",
+    });
+
+    assert!(unification_ctx
+      .unify(&type_variable, &union_a, &universe_stack)
+      .is_ok());
+
+    assert!(matches!(
+      unification_ctx.substitutions.get(&substitution_id),
+      Some(types::Type::Union(union)) if union.registry_id == symbol_table::RegistryId(0)
+    ));
+  }
+
+  #[test]
+  fn solve_constraints_step_limit() {
+    let symbol_table = symbol_table::SymbolTable::default();
+    let universes = instantiation::TypeSchemes::new();
+
+    let tuple_type = types::Type::Tuple(types::TupleType(vec![
+      types::Type::Primitive(types::PrimitiveType::Bool),
+      types::Type::Opaque,
+    ]));
+
+    // NOTE: Each constraint is given a distinct origin, otherwise they
+    // would be deduplicated upon insertion.
+    let constraints = (0..100)
+      .map(|index| {
+        (
+          resolution::UniverseStack::new(),
+          inference::Constraint::Compatibility {
+            a: tuple_type.clone(),
+            b: tuple_type.clone(),
+            variance: inference::Variance::Invariant,
+            origin: Some(inference::ConstraintOrigin::CallArgument { index }),
+            type_id: None,
+          },
+        )
+      })
+      .collect::<inference::ConstraintSet>();
+
+    let solve = |step_limit| {
+      let mut unification_ctx = TypeUnificationContext::new(
+        &symbol_table,
+        symbol_table::SubstitutionEnv::new(),
+        &universes,
+      );
+
+      unification_ctx.set_step_limit(step_limit);
+
+      let result =
+        unification_ctx.solve_constraints(&symbol_table::TypeEnvironment::new(), &constraints);
+
+      (result, unification_ctx.steps)
+    };
+
+    let (result, steps) = solve(DEFAULT_STEP_LIMIT);
+
+    // Each tuple constraint takes a step, plus one for each of its elements.
+    assert!(result.is_ok());
+    assert_eq!(300, steps);
+
+    // The budget is enforced exactly at the limit.
+    assert!(solve(steps).0.is_ok());
+
+    assert!(matches!(
+      solve(steps - 1).0.unwrap_err().as_slice(),
+      [diagnostic::Diagnostic::ConstraintOriginated { diagnostic, .. }]
+        if matches!(**diagnostic, diagnostic::Diagnostic::SolveBudgetExceeded { limit: 299 })
+    ));
   }
 }
@@ -400,6 +400,10 @@ impl Visitable for ast::TupleIndex {
   fn accept<T>(&self, visitor: &mut dyn Visitor<T>) -> T {
     visitor.visit_tuple_indexing(self)
   }
+
+  fn traverse_children<T>(&self, visitor: &mut dyn Visitor<T>) {
+    self.indexed_tuple.traverse(visitor);
+  }
 }
 
 impl Visitable for ast::UnaryOp {
@@ -592,7 +596,9 @@ impl Visitable for ast::Match {
       arm.body.traverse(visitor);
     }
 
-    self.default_case.traverse(visitor);
+    if let Some(default_case) = &self.default_case {
+      default_case.traverse(visitor);
+    }
   }
 }
 
@@ -775,6 +781,12 @@ impl Visitable for types::Type {
       types::Type::Reference(pointee_type) => {
         pointee_type.traverse(visitor);
       }
+      types::Type::Array { element, .. } => {
+        element.traverse(visitor);
+      }
+      types::Type::Typeof(typeof_) => {
+        typeof_.expr.traverse(visitor);
+      }
       _ => {}
     }
   }
@@ -4,7 +4,7 @@ extern crate tails;
 #[cfg(test)]
 mod tests {
   use pretty_assertions::assert_eq;
-  use tails::{diagnostic, lexer, pass};
+  use tails::{diagnostic, inference, lexer, pass};
 
   const TESTS_FOLDER: &str = "tests";
   const BUG_CURRENT_FOLDER: &str = "the current directory should exist and be accessible";
@@ -31,10 +31,12 @@ mod tests {
     Ok(filtered_tokens)
   }
 
+  /// Lower the given source file, returning the LLVM IR output alongside any
+  /// non-error diagnostics (warnings) that were produced.
   fn lower_file(
     source_file_contents: &str,
     qualifier: tails::symbol_table::Qualifier,
-  ) -> diagnostic::Maybe<String> {
+  ) -> diagnostic::Maybe<(String, Vec<diagnostic::Diagnostic>)> {
     let mut parser = tails::parser::Parser::new(lex_and_filter(source_file_contents)?);
     let module_result = parser.parse_module(qualifier.clone());
 
@@ -71,16 +73,21 @@ mod tests {
       .get(&pass::PassId::LlvmLowering)
       .expect("backend output should have been produced if there were no error diagnostics");
 
-    Ok(match llvm_lowering_pass_result {
+    let llvm_ir_output = match llvm_lowering_pass_result {
       // OPTIMIZE: Consume result and avoid cloning.
       pass::PassResult::LlvmIrOutput(llvm_ir_output) => llvm_ir_output.to_owned(),
       _ => {
         unreachable!("backend output should have been produced if there were no error diagnostics")
       }
-    })
+    };
+
+    Ok((llvm_ir_output, diagnostics_helper.diagnostics))
   }
 
-  fn run_test(name: &str, folder_name: &str) -> diagnostic::Maybe<String> {
+  fn run_test(
+    name: &str,
+    folder_name: &str,
+  ) -> diagnostic::Maybe<(String, Vec<diagnostic::Diagnostic>)> {
     const FILENAME_EXTENSION: &str = "tails";
 
     let tests_path = std::env::current_dir()
@@ -101,7 +108,8 @@ mod tests {
       module_name: name.to_string(),
     };
 
-    lower_file(&source_file_contents, qualifier).map(|output| output.trim().to_string())
+    lower_file(&source_file_contents, qualifier)
+      .map(|(output, warnings)| (output.trim().to_string(), warnings))
   }
 
   fn run_passing_test(name: &str) {
@@ -119,7 +127,7 @@ mod tests {
       .join(name)
       .with_extension(LLVM_FILENAME_EXTENSION);
 
-    let actual_output = run_test(name, INPUT_FOLDER)
+    let (actual_output, _) = run_test(name, INPUT_FOLDER)
       .expect("there should be no error diagnostics produced on a passing test");
 
     let expected_output = if output_file_path.exists() {
@@ -143,7 +151,7 @@ mod tests {
     const FAILING_FOLDER: &str = "failing";
 
     match run_test(name, FAILING_FOLDER) {
-      Ok(llvm_ir_output) => {
+      Ok((llvm_ir_output, _)) => {
         println!("{}", llvm_ir_output);
         panic!("failing tests should not succeed");
       }
@@ -162,6 +170,15 @@ mod tests {
     }
   }
 
+  fn run_passing_test_for_warnings(name: &str) -> Vec<diagnostic::Diagnostic> {
+    const INPUT_FOLDER: &str = "passing";
+
+    let (_, warnings) = run_test(name, INPUT_FOLDER)
+      .expect("there should be no error diagnostics produced on a passing test");
+
+    warnings
+  }
+
   macro_rules! define_passing_tests {
     ($($name:ident),* $(,)?) => {
       $(
@@ -202,6 +219,8 @@ mod tests {
     closure,
     closure_capture_binding,
     closure_capture_parameter,
+    closure_capture_reference_binding,
+    closure_capture_reference_parameter,
     closure_capture_generic_parameter,
     closure_capture_self_calling,
     closure_capture_object,
@@ -266,8 +285,10 @@ mod tests {
     name_tick,
     object,
     object_nested,
+    object_field_write,
     object_field_shorthand,
     object_call_pass_binding,
+    parameter_pattern,
     playground,
     pipe,
     pipe_chain,
@@ -335,6 +356,7 @@ mod tests {
     reference_return,
     object_missing_field,
     constant_runtime_value,
+    constant_type_mismatch,
     declare_parameter_redefine,
     declare_parameter_redefine_function,
     declare_generic_redefine,
@@ -344,6 +366,148 @@ mod tests {
     call_site_invalid_direct_callee,
     call_site_invalid_indirect_callee,
     resolution_missing_function,
-    type_infer_mismatch
+    type_infer_mismatch,
+    binary_op_logical_non_bool
   );
+
+  #[test]
+  fn cast_redundant() {
+    assert!(run_passing_test_for_warnings("cast_redundant")
+      .iter()
+      .any(|diagnostic| matches!(diagnostic, diagnostic::Diagnostic::RedundantCast)));
+  }
+
+  #[test]
+  fn foreign_varargs_promotion() {
+    assert!(run_passing_test_for_warnings("foreign_varargs_promotion")
+      .iter()
+      .any(|diagnostic| matches!(
+        diagnostic,
+        diagnostic::Diagnostic::VariadicArgumentPromoted { .. }
+      )));
+  }
+
+  #[test]
+  fn foreign_varargs_pointer_decay() {
+    assert!(
+      run_passing_test_for_warnings("foreign_varargs_pointer_decay")
+        .iter()
+        .any(|diagnostic| matches!(
+          diagnostic,
+          diagnostic::Diagnostic::VariadicPointerDecayed(..)
+        ))
+    );
+  }
+
+  #[test]
+  fn cast_coercion() {
+    assert!(!run_passing_test_for_warnings("cast_coercion")
+      .iter()
+      .any(|diagnostic| matches!(diagnostic, diagnostic::Diagnostic::RedundantCast)));
+  }
+
+  #[test]
+  fn function_return_type_mismatch() {
+    run_failing_test("function_return_type_mismatch", &|diagnostics| {
+      diagnostics.iter().any(|diagnostic| match diagnostic {
+        diagnostic::Diagnostic::ConstraintOriginated {
+          origin, diagnostic, ..
+        } => {
+          origin.to_string() == "in the return type of a function"
+            && matches!(
+              diagnostic.as_ref(),
+              diagnostic::Diagnostic::TypeMismatch(..)
+            )
+        }
+        _ => false,
+      })
+    });
+  }
+
+  #[test]
+  fn match_arm_type_mismatch() {
+    run_failing_test("match_arm_type_mismatch", &|diagnostics| {
+      diagnostics.iter().any(|diagnostic| {
+        matches!(
+          diagnostic,
+          diagnostic::Diagnostic::ConstraintOriginated {
+            origin: inference::ConstraintOrigin::MatchArmCase { arm_index: 1 },
+            ..
+          }
+        )
+      })
+    });
+  }
+
+  #[test]
+  fn dependency_graph() {
+    let source_file_contents = std::fs::read_to_string(
+      std::env::current_dir()
+        .expect(BUG_CURRENT_FOLDER)
+        .join(TESTS_FOLDER)
+        .join("passing")
+        .join("function_return.tails"),
+    )
+    .expect(BUG_FILE_READ);
+
+    let qualifier = tails::symbol_table::Qualifier {
+      package_name: String::from(TESTS_FOLDER),
+      module_name: String::from("function_return"),
+    };
+
+    let mut parser = tails::parser::Parser::new(lex_and_filter(&source_file_contents).unwrap());
+    let module = parser.parse_module(qualifier.clone()).unwrap();
+    let test_package = tails::ast::Package::from([(qualifier, module)]);
+    let mut pass_manager = pass::PassManager::new(&test_package);
+
+    pass_manager.add_default_pass::<pass::DeclarePass>();
+    pass_manager.add_default_pass::<pass::LinkPass>();
+    pass_manager.add_default_pass::<pass::TypeInferencePass>();
+
+    let dependency_graph = pass_manager
+      .run(parser.get_id_count())
+      .dependency_graph
+      .expect("type inference should have produced a dependency graph");
+
+    // The `tests` function invokes `foo`, thus its type depends on it.
+    assert!(dependency_graph
+      .values()
+      .any(|dependency_ids| !dependency_ids.is_empty()));
+  }
+
+  #[test]
+  fn diagnostic_span() {
+    let source_file_contents = std::fs::read_to_string(
+      std::env::current_dir()
+        .expect(BUG_CURRENT_FOLDER)
+        .join(TESTS_FOLDER)
+        .join("failing")
+        .join("match_arm_type_mismatch.tails"),
+    )
+    .expect(BUG_FILE_READ);
+
+    let qualifier = tails::symbol_table::Qualifier {
+      package_name: String::from(TESTS_FOLDER),
+      module_name: String::from("match_arm_type_mismatch"),
+    };
+
+    let mut parser = tails::parser::Parser::new(lex_and_filter(&source_file_contents).unwrap());
+    let module = parser.parse_module(qualifier.clone()).unwrap();
+    let test_package = tails::ast::Package::from([(qualifier, module)]);
+    let mut pass_manager = pass::PassManager::new(&test_package);
+
+    pass_manager.add_default_pass::<pass::DeclarePass>();
+    pass_manager.add_default_pass::<pass::LinkPass>();
+    pass_manager.add_default_pass::<pass::TypeInferencePass>();
+
+    let span = pass_manager
+      .run(parser.get_id_count())
+      .diagnostics
+      .iter()
+      .find_map(|diagnostic| diagnostic.to_structured(parser.get_span_table()).span)
+      .expect("the mismatched match arm case should be located");
+
+    // The diagnostic is located at the case that mismatches the subject.
+    assert!(source_file_contents[span.start..].starts_with("'a'"));
+  }
 }